@@ -0,0 +1,119 @@
+// This file is part of css. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/css/master/COPYRIGHT. No part of predicator, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2017 The developers of css. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/css/master/COPYRIGHT.
+
+use {
+    super::BrowserVersion,
+    crate::domain::VendorPrefix,
+    std::fmt::{self, Display, Formatter},
+};
+
+/// A browser that can be targeted by the autoprefixer. The names follow [browserslist](https://github.com/browserslist/browserslist#browsers).
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum Browser {
+    /// Google Chrome, including Chrome for Android.
+    chrome,
+
+    /// Microsoft Edge.
+    edge,
+
+    /// Mozilla Firefox, including Firefox for Android.
+    firefox,
+
+    /// Internet Explorer.
+    ie,
+
+    /// Safari on iOS.
+    ios_saf,
+
+    /// Opera.
+    opera,
+
+    /// Safari on macOS.
+    safari,
+
+    /// Samsung Internet.
+    samsung,
+}
+
+impl Display for Browser {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        use self::Browser::*;
+
+        f.write_str(match *self {
+            chrome => "chrome",
+            edge => "edge",
+            firefox => "firefox",
+            ie => "ie",
+            ios_saf => "ios_saf",
+            opera => "opera",
+            safari => "safari",
+            samsung => "samsung",
+        })
+    }
+}
+
+impl Browser {
+    /// Finds a browser by its browserslist name or one of its aliases, ignoring case.
+    pub fn parse(name: &str) -> Option<Self> {
+        use self::Browser::*;
+
+        let browser = match_ignore_ascii_case! {
+            name,
+
+            "chrome" | "and_chr" | "chromeandroid" => chrome,
+
+            "edge" => edge,
+
+            "firefox" | "ff" | "and_ff" | "firefoxandroid" => firefox,
+
+            "ie" | "explorer" => ie,
+
+            "ios_saf" | "ios" => ios_saf,
+
+            "opera" => opera,
+
+            "safari" => safari,
+
+            "samsung" => samsung,
+
+            _ => return None,
+        };
+        Some(browser)
+    }
+
+    /// The vendor prefix used by a version of this browser. Edge used `-ms-` until it switched to Chromium in version 79.
+    pub fn vendor_prefix(&self, version: BrowserVersion) -> VendorPrefix {
+        use self::Browser::*;
+
+        match *self {
+            firefox => VendorPrefix::moz,
+            ie => VendorPrefix::ms,
+            edge if version < CHROMIUM_EDGE => VendorPrefix::ms,
+            chrome | edge | ios_saf | opera | safari | samsung => {
+                VendorPrefix::webkit
+            }
+        }
+    }
+
+    /// The vendor prefixes used by the versions of this browser from `minimum` up to, but excluding, `until`, or up to the latest version if `until` is `None`.
+    pub(crate) fn vendor_prefixes(
+        &self,
+        minimum: BrowserVersion,
+        until: Option<BrowserVersion>,
+    ) -> Vec<VendorPrefix> {
+        if until.is_some_and(|until| until <= minimum) {
+            return vec![];
+        }
+        let mut vendor_prefixes = vec![self.vendor_prefix(minimum)];
+        if *self == Browser::edge
+            && minimum < CHROMIUM_EDGE
+            && until.is_none_or(|until| until > CHROMIUM_EDGE)
+        {
+            vendor_prefixes.push(VendorPrefix::webkit);
+        }
+        vendor_prefixes
+    }
+}
+
+/// The first version of Edge that is based on Chromium.
+const CHROMIUM_EDGE: BrowserVersion = BrowserVersion::new(79, 0);
@@ -0,0 +1,247 @@
+// This file is part of css. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/css/master/COPYRIGHT. No part of predicator, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2017 The developers of css. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/css/master/COPYRIGHT.
+
+use {
+    super::{Browser, BrowserTargetsError, BrowserVersion},
+    crate::domain::VendorPrefix,
+    std::collections::BTreeMap,
+};
+
+/// The oldest version of every browser a stylesheet should work in. Browsers that are not listed are not targeted.
+///
+/// Targets are usually parsed from a query similar to [browserslist](https://github.com/browserslist/browserslist), queries that need usage statistics like `> 1%` or `last 2 versions` are not supported.
+///
+/// ```rust
+/// use lewp_css::autoprefixer::{Browser, BrowserTargets, BrowserVersion};
+///
+/// let targets =
+///     BrowserTargets::parse("chrome >= 100, firefox > 90, safari 14.1-15, ie <= 11")
+///         .unwrap();
+/// assert_eq!(targets.0[&Browser::chrome], BrowserVersion::new(100, 0));
+/// assert_eq!(targets.0[&Browser::firefox], BrowserVersion::new(91, 0));
+/// assert_eq!(targets.0[&Browser::safari], BrowserVersion::new(14, 1));
+/// assert_eq!(targets.0[&Browser::ie], BrowserVersion::new(0, 0));
+/// ```
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct BrowserTargets(pub BTreeMap<Browser, BrowserVersion>);
+
+impl BrowserTargets {
+    /// Parses a comma separated list of queries. Every query is of the form `<browser> <version>`, `<browser> <version>-<version>` or `<browser> <operator> <version>` where the operator is one of `>=`, `>`, `<=` and `<`.
+    pub fn parse(queries: &str) -> Result<Self, BrowserTargetsError> {
+        let mut targets = Self::default();
+        for query in queries.split(',').map(str::trim) {
+            if query.is_empty() {
+                continue;
+            }
+            let (browser, minimum) = Self::parse_query(query)?;
+            targets = targets.with(browser, minimum);
+        }
+        Ok(targets)
+    }
+
+    /// Adds a browser with the oldest version that should be supported. If the browser is already targeted, the older version of both is kept.
+    pub fn with(mut self, browser: Browser, version: BrowserVersion) -> Self {
+        let minimum = self.0.entry(browser).or_insert(version);
+        if version < *minimum {
+            *minimum = version;
+        }
+        self
+    }
+
+    /// The vendor prefixes needed by at least one of the targeted browsers for a feature, given the first version of each browser that supports the feature without its prefix or `None` if no version does yet.
+    pub(crate) fn vendor_prefixes(
+        &self,
+        unprefixed_since: &[(Browser, Option<BrowserVersion>)],
+    ) -> Vec<VendorPrefix> {
+        let mut vendor_prefixes = vec![];
+        for (browser, since) in unprefixed_since {
+            let Some(minimum) = self.0.get(browser) else {
+                continue;
+            };
+            for vendor_prefix in browser.vendor_prefixes(*minimum, *since) {
+                if !vendor_prefixes.contains(&vendor_prefix) {
+                    vendor_prefixes.push(vendor_prefix);
+                }
+            }
+        }
+        vendor_prefixes.sort();
+        vendor_prefixes
+    }
+
+    /// The vendor prefixes of a feature that none of the targeted browsers needs anymore.
+    pub(crate) fn obsolete_vendor_prefixes(
+        &self,
+        unprefixed_since: &[(Browser, Option<BrowserVersion>)],
+    ) -> Vec<VendorPrefix> {
+        let needed = self.vendor_prefixes(unprefixed_since);
+        let mut vendor_prefixes = vec![];
+        for (browser, since) in unprefixed_since {
+            for vendor_prefix in
+                browser.vendor_prefixes(BrowserVersion::default(), *since)
+            {
+                if !needed.contains(&vendor_prefix)
+                    && !vendor_prefixes.contains(&vendor_prefix)
+                {
+                    vendor_prefixes.push(vendor_prefix);
+                }
+            }
+        }
+        vendor_prefixes.sort();
+        vendor_prefixes
+    }
+
+    fn parse_query(
+        query: &str,
+    ) -> Result<(Browser, BrowserVersion), BrowserTargetsError> {
+        let invalid = || BrowserTargetsError::InvalidQuery(query.to_owned());
+        let (name, remainder) = match query.find(['<', '>']) {
+            Some(index) => query.split_at(index),
+            None => {
+                query.split_once(char::is_whitespace).ok_or_else(invalid)?
+            }
+        };
+        let browser = Browser::parse(name.trim()).ok_or_else(|| {
+            BrowserTargetsError::UnknownBrowser(name.trim().to_owned())
+        })?;
+        let remainder = remainder.trim();
+        let minimum = if let Some(version) = remainder.strip_prefix(">=") {
+            BrowserVersion::parse(version)?
+        } else if let Some(version) = remainder.strip_prefix('>') {
+            // the next version with the precision of the given one, so
+            // "chrome > 90" is 91 and "safari > 15.3" is 15.4
+            let next = BrowserVersion::parse(version)?;
+            let next = if version.contains('.') {
+                next.minor
+                    .checked_add(1)
+                    .map(|minor| BrowserVersion::new(next.major, minor))
+            } else {
+                next.major
+                    .checked_add(1)
+                    .map(|major| BrowserVersion::new(major, 0))
+            };
+            next.ok_or_else(|| {
+                BrowserTargetsError::InvalidVersion(version.to_owned())
+            })?
+        } else if let Some(version) = remainder
+            .strip_prefix("<=")
+            .or_else(|| remainder.strip_prefix('<'))
+        {
+            BrowserVersion::parse(version)?;
+            BrowserVersion::default()
+        } else {
+            let from = match remainder.split_once('-') {
+                Some((from, to)) => {
+                    BrowserVersion::parse(to)?;
+                    from
+                }
+                None => remainder,
+            };
+            BrowserVersion::parse(from)?
+        };
+        Ok((browser, minimum))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn minimum(query: &str) -> BrowserVersion {
+        let (browser, minimum) = BrowserTargets::parse_query(query).unwrap();
+        assert_eq!(browser, Browser::chrome, "{query}");
+        minimum
+    }
+
+    #[test]
+    fn parse_query_operators() {
+        assert_eq!(minimum("chrome 90"), BrowserVersion::new(90, 0));
+        assert_eq!(minimum("chrome >= 90"), BrowserVersion::new(90, 0));
+        assert_eq!(minimum("chrome>=90.1"), BrowserVersion::new(90, 1));
+        assert_eq!(minimum("chrome > 90"), BrowserVersion::new(91, 0));
+        assert_eq!(minimum("chrome > 90.1"), BrowserVersion::new(90, 2));
+        assert_eq!(minimum("chrome > 90.1.5"), BrowserVersion::new(90, 2));
+        assert_eq!(minimum("chrome <= 90"), BrowserVersion::default());
+        assert_eq!(minimum("chrome < 90"), BrowserVersion::default());
+        assert_eq!(minimum("chrome 80-90"), BrowserVersion::new(80, 0));
+        assert_eq!(minimum("Chrome 90"), BrowserVersion::new(90, 0));
+        assert_eq!(minimum("and_chr 90"), BrowserVersion::new(90, 0));
+    }
+
+    #[test]
+    fn parse_query_without_overflow() {
+        assert_eq!(
+            BrowserTargets::parse_query("chrome > 4294967295"),
+            Err(BrowserTargetsError::InvalidVersion(
+                " 4294967295".to_owned()
+            ))
+        );
+        assert_eq!(
+            BrowserTargets::parse_query("chrome > 1.4294967295"),
+            Err(BrowserTargetsError::InvalidVersion(
+                " 1.4294967295".to_owned()
+            ))
+        );
+        assert_eq!(
+            minimum("chrome >= 4294967295"),
+            BrowserVersion::new(u32::MAX, 0)
+        );
+    }
+
+    #[test]
+    fn reject_invalid_query() {
+        assert_eq!(
+            BrowserTargets::parse("chrome"),
+            Err(BrowserTargetsError::InvalidQuery("chrome".to_owned()))
+        );
+        assert_eq!(
+            BrowserTargets::parse("netscape >= 4"),
+            Err(BrowserTargetsError::UnknownBrowser("netscape".to_owned()))
+        );
+        assert_eq!(
+            BrowserTargets::parse("chrome >= 1.2.3.4"),
+            Err(BrowserTargetsError::InvalidVersion(" 1.2.3.4".to_owned()))
+        );
+        assert_eq!(
+            BrowserTargets::parse("chrome 80-"),
+            Err(BrowserTargetsError::InvalidVersion("".to_owned()))
+        );
+        assert_eq!(
+            BrowserTargets::parse("chrome >= latest"),
+            Err(BrowserTargetsError::InvalidVersion(" latest".to_owned()))
+        );
+    }
+
+    #[test]
+    fn parse_keeps_oldest_version() {
+        let targets =
+            BrowserTargets::parse("chrome >= 100, , safari 15, chrome > 90")
+                .unwrap();
+        assert_eq!(
+            targets,
+            BrowserTargets::default()
+                .with(Browser::chrome, BrowserVersion::new(91, 0))
+                .with(Browser::safari, BrowserVersion::new(15, 0))
+        );
+    }
+
+    #[test]
+    fn vendor_prefixes_of_edge() {
+        let unprefixed_since = [(Browser::edge, None)];
+        let vendor_prefixes = |query| {
+            BrowserTargets::parse(query)
+                .unwrap()
+                .vendor_prefixes(&unprefixed_since)
+        };
+        assert_eq!(
+            vendor_prefixes("edge >= 12"),
+            vec![VendorPrefix::webkit, VendorPrefix::ms]
+        );
+        assert_eq!(vendor_prefixes("edge >= 79"), vec![VendorPrefix::webkit]);
+        assert_eq!(
+            BrowserTargets::parse("edge >= 79")
+                .unwrap()
+                .obsolete_vendor_prefixes(&unprefixed_since),
+            vec![VendorPrefix::ms]
+        );
+    }
+}
@@ -0,0 +1,27 @@
+// This file is part of css. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/css/master/COPYRIGHT. No part of predicator, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2017 The developers of css. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/css/master/COPYRIGHT.
+
+quick_error! {
+    /// Represents all the things that can go wrong when parsing [BrowserTargets](super::BrowserTargets).
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum BrowserTargetsError
+    {
+        /// The browser is not known, see [Browser](super::Browser) for the supported names.
+        UnknownBrowser(name: String)
+        {
+            display("Unknown browser '{}'", name)
+        }
+
+        /// The version is not a number like `100` or `15.4`.
+        InvalidVersion(version: String)
+        {
+            display("Invalid browser version '{}'", version)
+        }
+
+        /// The query is not of the form `<browser> <version>`, `<browser> <operator> <version>` or `<browser> <version>-<version>`.
+        InvalidQuery(query: String)
+        {
+            display("Invalid browser query '{}'", query)
+        }
+    }
+}
@@ -0,0 +1,98 @@
+// This file is part of css. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/css/master/COPYRIGHT. No part of predicator, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2017 The developers of css. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/css/master/COPYRIGHT.
+
+use {
+    super::BrowserTargetsError,
+    std::fmt::{self, Display, Formatter},
+};
+
+/// The version of a browser. Patch versions are not relevant for vendor prefixes and are ignored.
+#[derive(Default, Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct BrowserVersion {
+    /// The major version, eg `15` for Safari 15.4.
+    pub major: u32,
+
+    /// The minor version, eg `4` for Safari 15.4.
+    pub minor: u32,
+}
+
+impl Display for BrowserVersion {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self.minor {
+            0 => write!(f, "{}", self.major),
+            minor => write!(f, "{}.{}", self.major, minor),
+        }
+    }
+}
+
+impl BrowserVersion {
+    /// Creates a new version.
+    pub const fn new(major: u32, minor: u32) -> Self {
+        Self { major, minor }
+    }
+
+    /// Parses a version like `100`, `15.4` or `15.4.1`.
+    pub fn parse(version: &str) -> Result<Self, BrowserTargetsError> {
+        let invalid =
+            || BrowserTargetsError::InvalidVersion(version.to_owned());
+        let mut numbers = version.trim().split('.');
+        let mut next = |required: bool| match numbers.next() {
+            None if !required => Ok(0),
+            Some(number)
+                if !number.is_empty()
+                    && number.bytes().all(|b| b.is_ascii_digit()) =>
+            {
+                number.parse::<u32>().map_err(|_| invalid())
+            }
+            _ => Err(invalid()),
+        };
+        let major = next(true)?;
+        let minor = next(false)?;
+        next(false)?;
+        if numbers.next().is_some() {
+            return Err(invalid());
+        }
+        Ok(Self::new(major, minor))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_version() {
+        assert_eq!(
+            BrowserVersion::parse("100"),
+            Ok(BrowserVersion::new(100, 0))
+        );
+        assert_eq!(
+            BrowserVersion::parse(" 15.4 "),
+            Ok(BrowserVersion::new(15, 4))
+        );
+        assert_eq!(
+            BrowserVersion::parse("15.4.1"),
+            Ok(BrowserVersion::new(15, 4))
+        );
+    }
+
+    #[test]
+    fn reject_invalid_version() {
+        for version in [
+            "",
+            "15.",
+            ".4",
+            "15..4",
+            "1.2.3.4",
+            "+15",
+            "15.4a",
+            "4294967296",
+        ] {
+            assert_eq!(
+                BrowserVersion::parse(version),
+                Err(BrowserTargetsError::InvalidVersion(version.to_owned())),
+                "{version}"
+            );
+        }
+    }
+}
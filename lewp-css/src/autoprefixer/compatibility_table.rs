@@ -0,0 +1,453 @@
+// This file is part of css. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/css/master/COPYRIGHT. No part of predicator, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2017 The developers of css. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/css/master/COPYRIGHT.
+
+//! The browsers that need a vendor prefix for a feature, together with the first version that supports it without the prefix or `None` if no version does yet.
+//! The data is taken from [caniuse](https://caniuse.com) and [MDN](https://developer.mozilla.org), features that are only prefixed in browsers older than the ones listed in [Browser] are left out.
+
+use {
+    super::{
+        Browser::{self, *},
+        BrowserVersion,
+    },
+    crate::domain::selectors::{
+        VendorPrefixablePseudoClassName,
+        VendorPrefixablePseudoElementName,
+    },
+};
+
+/// The browsers that need a vendor prefix for a feature.
+pub(crate) type UnprefixedSince =
+    &'static [(Browser, Option<BrowserVersion>)];
+
+/// A value, or the name of a function, that is prefixed in the values of some properties, eg `position: -webkit-sticky`.
+pub(crate) struct PrefixedValue {
+    /// The unprefixed value in ASCII lowercase.
+    pub(crate) name: &'static str,
+
+    /// The properties that the value is prefixed in, all properties if empty.
+    pub(crate) properties: &'static [&'static str],
+
+    pub(crate) unprefixed_since: UnprefixedSince,
+}
+
+const fn since(major: u32, minor: u32) -> Option<BrowserVersion> {
+    Some(BrowserVersion::new(major, minor))
+}
+
+const ANIMATION: UnprefixedSince = &[
+    (chrome, since(43, 0)),
+    (firefox, since(16, 0)),
+    (ios_saf, since(9, 0)),
+    (opera, since(30, 0)),
+    (safari, since(9, 0)),
+    (samsung, since(4, 0)),
+];
+
+const TRANSITION: UnprefixedSince = &[
+    (chrome, since(26, 0)),
+    (firefox, since(16, 0)),
+    (ios_saf, since(7, 0)),
+    (opera, since(15, 0)),
+    (safari, since(6, 1)),
+];
+
+const TRANSFORM: UnprefixedSince = &[
+    (chrome, since(36, 0)),
+    (firefox, since(16, 0)),
+    (ie, since(10, 0)),
+    (ios_saf, since(9, 0)),
+    (opera, since(23, 0)),
+    (safari, since(9, 0)),
+    (samsung, since(4, 0)),
+];
+
+const FLEXBOX: UnprefixedSince = &[
+    (chrome, since(29, 0)),
+    (ios_saf, since(9, 0)),
+    (opera, since(17, 0)),
+    (safari, since(9, 0)),
+];
+
+const COLUMNS: UnprefixedSince = &[
+    (chrome, since(50, 0)),
+    (firefox, since(52, 0)),
+    (ios_saf, since(9, 0)),
+    (opera, since(37, 0)),
+    (safari, since(9, 0)),
+    (samsung, since(5, 0)),
+];
+
+const MASK: UnprefixedSince = &[
+    (chrome, since(120, 0)),
+    (edge, since(120, 0)),
+    (ios_saf, since(15, 4)),
+    (opera, since(106, 0)),
+    (safari, since(15, 4)),
+    (samsung, since(25, 0)),
+];
+
+const TEXT_EMPHASIS: UnprefixedSince = &[
+    (chrome, since(99, 0)),
+    (edge, since(99, 0)),
+    (ios_saf, since(7, 0)),
+    (opera, since(85, 0)),
+    (safari, since(7, 0)),
+    (samsung, since(18, 0)),
+];
+
+const INTRINSIC_SIZE: UnprefixedSince = &[
+    (chrome, since(46, 0)),
+    (firefox, since(66, 0)),
+    (ios_saf, since(11, 0)),
+    (opera, since(33, 0)),
+    (safari, since(11, 0)),
+    (samsung, since(5, 0)),
+];
+
+const SIZING_PROPERTIES: &[&str] = &[
+    "width",
+    "min-width",
+    "max-width",
+    "height",
+    "min-height",
+    "max-height",
+    "inline-size",
+    "min-inline-size",
+    "max-inline-size",
+    "block-size",
+    "min-block-size",
+    "max-block-size",
+    "flex-basis",
+];
+
+/// Properties that are prefixed in some browsers, by their unprefixed name in ASCII lowercase.
+pub(crate) static PROPERTIES: &[(&str, UnprefixedSince)] = &[
+    ("animation", ANIMATION),
+    ("animation-delay", ANIMATION),
+    ("animation-direction", ANIMATION),
+    ("animation-duration", ANIMATION),
+    ("animation-fill-mode", ANIMATION),
+    ("animation-iteration-count", ANIMATION),
+    ("animation-name", ANIMATION),
+    ("animation-play-state", ANIMATION),
+    ("animation-timing-function", ANIMATION),
+    ("transition", TRANSITION),
+    ("transition-delay", TRANSITION),
+    ("transition-duration", TRANSITION),
+    ("transition-property", TRANSITION),
+    ("transition-timing-function", TRANSITION),
+    ("transform", TRANSFORM),
+    ("transform-origin", TRANSFORM),
+    ("transform-style", TRANSFORM),
+    ("perspective", TRANSFORM),
+    ("perspective-origin", TRANSFORM),
+    (
+        "backface-visibility",
+        &[
+            (chrome, since(36, 0)),
+            (firefox, since(16, 0)),
+            (ios_saf, since(15, 4)),
+            (opera, since(23, 0)),
+            (safari, since(15, 4)),
+            (samsung, since(4, 0)),
+        ],
+    ),
+    ("align-content", FLEXBOX),
+    ("align-items", FLEXBOX),
+    ("align-self", FLEXBOX),
+    ("flex", FLEXBOX),
+    ("flex-basis", FLEXBOX),
+    ("flex-direction", FLEXBOX),
+    ("flex-flow", FLEXBOX),
+    ("flex-grow", FLEXBOX),
+    ("flex-shrink", FLEXBOX),
+    ("flex-wrap", FLEXBOX),
+    ("justify-content", FLEXBOX),
+    ("order", FLEXBOX),
+    ("columns", COLUMNS),
+    ("column-count", COLUMNS),
+    ("column-fill", COLUMNS),
+    ("column-gap", COLUMNS),
+    ("column-rule", COLUMNS),
+    ("column-rule-color", COLUMNS),
+    ("column-rule-style", COLUMNS),
+    ("column-rule-width", COLUMNS),
+    ("column-width", COLUMNS),
+    ("mask", MASK),
+    ("mask-clip", MASK),
+    ("mask-image", MASK),
+    ("mask-origin", MASK),
+    ("mask-position", MASK),
+    ("mask-repeat", MASK),
+    ("mask-size", MASK),
+    ("text-emphasis", TEXT_EMPHASIS),
+    ("text-emphasis-color", TEXT_EMPHASIS),
+    ("text-emphasis-position", TEXT_EMPHASIS),
+    ("text-emphasis-style", TEXT_EMPHASIS),
+    (
+        "appearance",
+        &[
+            (chrome, since(84, 0)),
+            (edge, since(84, 0)),
+            (firefox, since(80, 0)),
+            (ios_saf, since(15, 4)),
+            (opera, since(70, 0)),
+            (safari, since(15, 4)),
+            (samsung, since(14, 0)),
+        ],
+    ),
+    (
+        "backdrop-filter",
+        &[(ios_saf, since(18, 0)), (safari, since(18, 0))],
+    ),
+    (
+        "box-decoration-break",
+        &[
+            (chrome, since(130, 0)),
+            (edge, since(130, 0)),
+            (ios_saf, None),
+            (opera, since(115, 0)),
+            (safari, None),
+            (samsung, None),
+        ],
+    ),
+    (
+        "clip-path",
+        &[
+            (chrome, since(55, 0)),
+            (ios_saf, since(13, 4)),
+            (opera, since(42, 0)),
+            (safari, since(13, 1)),
+            (samsung, since(6, 2)),
+        ],
+    ),
+    (
+        "filter",
+        &[
+            (chrome, since(53, 0)),
+            (ios_saf, since(9, 3)),
+            (opera, since(40, 0)),
+            (safari, since(9, 1)),
+            (samsung, since(6, 2)),
+        ],
+    ),
+    (
+        "hyphens",
+        &[
+            (firefox, since(43, 0)),
+            (ie, None),
+            (ios_saf, since(17, 0)),
+            (safari, since(17, 0)),
+        ],
+    ),
+    ("initial-letter", &[(ios_saf, None), (safari, None)]),
+    (
+        "print-color-adjust",
+        &[
+            (chrome, since(136, 0)),
+            (edge, since(136, 0)),
+            (ios_saf, since(15, 4)),
+            (opera, since(121, 0)),
+            (safari, since(15, 4)),
+            (samsung, None),
+        ],
+    ),
+    ("tab-size", &[(firefox, since(91, 0))]),
+    (
+        "text-orientation",
+        &[(ios_saf, since(14, 0)), (safari, since(14, 0))],
+    ),
+    ("text-size-adjust", &[(firefox, None), (ios_saf, None)]),
+    (
+        "user-select",
+        &[
+            (chrome, since(54, 0)),
+            (edge, since(79, 0)),
+            (firefox, since(69, 0)),
+            (ie, None),
+            (ios_saf, None),
+            (opera, since(41, 0)),
+            (safari, None),
+            (samsung, since(6, 2)),
+        ],
+    ),
+    (
+        "writing-mode",
+        &[
+            (chrome, since(48, 0)),
+            (ios_saf, since(10, 3)),
+            (opera, since(35, 0)),
+            (safari, since(10, 1)),
+        ],
+    ),
+];
+
+/// Values that are prefixed in some browsers.
+pub(crate) static VALUES: &[PrefixedValue] = &[
+    PrefixedValue {
+        name: "sticky",
+        properties: &["position"],
+        unprefixed_since: &[(ios_saf, since(13, 0)), (safari, since(13, 0))],
+    },
+    PrefixedValue {
+        name: "flex",
+        properties: &["display"],
+        unprefixed_since: FLEXBOX,
+    },
+    PrefixedValue {
+        name: "inline-flex",
+        properties: &["display"],
+        unprefixed_since: FLEXBOX,
+    },
+    PrefixedValue {
+        name: "fit-content",
+        properties: SIZING_PROPERTIES,
+        unprefixed_since: &[
+            (chrome, since(46, 0)),
+            (firefox, since(94, 0)),
+            (ios_saf, since(11, 0)),
+            (opera, since(33, 0)),
+            (safari, since(11, 0)),
+            (samsung, since(5, 0)),
+        ],
+    },
+    PrefixedValue {
+        name: "max-content",
+        properties: SIZING_PROPERTIES,
+        unprefixed_since: INTRINSIC_SIZE,
+    },
+    PrefixedValue {
+        name: "min-content",
+        properties: SIZING_PROPERTIES,
+        unprefixed_since: INTRINSIC_SIZE,
+    },
+    PrefixedValue {
+        name: "image-set",
+        properties: &[],
+        unprefixed_since: &[
+            (chrome, since(113, 0)),
+            (edge, since(113, 0)),
+            (ios_saf, since(17, 0)),
+            (opera, since(99, 0)),
+            (safari, since(17, 0)),
+            (samsung, since(24, 0)),
+        ],
+    },
+    PrefixedValue {
+        name: "grab",
+        properties: &["cursor"],
+        unprefixed_since: &[
+            (chrome, since(68, 0)),
+            (firefox, since(27, 0)),
+            (opera, since(55, 0)),
+            (safari, since(11, 0)),
+            (samsung, since(10, 1)),
+        ],
+    },
+    PrefixedValue {
+        name: "grabbing",
+        properties: &["cursor"],
+        unprefixed_since: &[
+            (chrome, since(68, 0)),
+            (firefox, since(27, 0)),
+            (opera, since(55, 0)),
+            (safari, since(11, 0)),
+            (samsung, since(10, 1)),
+        ],
+    },
+    PrefixedValue {
+        name: "zoom-in",
+        properties: &["cursor"],
+        unprefixed_since: &[
+            (chrome, since(37, 0)),
+            (firefox, since(24, 0)),
+            (opera, since(24, 0)),
+            (safari, since(9, 0)),
+        ],
+    },
+    PrefixedValue {
+        name: "zoom-out",
+        properties: &["cursor"],
+        unprefixed_since: &[
+            (chrome, since(37, 0)),
+            (firefox, since(24, 0)),
+            (opera, since(24, 0)),
+            (safari, since(9, 0)),
+        ],
+    },
+];
+
+/// Pseudo-elements that are prefixed in some browsers.
+pub(crate) static PSEUDO_ELEMENTS: &[(
+    VendorPrefixablePseudoElementName,
+    UnprefixedSince,
+)] = &[
+    (
+        VendorPrefixablePseudoElementName::backdrop,
+        &[(ie, None), (ios_saf, since(15, 4)), (safari, since(15, 4))],
+    ),
+    (
+        VendorPrefixablePseudoElementName::placeholder,
+        &[
+            (chrome, since(57, 0)),
+            (firefox, since(51, 0)),
+            (ie, None),
+            (ios_saf, since(10, 3)),
+            (opera, since(44, 0)),
+            (safari, since(10, 1)),
+            (samsung, since(7, 2)),
+        ],
+    ),
+    (
+        VendorPrefixablePseudoElementName::selection,
+        &[(firefox, since(62, 0))],
+    ),
+];
+
+/// Pseudo-classes that are prefixed in some browsers.
+pub(crate) static PSEUDO_CLASSES: &[(
+    VendorPrefixablePseudoClassName,
+    UnprefixedSince,
+)] = &[
+    (
+        VendorPrefixablePseudoClassName::any_link,
+        &[
+            (chrome, since(65, 0)),
+            (firefox, since(50, 0)),
+            (ios_saf, since(9, 0)),
+            (opera, since(52, 0)),
+            (safari, since(9, 0)),
+            (samsung, since(9, 2)),
+        ],
+    ),
+    (
+        VendorPrefixablePseudoClassName::fullscreen,
+        &[
+            (chrome, since(71, 0)),
+            (firefox, since(64, 0)),
+            (ie, None),
+            (opera, since(58, 0)),
+            (safari, since(16, 4)),
+            (samsung, since(10, 1)),
+        ],
+    ),
+    (
+        VendorPrefixablePseudoClassName::read_only,
+        &[(firefox, since(78, 0))],
+    ),
+    (
+        VendorPrefixablePseudoClassName::read_write,
+        &[(firefox, since(78, 0))],
+    ),
+];
+
+/// The browsers that need a prefix for `@keyframes`.
+pub(crate) static KEYFRAMES: UnprefixedSince = ANIMATION;
+
+/// Finds the browsers that need a prefix for a property.
+pub(crate) fn property(name: &str) -> Option<UnprefixedSince> {
+    PROPERTIES
+        .iter()
+        .find(|(property, _)| *property == name)
+        .map(|(_, unprefixed_since)| *unprefixed_since)
+}
@@ -0,0 +1,19 @@
+// This file is part of css. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/css/master/COPYRIGHT. No part of predicator, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2017 The developers of css. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/css/master/COPYRIGHT.
+
+//! Adds the vendor prefixes needed by a set of target browsers and removes obsolete ones, see [CssRules::autoprefix](crate::domain::CssRules::autoprefix).
+
+mod browser;
+mod browser_targets;
+mod browser_targets_error;
+mod browser_version;
+mod compatibility_table;
+mod prefix;
+
+pub(crate) use prefix::prefix_rules;
+pub use {
+    browser::Browser,
+    browser_targets::BrowserTargets,
+    browser_targets_error::BrowserTargetsError,
+    browser_version::BrowserVersion,
+};
@@ -0,0 +1,735 @@
+// This file is part of css. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/css/master/COPYRIGHT. No part of predicator, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2017 The developers of css. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/css/master/COPYRIGHT.
+
+use {
+    super::{
+        compatibility_table::{
+            self,
+            PrefixedValue,
+            UnprefixedSince,
+            PSEUDO_CLASSES,
+            PSEUDO_ELEMENTS,
+            VALUES,
+        },
+        BrowserTargets,
+    },
+    crate::domain::{
+        at_rules::{
+            container::ContainerAtRule,
+            document::DocumentAtRule,
+            keyframes::KeyframesAtRule,
+            layer::LayerAtRule,
+            media::MediaAtRule,
+            supports::SupportsAtRule,
+        },
+        properties::{
+            HasImportance,
+            PropertyDeclaration,
+            PropertyDeclarations,
+            SpecifiedValue,
+            UnparsedPropertyValue,
+        },
+        selectors::{
+            DeduplicatedSelectors,
+            OurSelector,
+            OurSelectorImpl,
+            VendorPrefixablePseudoClassName,
+            VendorPrefixablePseudoElementName,
+        },
+        CssRule,
+        CssRules,
+        StyleRule,
+        VendorPrefix,
+    },
+    cssparser::{ParseError, Parser, ParserInput, ToCss, Token},
+    selectors::parser::Component,
+    std::{
+        collections::{BTreeMap, HashMap, HashSet},
+        ops::Range,
+    },
+};
+
+type PseudoVendorPrefixes = (
+    HashMap<VendorPrefixablePseudoClassName, VendorPrefix>,
+    HashMap<VendorPrefixablePseudoElementName, VendorPrefix>,
+);
+
+/// Prefixes the declarations, selectors and `@keyframes` of the given rules and their child rules for the targets.
+///
+/// Prefixed copies of a style rule or `@keyframes` rule are inserted in front of it, unless they exist already. Prefixed rules that are obsolete are only removed if the unprefixed rule exists in the same list.
+pub(crate) fn prefix_rules(rules: &mut CssRules, targets: &BrowserTargets) {
+    let keyframes_prefixes =
+        targets.vendor_prefixes(compatibility_table::KEYFRAMES);
+
+    let mut existing = HashSet::new();
+    let mut obsolete = HashSet::new();
+    for rule in rules.0.iter() {
+        match *rule {
+            CssRule::Style(ref style_rule) => {
+                existing.insert(style_rule.selectors.to_css_string());
+                for selectors in
+                    prefixed_selectors(&style_rule.selectors, targets, true)
+                {
+                    obsolete
+                        .extend(selectors.0.iter().map(|s| s.to_css_string()));
+                }
+            }
+            CssRule::Keyframes(ref keyframes_rule) => {
+                if keyframes_rule.vendor_prefix.is_some() {
+                    existing.insert(keyframes_key(keyframes_rule));
+                    continue;
+                }
+                for vendor_prefix in targets
+                    .obsolete_vendor_prefixes(compatibility_table::KEYFRAMES)
+                {
+                    obsolete.insert(keyframes_key(&KeyframesAtRule {
+                        vendor_prefix: Some(vendor_prefix),
+                        ..keyframes_rule.clone()
+                    }));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    for mut rule in std::mem::take(&mut rules.0) {
+        match rule {
+            CssRule::Style(ref mut style_rule) => {
+                prefix_declarations(
+                    &mut style_rule.property_declarations,
+                    targets,
+                );
+                prefix_rules(&mut style_rule.rules, targets);
+                style_rule
+                    .selectors
+                    .0
+                    .retain(|s| !obsolete.contains(&s.to_css_string()));
+                if style_rule.selectors.0.is_empty() {
+                    continue;
+                }
+                for selectors in
+                    prefixed_selectors(&style_rule.selectors, targets, false)
+                {
+                    if existing.insert(selectors.to_css_string()) {
+                        rules.0.push(CssRule::Style(StyleRule {
+                            selectors,
+                            ..style_rule.clone()
+                        }));
+                    }
+                }
+            }
+            CssRule::Keyframes(ref mut keyframes_rule) => {
+                for keyframe in keyframes_rule.keyframes.iter_mut() {
+                    prefix_declarations(
+                        &mut keyframe.property_declarations,
+                        targets,
+                    );
+                }
+                if obsolete.contains(&keyframes_key(keyframes_rule)) {
+                    continue;
+                }
+                if keyframes_rule.vendor_prefix.is_none() {
+                    for vendor_prefix in keyframes_prefixes.iter() {
+                        let prefixed = KeyframesAtRule {
+                            vendor_prefix: Some(vendor_prefix.clone()),
+                            ..keyframes_rule.clone()
+                        };
+                        if existing.insert(keyframes_key(&prefixed)) {
+                            rules.0.push(CssRule::Keyframes(prefixed));
+                        }
+                    }
+                }
+            }
+            CssRule::Media(MediaAtRule { ref mut rules, .. })
+            | CssRule::Document(DocumentAtRule { ref mut rules, .. })
+            | CssRule::Supports(SupportsAtRule { ref mut rules, .. })
+            | CssRule::Container(ContainerAtRule { ref mut rules, .. })
+            | CssRule::Layer(LayerAtRule {
+                rules: Some(ref mut rules),
+                ..
+            }) => prefix_rules(rules, targets),
+            _ => {}
+        }
+        rules.0.push(rule);
+    }
+}
+
+/// Identifies a `@keyframes` rule by its vendor prefix and name.
+fn keyframes_key(keyframes_rule: &KeyframesAtRule) -> String {
+    let mut key = String::from("@");
+    if let Some(ref vendor_prefix) = keyframes_rule.vendor_prefix {
+        key.push_str(&vendor_prefix.to_css_string());
+    }
+    key.push_str("keyframes ");
+    key.push_str(&keyframes_rule.name.to_css_string());
+    key
+}
+
+/// Adds the prefixed declarations needed by the targets in front of their unprefixed declaration, unless they exist already.
+/// Prefixed declarations that are obsolete are only removed if the unprefixed declaration exists in the same block.
+fn prefix_declarations<I: HasImportance>(
+    declarations: &mut PropertyDeclarations<I>,
+    targets: &BrowserTargets,
+) {
+    let original = std::mem::take(&mut declarations.0);
+    for declaration in original.iter() {
+        if declaration.hasACustomPropertyName() {
+            declarations.0.push(declaration.clone());
+            continue;
+        }
+        if is_obsolete(declaration, &original, targets) {
+            continue;
+        }
+        if declaration.vendor_prefix.is_some() {
+            declarations.0.push(declaration.clone());
+            continue;
+        }
+        for prefixed in prefixed_declarations(declaration, targets) {
+            push_unless_exists(&mut declarations.0, &original, prefixed);
+        }
+        declarations.0.push(declaration.clone());
+        // follows the unprefixed declaration, as browsers that understand both use the last one
+        if let Some(transition) =
+            transition_with_prefixed_properties(declaration, targets)
+        {
+            push_unless_exists(&mut declarations.0, &original, transition);
+        }
+    }
+}
+
+/// Adds a declaration unless the original or the new declarations contain it already. Prefixed declarations are compared by their name, unprefixed ones by their name and value.
+fn push_unless_exists<I: HasImportance>(
+    declarations: &mut Vec<PropertyDeclaration<I>>,
+    original: &[PropertyDeclaration<I>],
+    declaration: PropertyDeclaration<I>,
+) {
+    let exists = original.iter().chain(declarations.iter()).any(|d| {
+        d.vendor_prefix == declaration.vendor_prefix
+            && d.name == declaration.name
+            && (declaration.vendor_prefix.is_some()
+                || d.value == declaration.value)
+    });
+    if !exists {
+        declarations.push(declaration);
+    }
+}
+
+/// The prefixed copies of an unprefixed declaration that are needed by the targets, one for each vendor prefix.
+fn prefixed_declarations<I: HasImportance>(
+    declaration: &PropertyDeclaration<I>,
+    targets: &BrowserTargets,
+) -> Vec<PropertyDeclaration<I>> {
+    let property_prefixes = compatibility_table::property(&declaration.name)
+        .map(|unprefixed_since| targets.vendor_prefixes(unprefixed_since))
+        .unwrap_or_default();
+    let values = prefixable_values(declaration)
+        .into_iter()
+        .filter(|(_, vendor_prefix, _)| vendor_prefix.is_none())
+        .map(|(offset, _, value)| {
+            (offset, targets.vendor_prefixes(value.unprefixed_since))
+        })
+        .collect::<Vec<_>>();
+
+    let properties = prefixable_properties(declaration)
+        .into_iter()
+        .filter(|(_, vendor_prefix, ..)| vendor_prefix.is_none())
+        .map(|(offset, _, _, unprefixed_since)| {
+            (offset, targets.vendor_prefixes(unprefixed_since))
+        })
+        .collect::<Vec<_>>();
+
+    let mut vendor_prefixes = property_prefixes.clone();
+    for vendor_prefix in values.iter().flat_map(|(_, prefixes)| prefixes) {
+        if !vendor_prefixes.contains(vendor_prefix) {
+            vendor_prefixes.push(vendor_prefix.clone());
+        }
+    }
+    vendor_prefixes.sort();
+
+    vendor_prefixes
+        .into_iter()
+        .map(|vendor_prefix| {
+            // property names in the value of a prefixed `transition` get the same prefix
+            let mut offsets = values
+                .iter()
+                .chain(
+                    properties
+                        .iter()
+                        .filter(|_| property_prefixes.contains(&vendor_prefix)),
+                )
+                .filter(|(_, prefixes)| prefixes.contains(&vendor_prefix))
+                .map(|(offset, _)| *offset)
+                .collect::<Vec<_>>();
+            offsets.sort();
+            let value = match declaration.value {
+                UnparsedPropertyValue::SpecifiedValue(ref value)
+                    if !offsets.is_empty() =>
+                {
+                    let mut originalCss = value.originalCss.clone();
+                    for offset in offsets.iter().rev() {
+                        originalCss.insert_str(
+                            *offset,
+                            &vendor_prefix.to_css_string(),
+                        );
+                    }
+                    UnparsedPropertyValue::SpecifiedValue(SpecifiedValue {
+                        originalCss,
+                    })
+                }
+                ref value => value.clone(),
+            };
+            PropertyDeclaration {
+                vendor_prefix: property_prefixes
+                    .contains(&vendor_prefix)
+                    .then_some(vendor_prefix),
+                name: declaration.name.clone(),
+                value,
+                importance: declaration.importance,
+            }
+        })
+        .collect()
+}
+
+/// An unprefixed copy of a `transition` or `transition-property` declaration that lists the property names needing a prefix for the targets once with every prefix in front of the unprefixed name, eg `transition:-webkit-transform 1s,transform 1s`.
+/// It is needed by the browsers that support `transition` without, but the listed property only with a prefix. `None` if no listed property needs a prefix or its prefixed names are listed already.
+fn transition_with_prefixed_properties<I: HasImportance>(
+    declaration: &PropertyDeclaration<I>,
+    targets: &BrowserTargets,
+) -> Option<PropertyDeclaration<I>> {
+    let UnparsedPropertyValue::SpecifiedValue(ref value) = declaration.value
+    else {
+        return None;
+    };
+    let properties = prefixable_properties(declaration);
+    let listed = properties
+        .iter()
+        .filter_map(|(_, vendor_prefix, name, _)| {
+            Some((vendor_prefix.clone()?, name.as_str()))
+        })
+        .collect::<HashSet<_>>();
+    let prefixed = properties
+        .iter()
+        .filter(|(_, vendor_prefix, ..)| vendor_prefix.is_none())
+        .flat_map(|(offset, _, name, unprefixed_since)| {
+            targets
+                .vendor_prefixes(unprefixed_since)
+                .into_iter()
+                .filter(|vendor_prefix| {
+                    !listed.contains(&(vendor_prefix.clone(), name.as_str()))
+                })
+                .map(|vendor_prefix| (*offset, vendor_prefix))
+        })
+        .collect::<Vec<_>>();
+    if prefixed.is_empty() {
+        return None;
+    }
+
+    let mut items = vec![];
+    for item in comma_separated_items(&value.originalCss) {
+        let mut offsets = BTreeMap::<VendorPrefix, Vec<usize>>::new();
+        for (offset, vendor_prefix) in prefixed.iter() {
+            if item.contains(offset) {
+                offsets
+                    .entry(vendor_prefix.clone())
+                    .or_default()
+                    .push(*offset - item.start);
+            }
+        }
+        let item = &value.originalCss[item];
+        for (vendor_prefix, offsets) in offsets {
+            let mut prefixed_item = item.to_owned();
+            for offset in offsets.iter().rev() {
+                prefixed_item
+                    .insert_str(*offset, &vendor_prefix.to_css_string());
+            }
+            items.push(prefixed_item);
+        }
+        items.push(item.to_owned());
+    }
+
+    Some(PropertyDeclaration {
+        vendor_prefix: None,
+        name: declaration.name.clone(),
+        value: UnparsedPropertyValue::SpecifiedValue(SpecifiedValue {
+            originalCss: items.join(","),
+        }),
+        importance: declaration.importance,
+    })
+}
+
+/// The byte ranges of the comma separated items of a value, without surrounding whitespace.
+fn comma_separated_items(value: &str) -> Vec<Range<usize>> {
+    let mut ranges = vec![];
+    let mut parserInput = ParserInput::new(value);
+    let mut input = Parser::new(&mut parserInput);
+    let mut start = 0;
+    while let Ok(token) = input.next() {
+        if *token == Token::Comma {
+            let end = input.position().byte_index();
+            ranges.push(start..end - 1);
+            start = end;
+        }
+    }
+    ranges.push(start..value.len());
+
+    ranges
+        .into_iter()
+        .map(|range| {
+            let item = &value[range.clone()];
+            let start = range.start + item.len() - item.trim_start().len();
+            start..start + item.trim().len()
+        })
+        .collect()
+}
+
+/// Whether the vendor prefix of a declaration, or of one of its values, is not needed by the targets anymore and the unprefixed declaration exists in the same block.
+fn is_obsolete<I: HasImportance>(
+    declaration: &PropertyDeclaration<I>,
+    declarations: &[PropertyDeclaration<I>],
+    targets: &BrowserTargets,
+) -> bool {
+    if let Some(ref vendor_prefix) = declaration.vendor_prefix {
+        return match compatibility_table::property(&declaration.name) {
+            None => false,
+            Some(unprefixed_since) => {
+                !targets
+                    .vendor_prefixes(unprefixed_since)
+                    .contains(vendor_prefix)
+                    && declarations.iter().any(|d| {
+                        d.vendor_prefix.is_none() && d.name == declaration.name
+                    })
+            }
+        };
+    }
+
+    let is_obsolete =
+        |vendor_prefix: Option<VendorPrefix>,
+         unprefixed_since: UnprefixedSince| {
+            match vendor_prefix {
+                None => false,
+                Some(vendor_prefix) => !targets
+                    .vendor_prefixes(unprefixed_since)
+                    .contains(&vendor_prefix),
+            }
+        };
+    let has_obsolete_value = prefixable_values(declaration).into_iter().any(
+        |(_, vendor_prefix, value)| {
+            is_obsolete(vendor_prefix, value.unprefixed_since)
+        },
+    ) || prefixable_properties(declaration)
+        .into_iter()
+        .any(|(_, vendor_prefix, _, unprefixed_since)| {
+            is_obsolete(vendor_prefix, unprefixed_since)
+        });
+    has_obsolete_value
+        && declarations.iter().any(|d| {
+            d.vendor_prefix.is_none()
+                && d.name == declaration.name
+                && prefixable_values(d)
+                    .iter()
+                    .all(|(_, vendor_prefix, _)| vendor_prefix.is_none())
+                && prefixable_properties(d)
+                    .iter()
+                    .all(|(_, vendor_prefix, ..)| vendor_prefix.is_none())
+        })
+}
+
+/// The property names in the value of a `transition` or `transition-property` declaration that are prefixed in some browsers, together with their byte offset in the value, their current vendor prefix and their unprefixed name.
+fn prefixable_properties<I: HasImportance>(
+    declaration: &PropertyDeclaration<I>,
+) -> Vec<(usize, Option<VendorPrefix>, String, UnprefixedSince)> {
+    if !matches!(&*declaration.name, "transition" | "transition-property") {
+        return vec![];
+    }
+
+    value_identifiers(declaration)
+        .into_iter()
+        .filter_map(|(offset, identifier)| {
+            let (vendor_prefix, name) = split_vendor_prefix(&identifier);
+            let unprefixed_since = compatibility_table::property(name)?;
+            Some((offset, vendor_prefix, name.to_owned(), unprefixed_since))
+        })
+        .collect()
+}
+
+/// The values of a declaration that are prefixed in some browsers, together with their byte offset in the value and their current vendor prefix.
+fn prefixable_values<I: HasImportance>(
+    declaration: &PropertyDeclaration<I>,
+) -> Vec<(usize, Option<VendorPrefix>, &'static PrefixedValue)> {
+    value_identifiers(declaration)
+        .into_iter()
+        .filter_map(|(offset, identifier)| {
+            let (vendor_prefix, name) = split_vendor_prefix(&identifier);
+            let value = VALUES.iter().find(|v| {
+                v.name == name
+                    && (v.properties.is_empty()
+                        || v.properties.contains(&&*declaration.name))
+            })?;
+            Some((offset, vendor_prefix, value))
+        })
+        .collect()
+}
+
+/// The identifiers and function names in the value of a declaration in ASCII lowercase, together with their byte offset.
+fn value_identifiers<I: HasImportance>(
+    declaration: &PropertyDeclaration<I>,
+) -> Vec<(usize, String)> {
+    let value = match declaration.value {
+        UnparsedPropertyValue::SpecifiedValue(ref value) => &value.originalCss,
+        UnparsedPropertyValue::CssWideKeyword(..) => return vec![],
+    };
+
+    let mut identifiers = vec![];
+    let mut parserInput = ParserInput::new(value);
+    collect_identifiers(&mut Parser::new(&mut parserInput), &mut identifiers);
+    identifiers
+}
+
+/// Collects the identifiers and function names in ASCII lowercase, together with their byte offset.
+fn collect_identifiers(
+    input: &mut Parser,
+    identifiers: &mut Vec<(usize, String)>,
+) {
+    loop {
+        input.skip_whitespace();
+        let offset = input.position().byte_index();
+        let token = match input.next() {
+            Ok(token) => token.clone(),
+            Err(_) => return,
+        };
+        match token {
+            Token::Ident(ref name) => {
+                identifiers.push((offset, name.to_ascii_lowercase()))
+            }
+            Token::Function(ref name) => {
+                identifiers.push((offset, name.to_ascii_lowercase()))
+            }
+            _ => {}
+        }
+        if matches!(
+            token,
+            Token::Function(_)
+                | Token::ParenthesisBlock
+                | Token::SquareBracketBlock
+                | Token::CurlyBracketBlock
+        ) {
+            let _ = input.parse_nested_block(|input| {
+                collect_identifiers(input, identifiers);
+                Ok::<_, ParseError<()>>(())
+            });
+        }
+    }
+}
+
+fn split_vendor_prefix(name: &str) -> (Option<VendorPrefix>, &str) {
+    for (prefix, vendor_prefix) in [
+        ("-webkit-", VendorPrefix::webkit),
+        ("-moz-", VendorPrefix::moz),
+        ("-ms-", VendorPrefix::ms),
+        ("-o-", VendorPrefix::o),
+    ] {
+        if let Some(name) = name.strip_prefix(prefix) {
+            return (Some(vendor_prefix), name);
+        }
+    }
+    (None, name)
+}
+
+/// Copies of the selectors that use the vendor prefixes of their pseudo-classes and pseudo-elements, one for each vendor prefix that is needed by the targets or, if `obsolete`, one for each vendor prefix that is not needed anymore.
+/// Every copy only contains the selectors that have been prefixed.
+fn prefixed_selectors(
+    selectors: &DeduplicatedSelectors,
+    targets: &BrowserTargets,
+    obsolete: bool,
+) -> Vec<DeduplicatedSelectors> {
+    let vendor_prefixes = |unprefixed_since: UnprefixedSince| {
+        if obsolete {
+            targets.obsolete_vendor_prefixes(unprefixed_since)
+        } else {
+            targets.vendor_prefixes(unprefixed_since)
+        }
+    };
+
+    let mut pseudos = BTreeMap::<VendorPrefix, PseudoVendorPrefixes>::new();
+    for component in selectors.0.iter().flat_map(|s| s.iter_raw_match_order()) {
+        match *component {
+            Component::NonTSPseudoClass(ref pseudo_class) => {
+                let Some((name, None)) = pseudo_class.vendor_prefixable_name()
+                else {
+                    continue;
+                };
+                let Some((_, unprefixed_since)) =
+                    PSEUDO_CLASSES.iter().find(|(n, _)| *n == name)
+                else {
+                    continue;
+                };
+                for vendor_prefix in vendor_prefixes(unprefixed_since) {
+                    pseudos
+                        .entry(vendor_prefix.clone())
+                        .or_default()
+                        .0
+                        .insert(name, vendor_prefix);
+                }
+            }
+            Component::PseudoElement(ref pseudo_element) => {
+                let Some((name, None)) =
+                    pseudo_element.vendor_prefixable_name()
+                else {
+                    continue;
+                };
+                let Some((_, unprefixed_since)) =
+                    PSEUDO_ELEMENTS.iter().find(|(n, _)| *n == name)
+                else {
+                    continue;
+                };
+                for vendor_prefix in vendor_prefixes(unprefixed_since) {
+                    pseudos
+                        .entry(vendor_prefix.clone())
+                        .or_default()
+                        .1
+                        .insert(name, vendor_prefix);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pseudos
+        .into_values()
+        .filter_map(|(pseudo_classes, pseudo_elements)| {
+            let prefixed = selectors
+                .0
+                .iter()
+                .filter_map(|selector| {
+                    OurSelectorImpl::reparse_with_vendor_prefix(
+                        selector,
+                        &pseudo_classes,
+                        &pseudo_elements,
+                    )
+                })
+                .collect::<Vec<OurSelector>>();
+            (!prefixed.is_empty()).then_some(DeduplicatedSelectors(prefixed))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::Stylesheet};
+
+    fn autoprefix(targets: &str, css: &str) -> String {
+        let targets = BrowserTargets::parse(targets).unwrap();
+        let mut stylesheet = Stylesheet::parse(css).unwrap();
+        stylesheet.rules.autoprefix(&targets);
+        stylesheet.rules.to_css_string()
+    }
+
+    #[test]
+    fn prefix_property_names_in_unprefixed_transition() {
+        assert_eq!(
+            autoprefix("safari >= 8", "a{transition:transform 1s}"),
+            "a{transition:transform 1s;transition:-webkit-transform 1s,transform 1s}"
+        );
+        assert_eq!(
+            autoprefix(
+                "chrome >= 30",
+                "a{transition:opacity 1s cubic-bezier(0, 0, 1, 1), transform 2s ease}"
+            ),
+            "a{transition:opacity 1s cubic-bezier(0, 0, 1, 1), transform 2s ease;transition:opacity 1s cubic-bezier(0, 0, 1, 1),-webkit-transform 2s ease,transform 2s ease}"
+        );
+        assert_eq!(
+            autoprefix(
+                "chrome >= 36, safari >= 9",
+                "a{transition:transform 1s}"
+            ),
+            "a{transition:transform 1s}"
+        );
+    }
+
+    #[test]
+    fn prefix_property_names_for_every_vendor_prefix() {
+        assert_eq!(
+            autoprefix("chrome >= 30, firefox >= 15", "a{transition:transform 1s}"),
+            "a{-moz-transition:-moz-transform 1s;transition:transform 1s;transition:-moz-transform 1s,-webkit-transform 1s,transform 1s}"
+        );
+    }
+
+    #[test]
+    fn keep_listed_prefixed_property_names() {
+        let css = "a{transition:-webkit-transform 1s,transform 1s}";
+        assert_eq!(autoprefix("chrome >= 30", css), css);
+        // no unprefixed declaration replaces it
+        let css = "a{transition:-webkit-transform 1s}";
+        assert_eq!(autoprefix("chrome >= 100", css), css);
+    }
+
+    #[test]
+    fn autoprefix_twice() {
+        let css = "a{transition:transform 1s}";
+        let prefixed = autoprefix("chrome >= 20", css);
+        assert_eq!(autoprefix("chrome >= 20", &prefixed), prefixed);
+        assert_eq!(autoprefix("chrome >= 100", &prefixed), css);
+    }
+
+    #[test]
+    fn prefix_for_each_target() {
+        let css =
+            "a{display:flex;transform:none;animation:spin 1s;user-select:none}";
+        for (targets, prefixed) in [
+            (
+                "chrome >= 20",
+                "a{display:-webkit-flex;display:flex;-webkit-transform:none;transform:none;-webkit-animation:spin 1s;animation:spin 1s;-webkit-user-select:none;user-select:none}",
+            ),
+            (
+                "chrome >= 40",
+                "a{display:flex;transform:none;-webkit-animation:spin 1s;animation:spin 1s;-webkit-user-select:none;user-select:none}",
+            ),
+            (
+                "chrome >= 100",
+                "a{display:flex;transform:none;animation:spin 1s;user-select:none}",
+            ),
+            (
+                "edge >= 12",
+                "a{display:flex;transform:none;animation:spin 1s;-ms-user-select:none;user-select:none}",
+            ),
+            (
+                "edge >= 79",
+                "a{display:flex;transform:none;animation:spin 1s;user-select:none}",
+            ),
+            (
+                "firefox >= 15",
+                "a{display:flex;-moz-transform:none;transform:none;-moz-animation:spin 1s;animation:spin 1s;-moz-user-select:none;user-select:none}",
+            ),
+            (
+                "firefox >= 100",
+                "a{display:flex;transform:none;animation:spin 1s;user-select:none}",
+            ),
+            (
+                "ie >= 9",
+                "a{display:flex;-ms-transform:none;transform:none;animation:spin 1s;-ms-user-select:none;user-select:none}",
+            ),
+            (
+                "ios_saf >= 8",
+                "a{display:-webkit-flex;display:flex;-webkit-transform:none;transform:none;-webkit-animation:spin 1s;animation:spin 1s;-webkit-user-select:none;user-select:none}",
+            ),
+            (
+                "opera >= 12",
+                "a{display:-webkit-flex;display:flex;-webkit-transform:none;transform:none;-webkit-animation:spin 1s;animation:spin 1s;-webkit-user-select:none;user-select:none}",
+            ),
+            (
+                "safari >= 8",
+                "a{display:-webkit-flex;display:flex;-webkit-transform:none;transform:none;-webkit-animation:spin 1s;animation:spin 1s;-webkit-user-select:none;user-select:none}",
+            ),
+            (
+                "safari >= 16",
+                "a{display:flex;transform:none;animation:spin 1s;-webkit-user-select:none;user-select:none}",
+            ),
+            (
+                "samsung >= 4",
+                "a{display:flex;transform:none;animation:spin 1s;-webkit-user-select:none;user-select:none}",
+            ),
+        ] {
+            assert_eq!(autoprefix(targets, css), prefixed, "{targets}");
+        }
+    }
+}
@@ -24,6 +24,11 @@ pub enum CustomParseError<'i> {
     // @charset
     UnexpectedCharsetAtRule,
 
+    // @container
+    InvalidContainerCondition(CowRcStr<'i>),
+    UnsupportedContainerSizeFeature(CowRcStr<'i>),
+    ContainerRangeComparisonsMustPointInTheSameDirection,
+
     // @counter-style
     UnsupportedCounterStyleProperty(CowRcStr<'i>),
     InvalidCounterStyleWithoutSymbols(System),
@@ -69,6 +74,9 @@ pub enum CustomParseError<'i> {
     ImportantIsNotAllowedInKeyframePropertyDeclarationValues,
     UnexpectedTokenWhenParsingZoom(Token<'i>),
 
+    // @layer
+    LayerBlockCanNotHaveMoreThanOneName,
+
     // @media
     InvalidMediaType(CowRcStr<'i>),
     DeprecatedMediaType(CowRcStr<'i>),
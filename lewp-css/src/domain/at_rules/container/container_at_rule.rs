@@ -0,0 +1,187 @@
+// This file is part of css. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/css/master/COPYRIGHT. No part of predicator, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2017 The developers of css. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/css/master/COPYRIGHT.
+
+use {
+    super::{Container, ContainerCondition},
+    crate::domain::{CssRule, CssRules, CustomIdent, HasCssRules},
+    cssparser::{Parser, ToCss},
+    std::fmt,
+};
+
+/// A [`@container`][container] rule.
+///
+/// ```
+/// use lewp_css::{
+///     domain::{
+///         at_rules::container::ContainerSize,
+///         units::conversions::SimplisticExampleOfConversion,
+///         CssRule,
+///     },
+///     Stylesheet,
+/// };
+///
+/// let css = "@container sidebar (width>=400px) and style(--theme:dark){h1{color: white}}";
+/// let stylesheet = Stylesheet::parse(css).unwrap();
+/// assert_eq!(stylesheet.to_css_string(false), css);
+///
+/// let rule = match &stylesheet.rules.0[0] {
+///     CssRule::Container(rule) => rule,
+///     _ => unreachable!(),
+/// };
+/// let mut container = ContainerSize {
+///     width: 480.0,
+///     height: 800.0,
+///     names: vec!["sidebar".into()],
+///     conversion: SimplisticExampleOfConversion::default(),
+///     ..Default::default()
+/// };
+/// container.styles.insert("--theme".into(), "dark".into());
+/// assert!(rule.evaluate(&container));
+///
+/// container.width = 320.0;
+/// assert!(!rule.evaluate(&container));
+/// ```
+///
+/// [container]: <https://drafts.csswg.org/css-contain-3/#container-rule>
+#[derive(Debug, Clone)]
+pub struct ContainerAtRule {
+    /// The name of the query container, if any.
+    pub name: Option<CustomIdent>,
+
+    /// The parsed condition. Without a condition, every query container with
+    /// the [name](Self::name) matches.
+    pub condition: Option<ContainerCondition>,
+
+    /// Child rules
+    pub rules: CssRules,
+}
+
+impl HasCssRules for ContainerAtRule {
+    #[inline(always)]
+    fn css_rules(&self) -> &CssRules {
+        &self.rules
+    }
+
+    #[inline(always)]
+    fn css_rules_mut(&mut self) -> &mut CssRules {
+        &mut self.rules
+    }
+
+    #[inline(always)]
+    fn css_rules_slice(&self) -> &[CssRule] {
+        &self.rules.0[..]
+    }
+
+    #[inline(always)]
+    fn css_rules_vec(&self) -> &Vec<CssRule> {
+        &self.rules.0
+    }
+
+    #[inline(always)]
+    fn css_rules_vec_mut(&mut self) -> &mut Vec<CssRule> {
+        &mut self.rules.0
+    }
+}
+
+impl ToCss for ContainerAtRule {
+    fn to_css<W: fmt::Write>(&self, dest: &mut W) -> fmt::Result {
+        dest.write_str("@container ")?;
+        if let Some(ref name) = self.name {
+            name.to_css(dest)?;
+        }
+        if let Some(ref condition) = self.condition {
+            if self.name.is_some() {
+                dest.write_char(' ')?;
+            }
+            condition.to_css(dest)?;
+        }
+        dest.write_char('{')?;
+        self.rules.to_css(dest)?;
+        dest.write_char('}')
+    }
+}
+
+impl ContainerAtRule {
+    /// Parse the optional container name in front of the condition.
+    ///
+    /// <https://drafts.csswg.org/css-contain-3/#typedef-container-name>
+    pub(crate) fn parse_name(input: &mut Parser) -> Option<CustomIdent> {
+        input
+            .r#try(|input| {
+                let ident = input.expect_ident_cloned()?;
+                CustomIdent::from_ident(&ident, &["none", "and", "not", "or"])
+            })
+            .ok()
+    }
+
+    /// Evaluate the name and the condition of this rule against the given query container.
+    pub fn evaluate<C: Container>(&self, container: &C) -> bool {
+        let name_match = match self.name {
+            Some(ref name) => container.name_matches(name),
+            None => true,
+        };
+        name_match
+            && self
+                .condition
+                .as_ref()
+                .is_none_or(|condition| condition.matches(container))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            domain::{
+                at_rules::container::ContainerSize,
+                units::conversions::SimplisticExampleOfConversion,
+            },
+            Stylesheet,
+        },
+    };
+
+    fn container_rule(stylesheet: &Stylesheet) -> &ContainerAtRule {
+        match stylesheet.rules.0[0] {
+            CssRule::Container(ref rule) => rule,
+            ref rule => panic!("Expected a @container rule, got {rule:?}"),
+        }
+    }
+
+    #[test]
+    fn parse_named_container_without_query() {
+        let css = "@container card{h1{color: red}}";
+        let stylesheet = Stylesheet::parse(css).unwrap();
+        assert_eq!(stylesheet.to_css_string(false), css);
+
+        let rule = container_rule(&stylesheet);
+        assert!(rule.condition.is_none());
+        let mut container = ContainerSize {
+            names: vec!["card".into()],
+            conversion: SimplisticExampleOfConversion::default(),
+            ..Default::default()
+        };
+        assert!(rule.evaluate(&container));
+        container.names = vec!["sidebar".into()];
+        assert!(!rule.evaluate(&container));
+    }
+
+    #[test]
+    fn parse_container_name_and_query() {
+        for css in [
+            "@container card (width>=400px){h1{color: red}}",
+            "@container (width>=400px){h1{color: red}}",
+            "@container not (width>=400px){h1{color: red}}",
+        ] {
+            let stylesheet = Stylesheet::parse(css).unwrap();
+            assert_eq!(stylesheet.to_css_string(false), css);
+            assert!(container_rule(&stylesheet).condition.is_some());
+        }
+    }
+
+    #[test]
+    fn reject_container_without_name_and_query() {
+        assert!(Stylesheet::parse("@container{h1{color: red}}").is_err());
+        assert!(Stylesheet::parse("@container none{h1{color: red}}").is_err());
+    }
+}
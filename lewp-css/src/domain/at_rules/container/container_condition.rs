@@ -0,0 +1,188 @@
+// This file is part of css. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/css/master/COPYRIGHT. No part of predicator, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2017 The developers of css. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/css/master/COPYRIGHT.
+
+use {
+    super::{
+        Container,
+        ContainerCondition::*,
+        ContainerSizeFeature,
+        ContainerStyleQuery,
+    },
+    crate::{parsers::ParserContext, CustomParseError},
+    cssparser::{
+        BasicParseError,
+        BasicParseErrorKind,
+        ParseError,
+        Parser,
+        ToCss,
+        Token::{Function, ParenthesisBlock},
+    },
+    std::fmt,
+};
+
+/// An @container condition
+///
+/// <https://drafts.csswg.org/css-contain-3/#typedef-container-condition>
+#[derive(Debug, Clone, PartialEq)]
+pub enum ContainerCondition {
+    /// `not (condition)`
+    Not(Box<ContainerCondition>),
+
+    /// `(condition)`
+    Parenthesized(Box<ContainerCondition>),
+
+    /// `(condition) and (condition) and (condition) ..`
+    And(Vec<ContainerCondition>),
+
+    /// `(condition) or (condition) or (condition) ..`
+    Or(Vec<ContainerCondition>),
+
+    /// `(width >= 400px)`
+    Feature(ContainerSizeFeature),
+
+    /// `style(--theme: dark)`
+    Style(ContainerStyleQuery),
+}
+
+impl ToCss for ContainerCondition {
+    fn to_css<W: fmt::Write>(&self, dest: &mut W) -> fmt::Result {
+        match *self {
+            Not(ref condition) => {
+                dest.write_str("not ")?;
+                condition.to_css(dest)
+            }
+
+            Parenthesized(ref condition) => {
+                dest.write_str("(")?;
+                condition.to_css(dest)?;
+                dest.write_str(")")
+            }
+
+            And(ref conditions) => {
+                let mut first = true;
+                for condition in conditions {
+                    if first {
+                        first = false;
+                    } else {
+                        dest.write_str(" and ")?;
+                    }
+                    condition.to_css(dest)?;
+                }
+                Ok(())
+            }
+
+            Or(ref conditions) => {
+                let mut first = true;
+                for condition in conditions {
+                    if first {
+                        first = false;
+                    } else {
+                        dest.write_str(" or ")?;
+                    }
+                    condition.to_css(dest)?;
+                }
+                Ok(())
+            }
+
+            Feature(ref feature) => feature.to_css(dest),
+
+            Style(ref query) => query.to_css(dest),
+        }
+    }
+}
+
+impl ContainerCondition {
+    /// Parse a condition
+    ///
+    /// <https://drafts.csswg.org/css-contain-3/#typedef-container-condition>
+    pub(crate) fn parse<'i, 't>(
+        context: &ParserContext,
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, CustomParseError<'i>>> {
+        if input.r#try(|i| i.expect_ident_matching("not")).is_ok() {
+            let inner = Self::parse_in_parentheses(context, input)?;
+            return Ok(Not(Box::new(inner)));
+        }
+
+        let in_parentheses = Self::parse_in_parentheses(context, input)?;
+
+        let (keyword, wrapper) = match input.r#try(|i| i.expect_ident_cloned())
+        {
+            // End of the condition
+            Err(_) => return Ok(in_parentheses),
+
+            Ok(ident) => {
+                match_ignore_ascii_case! {
+                    &ident,
+                    "and" => ("and", And as fn(_) -> _),
+                    "or" => ("or", Or as fn(_) -> _),
+                    _ => return Err(ParseError::from(CustomParseError::InvalidContainerCondition(ident.clone())))
+                }
+            }
+        };
+
+        let mut conditions = Vec::with_capacity(2);
+        conditions.push(in_parentheses);
+        loop {
+            conditions.push(Self::parse_in_parentheses(context, input)?);
+            if input
+                .r#try(|input| input.expect_ident_matching(keyword))
+                .is_err()
+            {
+                // Did not find the expected keyword.
+                // If we found some other token,
+                // it will be rejected by `Parser::parse_entirely` somewhere up the stack.
+                return Ok(wrapper(conditions));
+            }
+        }
+    }
+
+    /// Evaluate this condition and return whether it matches the given query container.
+    pub fn matches<C: Container>(&self, container: &C) -> bool {
+        match *self {
+            Not(ref condition) => !condition.matches(container),
+
+            Parenthesized(ref condition) => condition.matches(container),
+
+            And(ref conditions) => {
+                conditions.iter().all(|c| c.matches(container))
+            }
+
+            Or(ref conditions) => {
+                conditions.iter().any(|c| c.matches(container))
+            }
+
+            Feature(ref feature) => feature.matches(container),
+
+            Style(ref query) => container.style_matches(query),
+        }
+    }
+
+    /// <https://drafts.csswg.org/css-contain-3/#typedef-query-in-parens>
+    fn parse_in_parentheses<'i, 't>(
+        context: &ParserContext,
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, CustomParseError<'i>>> {
+        let token = input.next()?.clone();
+
+        match token {
+            ParenthesisBlock => input.parse_nested_block(|input| {
+                if let Ok(condition) =
+                    input.r#try(|input| Self::parse(context, input))
+                {
+                    return Ok(Parenthesized(Box::new(condition)));
+                }
+                ContainerSizeFeature::parse(context, input).map(Feature)
+            }),
+
+            Function(ref name) if name.eq_ignore_ascii_case("style") => input
+                .parse_nested_block(ContainerStyleQuery::parse)
+                .map(Style),
+
+            _ => Err(ParseError::from(BasicParseError {
+                kind: BasicParseErrorKind::UnexpectedToken(token),
+                location: input.state().source_location(),
+            })),
+        }
+    }
+}
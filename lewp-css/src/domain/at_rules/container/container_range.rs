@@ -0,0 +1,138 @@
+// This file is part of css. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/css/master/COPYRIGHT. No part of predicator, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2017 The developers of css. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/css/master/COPYRIGHT.
+
+use {cssparser::ToCss, std::fmt};
+
+/// A comparison of a size feature of a query container with one or two
+/// values, eg `(width >= 400px)` or `(400px < width <= 800px)`.
+///
+/// The legacy `min-` and `max-` prefixes are parsed as `AtLeast` and
+/// `AtMost`.
+///
+/// <https://drafts.csswg.org/mediaqueries-4/#mq-range-context>
+#[derive(Debug, Clone, PartialEq)]
+pub enum ContainerRange<T: ToCss> {
+    /// Less than the inner value.
+    LessThan(T),
+
+    /// At most the inner value.
+    AtMost(T),
+
+    /// Exactly the inner value.
+    Exact(T),
+
+    /// At least the inner value.
+    AtLeast(T),
+
+    /// Greater than the inner value.
+    GreaterThan(T),
+
+    /// Between the two values.
+    Between {
+        /// The lower bound.
+        lower: T,
+        /// Whether the lower bound matches.
+        lower_inclusive: bool,
+        /// The upper bound.
+        upper: T,
+        /// Whether the upper bound matches.
+        upper_inclusive: bool,
+    },
+}
+
+impl<T: ToCss> ContainerRange<T> {
+    /// Whether the given actual value of the size feature is within this range.
+    /// The values of the range are converted to the unit of the actual value by the given function, a value that can not be converted never matches.
+    pub fn matches<F: Fn(&T) -> Option<f32>>(
+        &self,
+        actual: f32,
+        evaluate: F,
+    ) -> bool {
+        use self::ContainerRange::*;
+
+        let compare = |value: &T, matches: fn(f32, f32) -> bool| {
+            evaluate(value).is_some_and(|value| matches(actual, value))
+        };
+
+        match *self {
+            LessThan(ref value) => compare(value, |a, v| a < v),
+
+            AtMost(ref value) => compare(value, |a, v| a <= v),
+
+            Exact(ref value) => compare(value, |a, v| a == v),
+
+            AtLeast(ref value) => compare(value, |a, v| a >= v),
+
+            GreaterThan(ref value) => compare(value, |a, v| a > v),
+
+            Between {
+                ref lower,
+                lower_inclusive,
+                ref upper,
+                upper_inclusive,
+            } => {
+                let lower_match = if lower_inclusive {
+                    compare(lower, |a, v| a >= v)
+                } else {
+                    compare(lower, |a, v| a > v)
+                };
+                let upper_match = if upper_inclusive {
+                    compare(upper, |a, v| a <= v)
+                } else {
+                    compare(upper, |a, v| a < v)
+                };
+                lower_match && upper_match
+            }
+        }
+    }
+
+    /// Serializes this range in parentheses for the size feature with the given name.
+    pub(crate) fn to_css_with_name<W: fmt::Write>(
+        &self,
+        name: &str,
+        dest: &mut W,
+    ) -> fmt::Result {
+        use self::ContainerRange::*;
+
+        #[inline(always)]
+        fn write<W: fmt::Write, T: ToCss>(
+            dest: &mut W,
+            name: &str,
+            operator: &str,
+            value: &T,
+        ) -> fmt::Result {
+            dest.write_char('(')?;
+            dest.write_str(name)?;
+            dest.write_str(operator)?;
+            value.to_css(dest)?;
+            dest.write_char(')')
+        }
+
+        match *self {
+            LessThan(ref value) => write(dest, name, "<", value),
+
+            AtMost(ref value) => write(dest, name, "<=", value),
+
+            Exact(ref value) => write(dest, name, ":", value),
+
+            AtLeast(ref value) => write(dest, name, ">=", value),
+
+            GreaterThan(ref value) => write(dest, name, ">", value),
+
+            Between {
+                ref lower,
+                lower_inclusive,
+                ref upper,
+                upper_inclusive,
+            } => {
+                dest.write_char('(')?;
+                lower.to_css(dest)?;
+                dest.write_str(if lower_inclusive { "<=" } else { "<" })?;
+                dest.write_str(name)?;
+                dest.write_str(if upper_inclusive { "<=" } else { "<" })?;
+                upper.to_css(dest)?;
+                dest.write_char(')')
+            }
+        }
+    }
+}
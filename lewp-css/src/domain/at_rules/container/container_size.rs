@@ -0,0 +1,154 @@
+// This file is part of css. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/css/master/COPYRIGHT. No part of predicator, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2017 The developers of css. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/css/master/COPYRIGHT.
+
+use {
+    super::{Container, ContainerRange, ContainerStyleQuery},
+    crate::domain::{
+        at_rules::media::{MediaOrientation, Ratio},
+        expressions::{CalculablePropertyValue, Expression},
+        numbers::{CssNumberNewType, CssSignedNumber},
+        units::{conversions::*, LengthUnit},
+        Atom,
+        CustomIdent,
+    },
+    std::collections::HashMap,
+};
+
+/// A query container with a known size, eg measured in a browser, that can be used to evaluate @container rules.
+/// Lengths are converted to px using the given conversion, see [SimplisticExampleOfConversion].
+#[derive(Default, Debug, Clone)]
+pub struct ContainerSize<Conversion> {
+    /// The width of the content box in px.
+    pub width: f32,
+
+    /// The height of the content box in px.
+    pub height: f32,
+
+    /// Whether the container has a vertical writing mode, so its inline size is its height.
+    pub vertical_writing_mode: bool,
+
+    /// The names in the `container-name` property of the container.
+    pub names: Vec<Atom>,
+
+    /// The computed values of the (custom) properties of the container by their name, used for style queries.
+    pub styles: HashMap<String, String>,
+
+    /// Used to convert relative lengths and expressions.
+    pub conversion: Conversion,
+}
+
+impl<Conversion> ContainerSize<Conversion>
+where
+    Conversion: FontRelativeLengthConversion<CssSignedNumber>
+        + ViewportPercentageLengthConversion<CssSignedNumber>
+        + PercentageConversion<CssSignedNumber>
+        + AttributeConversion<LengthUnit<CssSignedNumber>>
+        + CssVariableConversion,
+{
+    fn inline_size(&self) -> f32 {
+        if self.vertical_writing_mode {
+            self.height
+        } else {
+            self.width
+        }
+    }
+
+    fn block_size(&self) -> f32 {
+        if self.vertical_writing_mode {
+            self.width
+        } else {
+            self.height
+        }
+    }
+
+    fn length_matches(
+        &self,
+        actual: f32,
+        range: &ContainerRange<
+            CalculablePropertyValue<LengthUnit<CssSignedNumber>>,
+        >,
+    ) -> bool {
+        range.matches(actual, |value| match *value {
+            CalculablePropertyValue::Constant(ref length) => {
+                Some(length.to_px(&self.conversion).to_f32())
+            }
+            ref expression => {
+                expression.evaluate(&self.conversion).map(|v| v.to_f32())
+            }
+        })
+    }
+}
+
+impl<Conversion> Container for ContainerSize<Conversion>
+where
+    Conversion: FontRelativeLengthConversion<CssSignedNumber>
+        + ViewportPercentageLengthConversion<CssSignedNumber>
+        + PercentageConversion<CssSignedNumber>
+        + AttributeConversion<LengthUnit<CssSignedNumber>>
+        + CssVariableConversion,
+{
+    fn name_matches(&self, name: &CustomIdent) -> bool {
+        self.names.contains(&name.0)
+    }
+
+    fn width_matches(
+        &self,
+        width: &ContainerRange<
+            CalculablePropertyValue<LengthUnit<CssSignedNumber>>,
+        >,
+    ) -> bool {
+        self.length_matches(self.width, width)
+    }
+
+    fn height_matches(
+        &self,
+        height: &ContainerRange<
+            CalculablePropertyValue<LengthUnit<CssSignedNumber>>,
+        >,
+    ) -> bool {
+        self.length_matches(self.height, height)
+    }
+
+    fn inline_size_matches(
+        &self,
+        inline_size: &ContainerRange<
+            CalculablePropertyValue<LengthUnit<CssSignedNumber>>,
+        >,
+    ) -> bool {
+        self.length_matches(self.inline_size(), inline_size)
+    }
+
+    fn block_size_matches(
+        &self,
+        block_size: &ContainerRange<
+            CalculablePropertyValue<LengthUnit<CssSignedNumber>>,
+        >,
+    ) -> bool {
+        self.length_matches(self.block_size(), block_size)
+    }
+
+    fn aspect_ratio_matches(&self, ratio: &ContainerRange<Ratio>) -> bool {
+        if self.height == 0.0 {
+            return false;
+        }
+        ratio.matches(self.width / self.height, |value| {
+            Some(value.to_scalar() as f32)
+        })
+    }
+
+    fn orientation_matches(&self, orientation: MediaOrientation) -> bool {
+        // https://drafts.csswg.org/css-contain-3/#orientation
+        match orientation {
+            MediaOrientation::portrait => self.height >= self.width,
+            MediaOrientation::landscape => self.width > self.height,
+        }
+    }
+
+    fn style_matches(&self, style: &ContainerStyleQuery) -> bool {
+        match (self.styles.get(&style.property.0), &style.value) {
+            (Some(actual), Some(value)) => actual.trim() == value,
+            (Some(_), None) => true,
+            (None, _) => false,
+        }
+    }
+}
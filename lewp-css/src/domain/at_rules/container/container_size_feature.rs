@@ -0,0 +1,338 @@
+// This file is part of css. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/css/master/COPYRIGHT. No part of predicator, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2017 The developers of css. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/css/master/COPYRIGHT.
+
+use {
+    super::{Container, ContainerRange},
+    crate::{
+        domain::{
+            at_rules::media::{MediaOrientation, Ratio},
+            expressions::CalculablePropertyValue,
+            numbers::CssSignedNumber,
+            units::{LengthUnit, Unit},
+        },
+        parsers::{Parse, ParserContext},
+        CustomParseError,
+    },
+    cssparser::{CowRcStr, ParseError, Parser, ToCss, Token},
+    std::fmt,
+};
+
+/// A size feature of a query container, eg `(width >= 400px)`.
+///
+/// <https://drafts.csswg.org/css-contain-3/#size-container>
+#[derive(Debug, Clone, PartialEq)]
+pub enum ContainerSizeFeature {
+    /// <https://drafts.csswg.org/css-contain-3/#width>
+    Width(ContainerRange<CalculablePropertyValue<LengthUnit<CssSignedNumber>>>),
+
+    /// <https://drafts.csswg.org/css-contain-3/#height>
+    Height(
+        ContainerRange<CalculablePropertyValue<LengthUnit<CssSignedNumber>>>,
+    ),
+
+    /// <https://drafts.csswg.org/css-contain-3/#inline-size>
+    InlineSize(
+        ContainerRange<CalculablePropertyValue<LengthUnit<CssSignedNumber>>>,
+    ),
+
+    /// <https://drafts.csswg.org/css-contain-3/#block-size>
+    BlockSize(
+        ContainerRange<CalculablePropertyValue<LengthUnit<CssSignedNumber>>>,
+    ),
+
+    /// <https://drafts.csswg.org/css-contain-3/#aspect-ratio>
+    AspectRatio(ContainerRange<Ratio>),
+
+    /// <https://drafts.csswg.org/css-contain-3/#orientation>
+    Orientation(MediaOrientation),
+}
+
+impl ToCss for ContainerSizeFeature {
+    fn to_css<W: fmt::Write>(&self, dest: &mut W) -> fmt::Result {
+        use self::ContainerSizeFeature::*;
+
+        match *self {
+            Width(ref range) => range.to_css_with_name("width", dest),
+
+            Height(ref range) => range.to_css_with_name("height", dest),
+
+            InlineSize(ref range) => {
+                range.to_css_with_name("inline-size", dest)
+            }
+
+            BlockSize(ref range) => range.to_css_with_name("block-size", dest),
+
+            AspectRatio(ref range) => {
+                range.to_css_with_name("aspect-ratio", dest)
+            }
+
+            Orientation(ref value) => {
+                dest.write_str("(orientation:")?;
+                value.to_css(dest)?;
+                dest.write_char(')')
+            }
+        }
+    }
+}
+
+impl ContainerSizeFeature {
+    /// Parse the content of the parentheses of a size feature, in the plain form `width: 400px` or in the range form `width >= 400px` or `400px < width < 800px`.
+    pub(crate) fn parse<'i, 't>(
+        context: &ParserContext,
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, CustomParseError<'i>>> {
+        use self::{ContainerRange::*, ContainerSizeFeature::*};
+
+        let start = input.state();
+        let (name, value_first) =
+            match input.r#try(|input| input.expect_ident_cloned()) {
+                Ok(name) => (name, false),
+                Err(_) => {
+                    // the type of the value depends on the name of the feature that follows it
+                    let name = loop {
+                        if let Token::Ident(ref name) = *input.next()? {
+                            break name.clone();
+                        }
+                    };
+                    input.reset(&start);
+                    (name, true)
+                }
+            };
+
+        let length = |input: &mut Parser<'i, 't>| {
+            LengthUnit::parse_one_outside_calc_function(context, input)
+        };
+        let ratio = |input: &mut Parser<'i, 't>| Ratio::parse(context, input);
+
+        let feature = match (name.to_ascii_lowercase().as_str(), value_first) {
+            ("width", _) => {
+                Width(Self::parse_range(&name, value_first, input, length)?)
+            }
+
+            ("height", _) => {
+                Height(Self::parse_range(&name, value_first, input, length)?)
+            }
+
+            ("inline-size", _) => InlineSize(Self::parse_range(
+                &name,
+                value_first,
+                input,
+                length,
+            )?),
+
+            ("block-size", _) => {
+                BlockSize(Self::parse_range(&name, value_first, input, length)?)
+            }
+
+            ("aspect-ratio", _) => AspectRatio(Self::parse_range(
+                &name,
+                value_first,
+                input,
+                ratio,
+            )?),
+
+            ("min-width", false) => {
+                Width(AtLeast(Self::parse_plain(input, length)?))
+            }
+
+            ("max-width", false) => {
+                Width(AtMost(Self::parse_plain(input, length)?))
+            }
+
+            ("min-height", false) => {
+                Height(AtLeast(Self::parse_plain(input, length)?))
+            }
+
+            ("max-height", false) => {
+                Height(AtMost(Self::parse_plain(input, length)?))
+            }
+
+            ("min-inline-size", false) => {
+                InlineSize(AtLeast(Self::parse_plain(input, length)?))
+            }
+
+            ("max-inline-size", false) => {
+                InlineSize(AtMost(Self::parse_plain(input, length)?))
+            }
+
+            ("min-block-size", false) => {
+                BlockSize(AtLeast(Self::parse_plain(input, length)?))
+            }
+
+            ("max-block-size", false) => {
+                BlockSize(AtMost(Self::parse_plain(input, length)?))
+            }
+
+            ("min-aspect-ratio", false) => {
+                AspectRatio(AtLeast(Self::parse_plain(input, ratio)?))
+            }
+
+            ("max-aspect-ratio", false) => {
+                AspectRatio(AtMost(Self::parse_plain(input, ratio)?))
+            }
+
+            ("orientation", false) => {
+                Orientation(Self::parse_plain(input, MediaOrientation::parse)?)
+            }
+
+            _ => {
+                return Err(ParseError::from(
+                    CustomParseError::UnsupportedContainerSizeFeature(
+                        name.clone(),
+                    ),
+                ))
+            }
+        };
+        Ok(feature)
+    }
+
+    /// Evaluate this feature and return whether it matches the given query container.
+    pub fn matches<C: Container>(&self, container: &C) -> bool {
+        use self::ContainerSizeFeature::*;
+
+        match *self {
+            Width(ref range) => container.width_matches(range),
+
+            Height(ref range) => container.height_matches(range),
+
+            InlineSize(ref range) => container.inline_size_matches(range),
+
+            BlockSize(ref range) => container.block_size_matches(range),
+
+            AspectRatio(ref range) => container.aspect_ratio_matches(range),
+
+            Orientation(orientation) => {
+                container.orientation_matches(orientation)
+            }
+        }
+    }
+
+    /// `: value`
+    fn parse_plain<'i, 't, T, P>(
+        input: &mut Parser<'i, 't>,
+        parse_value: P,
+    ) -> Result<T, ParseError<'i, CustomParseError<'i>>>
+    where
+        P: Fn(
+            &mut Parser<'i, 't>,
+        ) -> Result<T, ParseError<'i, CustomParseError<'i>>>,
+    {
+        input.expect_colon()?;
+        parse_value(input)
+    }
+
+    /// `: value`, `operator value`, `value operator name` or `value operator name operator value`, the name itself has already been consumed if it is the first token.
+    fn parse_range<'i, 't, T, P>(
+        name: &CowRcStr<'i>,
+        value_first: bool,
+        input: &mut Parser<'i, 't>,
+        parse_value: P,
+    ) -> Result<ContainerRange<T>, ParseError<'i, CustomParseError<'i>>>
+    where
+        T: ToCss,
+        P: Fn(
+            &mut Parser<'i, 't>,
+        ) -> Result<T, ParseError<'i, CustomParseError<'i>>>,
+    {
+        use self::{ContainerRange::*, Operator::*};
+
+        if !value_first {
+            if input.r#try(|input| input.expect_colon()).is_ok() {
+                return Ok(Exact(parse_value(input)?));
+            }
+            let operator = Operator::parse(input)?;
+            return Ok(operator.range(parse_value(input)?));
+        }
+
+        let first = parse_value(input)?;
+        let first_operator = Operator::parse(input)?;
+        input.expect_ident_matching(name)?;
+        let second_operator = match input.r#try(Operator::parse) {
+            Ok(operator) => operator,
+            // `400px < width` is the same as `width > 400px`
+            Err(_) => return Ok(first_operator.reversed().range(first)),
+        };
+        let second = parse_value(input)?;
+        match (first_operator, second_operator) {
+            (Less | LessOrEqual, Less | LessOrEqual) => Ok(Between {
+                lower: first,
+                lower_inclusive: first_operator == LessOrEqual,
+                upper: second,
+                upper_inclusive: second_operator == LessOrEqual,
+            }),
+            (Greater | GreaterOrEqual, Greater | GreaterOrEqual) => {
+                Ok(Between {
+                    lower: second,
+                    lower_inclusive: second_operator == GreaterOrEqual,
+                    upper: first,
+                    upper_inclusive: first_operator == GreaterOrEqual,
+                })
+            }
+            _ => Err(ParseError::from(
+                CustomParseError::ContainerRangeComparisonsMustPointInTheSameDirection,
+            )),
+        }
+    }
+}
+
+/// <https://drafts.csswg.org/mediaqueries-4/#typedef-mf-comparison>
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    Less,
+    LessOrEqual,
+    Equal,
+    GreaterOrEqual,
+    Greater,
+}
+
+impl Operator {
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, CustomParseError<'i>>> {
+        use self::Operator::*;
+
+        let (strict, or_equal) = match *input.next()? {
+            Token::Delim('=') => return Ok(Equal),
+            Token::Delim('<') => (Less, LessOrEqual),
+            Token::Delim('>') => (Greater, GreaterOrEqual),
+            ref unexpectedToken => {
+                return CustomParseError::unexpectedToken(unexpectedToken)
+            }
+        };
+        // `<=` and `>=` must not contain whitespace
+        let equal =
+            input.r#try(|input| match *input.next_including_whitespace()? {
+                Token::Delim('=') => Ok(()),
+                ref unexpectedToken => {
+                    CustomParseError::unexpectedToken(unexpectedToken)
+                }
+            });
+        Ok(if equal.is_ok() { or_equal } else { strict })
+    }
+
+    /// The operator if the name and the value are swapped.
+    fn reversed(self) -> Self {
+        use self::Operator::*;
+
+        match self {
+            Less => Greater,
+            LessOrEqual => GreaterOrEqual,
+            Equal => Equal,
+            GreaterOrEqual => LessOrEqual,
+            Greater => Less,
+        }
+    }
+
+    /// The range of `name operator value`.
+    fn range<T: ToCss>(self, value: T) -> ContainerRange<T> {
+        use self::{ContainerRange::*, Operator::*};
+
+        match self {
+            Less => LessThan(value),
+            LessOrEqual => AtMost(value),
+            Equal => Exact(value),
+            GreaterOrEqual => AtLeast(value),
+            Greater => GreaterThan(value),
+        }
+    }
+}
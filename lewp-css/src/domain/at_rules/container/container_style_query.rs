@@ -0,0 +1,59 @@
+// This file is part of css. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/css/master/COPYRIGHT. No part of predicator, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2017 The developers of css. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/css/master/COPYRIGHT.
+
+use {
+    crate::{
+        domain::{at_rules::supports::consume_any_value, Atom},
+        CustomParseError,
+    },
+    cssparser::{ParseError, Parser, ToCss},
+    std::fmt,
+};
+
+/// A style query of a query container, eg `style(--theme: dark)`. Without a
+/// value, eg `style(--theme)`, the query matches if the property is set.
+///
+/// Only a single declaration is supported within `style()`.
+///
+/// <https://drafts.csswg.org/css-contain-3/#style-container>
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContainerStyleQuery {
+    /// The name of the (custom) property.
+    pub property: Atom,
+
+    /// The raw CSS of the value, if any.
+    pub value: Option<String>,
+}
+
+impl ToCss for ContainerStyleQuery {
+    fn to_css<W: fmt::Write>(&self, dest: &mut W) -> fmt::Result {
+        dest.write_str("style(")?;
+        self.property.to_css(dest)?;
+        if let Some(ref value) = self.value {
+            dest.write_char(':')?;
+            dest.write_str(value)?;
+        }
+        dest.write_char(')')
+    }
+}
+
+impl ContainerStyleQuery {
+    /// Parse the content of `style()`
+    pub(crate) fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, CustomParseError<'i>>> {
+        let property = Atom::from(input.expect_ident()?.as_ref());
+        if input.r#try(|input| input.expect_colon()).is_err() {
+            return Ok(Self {
+                property,
+                value: None,
+            });
+        }
+        let position = input.position();
+        consume_any_value(input)?;
+        Ok(Self {
+            property,
+            value: Some(input.slice_from(position).trim().to_owned()),
+        })
+    }
+}
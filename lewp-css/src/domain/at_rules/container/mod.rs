@@ -0,0 +1,20 @@
+// This file is part of css. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/css/master/COPYRIGHT. No part of predicator, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2017 The developers of css. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/css/master/COPYRIGHT.
+
+mod container_at_rule;
+mod container_condition;
+mod container_range;
+mod container_size;
+mod container_size_feature;
+mod container_style_query;
+mod query_container;
+
+pub use {
+    container_at_rule::ContainerAtRule,
+    container_condition::ContainerCondition,
+    container_range::ContainerRange,
+    container_size::ContainerSize,
+    container_size_feature::ContainerSizeFeature,
+    container_style_query::ContainerStyleQuery,
+    query_container::Container,
+};
@@ -0,0 +1,64 @@
+// This file is part of css. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/css/master/COPYRIGHT. No part of predicator, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2017 The developers of css. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/css/master/COPYRIGHT.
+
+use {
+    super::{ContainerRange, ContainerStyleQuery},
+    crate::domain::{
+        at_rules::media::{MediaOrientation, Ratio},
+        expressions::CalculablePropertyValue,
+        numbers::CssSignedNumber,
+        units::LengthUnit,
+        CustomIdent,
+    },
+};
+
+/// A trait that is used when evaluating CSS rules that are decided using the attributes of a query container, similar to [Device](crate::domain::at_rules::media::Device) for @media rules
+/// See [ContainerSize] for an implementation that uses a known size.
+///
+/// [ContainerSize]: super::ContainerSize
+pub trait Container {
+    /// Used when evaluating @container rules with a name
+    /// Does this container have this name in its `container-name` property?
+    fn name_matches(&self, name: &CustomIdent) -> bool;
+
+    /// <https://drafts.csswg.org/css-contain-3/#width>
+    fn width_matches(
+        &self,
+        width: &ContainerRange<
+            CalculablePropertyValue<LengthUnit<CssSignedNumber>>,
+        >,
+    ) -> bool;
+
+    /// <https://drafts.csswg.org/css-contain-3/#height>
+    fn height_matches(
+        &self,
+        height: &ContainerRange<
+            CalculablePropertyValue<LengthUnit<CssSignedNumber>>,
+        >,
+    ) -> bool;
+
+    /// <https://drafts.csswg.org/css-contain-3/#inline-size>
+    fn inline_size_matches(
+        &self,
+        inline_size: &ContainerRange<
+            CalculablePropertyValue<LengthUnit<CssSignedNumber>>,
+        >,
+    ) -> bool;
+
+    /// <https://drafts.csswg.org/css-contain-3/#block-size>
+    fn block_size_matches(
+        &self,
+        block_size: &ContainerRange<
+            CalculablePropertyValue<LengthUnit<CssSignedNumber>>,
+        >,
+    ) -> bool;
+
+    /// <https://drafts.csswg.org/css-contain-3/#aspect-ratio>
+    fn aspect_ratio_matches(&self, ratio: &ContainerRange<Ratio>) -> bool;
+
+    /// <https://drafts.csswg.org/css-contain-3/#orientation>
+    fn orientation_matches(&self, orientation: MediaOrientation) -> bool;
+
+    /// <https://drafts.csswg.org/css-contain-3/#style-container>
+    fn style_matches(&self, style: &ContainerStyleQuery) -> bool;
+}
@@ -30,7 +30,7 @@ impl Eq for KeyframePercentage {}
 impl ToCss for KeyframePercentage {
     fn to_css<W: fmt::Write>(&self, dest: &mut W) -> fmt::Result {
         if self.0.is_zero() {
-            dest.write_str("to")
+            dest.write_str("from")
         } else {
             self.0.to_css(dest)
         }
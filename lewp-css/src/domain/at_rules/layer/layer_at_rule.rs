@@ -0,0 +1,58 @@
+// This file is part of css. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/css/master/COPYRIGHT. No part of predicator, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2017 The developers of css. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/css/master/COPYRIGHT.
+
+use {super::LayerName, crate::domain::CssRules, cssparser::ToCss, std::fmt};
+
+/// A [`@layer`][layer] rule, either in its statement form that only
+/// declares the order of the layers, eg `@layer reset, base;`, or in its
+/// block form that adds the nested rules to a layer, eg
+/// `@layer base { h1 { color: red } }`.
+///
+/// ```
+/// use lewp_css::{domain::CssRule, Stylesheet};
+///
+/// let css = "@layer reset,framework.base;@layer framework.base{h1{color: red}}@layer{p{margin: 0}}";
+/// let stylesheet = Stylesheet::parse(css).unwrap();
+/// match &stylesheet.rules.0[0] {
+///     CssRule::Layer(rule) => assert!(rule.is_statement()),
+///     _ => unreachable!(),
+/// }
+/// assert_eq!(stylesheet.to_css_string(false), css);
+/// ```
+///
+/// [layer]: <https://drafts.csswg.org/css-cascade-5/#layering>
+#[derive(Debug, Clone)]
+pub struct LayerAtRule {
+    /// The names of the layers. A statement has at least one name, a block
+    /// has at most one. A block without a name is an anonymous layer.
+    pub names: Vec<LayerName>,
+
+    /// The nested rules of the block form, `None` for the statement form.
+    pub rules: Option<CssRules>,
+}
+
+impl LayerAtRule {
+    /// Whether this rule is a statement without nested rules.
+    #[inline(always)]
+    pub fn is_statement(&self) -> bool {
+        self.rules.is_none()
+    }
+}
+
+impl ToCss for LayerAtRule {
+    fn to_css<W: fmt::Write>(&self, dest: &mut W) -> fmt::Result {
+        dest.write_str("@layer")?;
+        for (index, name) in self.names.iter().enumerate() {
+            dest.write_str(if index == 0 { " " } else { "," })?;
+            name.to_css(dest)?;
+        }
+        match self.rules {
+            None => dest.write_char(';'),
+            Some(ref rules) => {
+                dest.write_char('{')?;
+                rules.to_css(dest)?;
+                dest.write_char('}')
+            }
+        }
+    }
+}
@@ -0,0 +1,91 @@
+// This file is part of css. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/css/master/COPYRIGHT. No part of predicator, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2017 The developers of css. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/css/master/COPYRIGHT.
+
+use {
+    crate::{
+        domain::{Atom, CustomIdent},
+        CustomParseError,
+    },
+    cssparser::{
+        BasicParseError,
+        BasicParseErrorKind,
+        ParseError,
+        Parser,
+        ToCss,
+        Token,
+    },
+    std::fmt,
+};
+
+/// The name of a cascade layer, eg `framework.base`. Every part of the name
+/// is a nested layer of the previous one.
+///
+/// <https://drafts.csswg.org/css-cascade-5/#typedef-layer-name>
+#[derive(Debug, Clone, Eq, Hash, PartialEq)]
+pub struct LayerName(pub Vec<Atom>);
+
+impl ToCss for LayerName {
+    fn to_css<W: fmt::Write>(&self, dest: &mut W) -> fmt::Result {
+        for (index, part) in self.0.iter().enumerate() {
+            if index != 0 {
+                dest.write_char('.')?;
+            }
+            part.to_css(dest)?;
+        }
+        Ok(())
+    }
+}
+
+impl LayerName {
+    /// Parses a comma separated list of layer names.
+    pub(crate) fn parse_list<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Vec<Self>, ParseError<'i, CustomParseError<'i>>> {
+        input.parse_comma_separated(Self::parse)
+    }
+
+    pub(crate) fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, CustomParseError<'i>>> {
+        let mut parts = vec![Self::parse_part(input.expect_ident()?)?];
+        loop {
+            // the parts are separated by a dot without any whitespace
+            let part = input.r#try(|input| {
+                match input.next_including_whitespace()? {
+                    Token::Delim('.') => (),
+                    t => {
+                        let t = t.clone();
+                        return Err(ParseError::from(BasicParseError {
+                            kind: BasicParseErrorKind::UnexpectedToken(t),
+                            location: input.state().source_location(),
+                        }));
+                    }
+                }
+                match input.next_including_whitespace()? {
+                    Token::Ident(ident) => {
+                        let ident = ident.clone();
+                        Self::parse_part(&ident)
+                    }
+                    t => {
+                        let t = t.clone();
+                        Err(ParseError::from(BasicParseError {
+                            kind: BasicParseErrorKind::UnexpectedToken(t),
+                            location: input.state().source_location(),
+                        }))
+                    }
+                }
+            });
+            match part {
+                Ok(part) => parts.push(part),
+                Err(_) => break,
+            }
+        }
+        Ok(LayerName(parts))
+    }
+
+    fn parse_part<'i>(
+        ident: &cssparser::CowRcStr<'i>,
+    ) -> Result<Atom, ParseError<'i, CustomParseError<'i>>> {
+        CustomIdent::from_ident(ident, &[]).map(|i| i.0)
+    }
+}
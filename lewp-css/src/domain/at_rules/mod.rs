@@ -1,12 +1,14 @@
 // This file is part of css. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/css/master/COPYRIGHT. No part of predicator, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
 // Copyright © 2017 The developers of css. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/css/master/COPYRIGHT.
 
+pub mod container;
 pub mod counter_style;
 pub mod document;
 pub mod font_face;
 pub mod font_feature_values;
 pub mod import;
 pub mod keyframes;
+pub mod layer;
 pub mod media;
 pub mod namespace;
 pub mod page;
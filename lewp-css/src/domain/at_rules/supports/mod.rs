@@ -2,13 +2,17 @@
 // Copyright © 2017 The developers of css. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/css/master/COPYRIGHT.
 
 mod consume_any_value;
+mod supported_features;
 mod supports_at_rule;
 mod supports_condition;
+mod supports_evaluator;
 mod supports_property_declaration;
 
 pub use {
     consume_any_value::consume_any_value,
+    supported_features::SupportedFeatures,
     supports_at_rule::SupportsAtRule,
     supports_condition::SupportsCondition,
+    supports_evaluator::SupportsEvaluator,
     supports_property_declaration::SupportsPropertyDeclaration,
 };
@@ -0,0 +1,210 @@
+// This file is part of css. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/css/master/COPYRIGHT. No part of predicator, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2017 The developers of css. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/css/master/COPYRIGHT.
+
+use {
+    super::SupportsEvaluator,
+    cssparser::{ParseError, Parser, ParserInput, Token},
+    std::collections::{HashMap, HashSet},
+};
+
+/// The features supported by a target browser, that can be used to evaluate @supports rules.
+/// All names and values are expected in ASCII lowercase.
+///
+/// ```rust
+/// use lewp_css::{
+///     cssparser::ToCss,
+///     domain::at_rules::supports::SupportedFeatures,
+///     Stylesheet,
+/// };
+///
+/// let features = SupportedFeatures {
+///     properties: ["display", "gap"].into_iter().map(String::from).collect(),
+///     values: [(
+///         "display".to_string(),
+///         ["block", "flex"].into_iter().map(String::from).collect(),
+///     )]
+///     .into(),
+///     selectors: [":is", ":hover"].into_iter().map(String::from).collect(),
+/// };
+/// let mut stylesheet = Stylesheet::parse(
+///     "@supports (display:flex) and (gap:1rem){a{display:flex}}@supports (display:grid){b{display:grid}}@supports selector(:has(a)) or (color:red){c{color:red}}",
+/// )
+/// .unwrap();
+/// stylesheet.rules.resolve_supports(&features);
+/// assert_eq!(stylesheet.rules.to_css_string(), "a{display:flex}");
+///
+/// // at-rules that end up empty are removed, unknown syntax is kept
+/// let mut stylesheet = Stylesheet::parse(
+///     "@media print{@supports (display:grid){a{display:grid}}}@supports font-tech(color-COLRv1){@supports (display:grid){b{display:grid}}}@supports font-tech(color-COLRv1){c{color:red}}",
+/// )
+/// .unwrap();
+/// stylesheet.rules.resolve_supports(&features);
+/// assert_eq!(
+///     stylesheet.rules.to_css_string(),
+///     "@supports font-tech(color-COLRv1){c{color:red}}"
+/// );
+/// ```
+#[derive(Default, Debug, Clone)]
+pub struct SupportedFeatures {
+    /// The names of the supported properties.
+    pub properties: HashSet<String>,
+
+    /// The supported values of a property. If a supported property is missing, all of its values are supported.
+    /// Functions are listed by their name, eg `fit-content` for `fit-content(20em)`.
+    pub values: HashMap<String, HashSet<String>>,
+
+    /// The supported pseudo-classes and pseudo-elements including their colons, eg `:has` or `::backdrop`, and `&` for the nesting selector.
+    pub selectors: HashSet<String>,
+}
+
+impl SupportsEvaluator for SupportedFeatures {
+    fn supports_declaration(
+        &self,
+        property: &str,
+        value: &str,
+    ) -> Option<bool> {
+        if property.starts_with("--") {
+            return Some(true);
+        }
+        if !self.properties.contains(property) {
+            return Some(false);
+        }
+        let values = match self.values.get(property) {
+            None => return Some(true),
+            Some(values) => values,
+        };
+        let value = value.to_ascii_lowercase();
+        if matches!(value.as_str(), "inherit" | "initial" | "unset" | "revert")
+        {
+            return Some(true);
+        }
+        Some(values.iter().any(|v| {
+            value == *v
+                || value
+                    .strip_prefix(v.as_str())
+                    .is_some_and(|arguments| arguments.starts_with('('))
+        }))
+    }
+
+    fn supports_selector(&self, selector: &str) -> Option<bool> {
+        // `selector()` without a selector is invalid
+        if selector.trim().is_empty() {
+            return Some(false);
+        }
+        let mut parserInput = ParserInput::new(selector);
+        let mut input = Parser::new(&mut parserInput);
+        let mut names = Vec::new();
+        Self::collect_selector_features(&mut input, &mut names);
+        Some(names.iter().all(|name| self.selectors.contains(name)))
+    }
+}
+
+impl SupportedFeatures {
+    /// Collects the names of all pseudo-classes, pseudo-elements and nesting selectors, including the ones in arguments like `:is(:hover)`.
+    fn collect_selector_features(input: &mut Parser, names: &mut Vec<String>) {
+        let mut colons = 0;
+        while let Ok(token) = input.next_including_whitespace() {
+            let token = token.clone();
+            match token {
+                Token::Colon => {
+                    colons += 1;
+                    continue;
+                }
+                Token::Ident(ref name) | Token::Function(ref name)
+                    if colons > 0 =>
+                {
+                    names.push(format!(
+                        "{}{}",
+                        ":".repeat(colons),
+                        name.to_ascii_lowercase()
+                    ))
+                }
+                Token::Delim('&') => names.push("&".to_string()),
+                _ => {}
+            }
+            colons = 0;
+            if matches!(
+                token,
+                Token::Function(_)
+                    | Token::ParenthesisBlock
+                    | Token::SquareBracketBlock
+            ) {
+                let _: Result<(), ParseError<()>> =
+                    input.parse_nested_block(|input| {
+                        Self::collect_selector_features(input, names);
+                        Ok(())
+                    });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn features() -> SupportedFeatures {
+        SupportedFeatures {
+            properties: ["display"].into_iter().map(String::from).collect(),
+            values: [(
+                "display".to_string(),
+                ["block", "fit-content"]
+                    .into_iter()
+                    .map(String::from)
+                    .collect(),
+            )]
+            .into(),
+            selectors: [":is", ":hover", "::before", "&"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn supports_declaration() {
+        let features = features();
+        assert_eq!(
+            features.supports_declaration("display", "block"),
+            Some(true)
+        );
+        assert_eq!(
+            features.supports_declaration("display", "BLOCK"),
+            Some(true)
+        );
+        assert_eq!(
+            features.supports_declaration("display", "fit-content(2em)"),
+            Some(true)
+        );
+        assert_eq!(
+            features.supports_declaration("display", "fit-contents"),
+            Some(false)
+        );
+        assert_eq!(
+            features.supports_declaration("display", "grid"),
+            Some(false)
+        );
+        assert_eq!(
+            features.supports_declaration("display", "unset"),
+            Some(true)
+        );
+        assert_eq!(features.supports_declaration("gap", "1rem"), Some(false));
+        assert_eq!(features.supports_declaration("--gap", "1rem"), Some(true));
+    }
+
+    #[test]
+    fn supports_selector() {
+        let features = features();
+        assert_eq!(features.supports_selector("a > b"), Some(true));
+        assert_eq!(features.supports_selector(""), Some(false));
+        assert_eq!(features.supports_selector(" "), Some(false));
+        assert_eq!(features.supports_selector("a:HOVER"), Some(true));
+        assert_eq!(features.supports_selector("a::before"), Some(true));
+        assert_eq!(features.supports_selector("a:before"), Some(false));
+        assert_eq!(features.supports_selector("& b"), Some(true));
+        assert_eq!(features.supports_selector(":is(a, :hover)"), Some(true));
+        assert_eq!(features.supports_selector(":is(a, :has(b))"), Some(false));
+        assert_eq!(features.supports_selector("a[title=':has']"), Some(true));
+        assert_eq!(features.supports_selector("a:focus-visible"), Some(false));
+    }
+}
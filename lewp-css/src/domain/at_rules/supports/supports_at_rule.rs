@@ -2,7 +2,7 @@
 // Copyright © 2017 The developers of css. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/css/master/COPYRIGHT.
 
 use {
-    super::SupportsCondition,
+    super::{SupportsCondition, SupportsEvaluator},
     crate::domain::{CssRule, CssRules, HasCssRules},
     cssparser::ToCss,
     std::fmt,
@@ -56,3 +56,13 @@ impl ToCss for SupportsAtRule {
         dest.write_char('}')
     }
 }
+
+impl SupportsAtRule {
+    /// Evaluate the condition of this rule, `None` if it can not be decided.
+    pub fn evaluate<E: SupportsEvaluator>(
+        &self,
+        evaluator: &E,
+    ) -> Option<bool> {
+        self.condition.evaluate(evaluator)
+    }
+}
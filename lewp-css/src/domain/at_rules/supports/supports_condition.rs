@@ -5,6 +5,7 @@ use {
     super::{
         consume_any_value::consume_any_value,
         SupportsCondition::*,
+        SupportsEvaluator,
         SupportsPropertyDeclaration,
     },
     crate::CustomParseError,
@@ -95,6 +96,102 @@ impl ToCss for SupportsCondition {
 }
 
 impl SupportsCondition {
+    /// Evaluate this condition, `None` if it can not be decided. Conditions combined with `and` and `or` are decided as soon as one of them is not respectively is supported.
+    ///
+    /// ```rust
+    /// use lewp_css::{
+    ///     domain::{at_rules::supports::SupportedFeatures, CssRule},
+    ///     Stylesheet,
+    /// };
+    ///
+    /// let features = SupportedFeatures {
+    ///     properties: ["display"].into_iter().map(String::from).collect(),
+    ///     selectors: [":is"].into_iter().map(String::from).collect(),
+    ///     ..Default::default()
+    /// };
+    /// let evaluate = |condition: &str| {
+    ///     let stylesheet =
+    ///         Stylesheet::parse(&format!("@supports {condition}{{}}")).unwrap();
+    ///     match &stylesheet.rules.0[0] {
+    ///         CssRule::Supports(rule) => rule.condition.evaluate(&features),
+    ///         _ => unreachable!(),
+    ///     }
+    /// };
+    /// assert_eq!(evaluate("(display:grid)"), Some(true));
+    /// assert_eq!(evaluate("not (gap:1rem)"), Some(true));
+    /// assert_eq!(evaluate("(gap:1rem) or (display:grid)"), Some(true));
+    /// assert_eq!(evaluate("(gap:1rem) and (display:grid)"), Some(false));
+    /// assert_eq!(evaluate("selector(:is(a, b))"), Some(true));
+    /// assert_eq!(evaluate("not selector(:has(a))"), Some(true));
+    ///
+    /// // unknown syntax can not be decided, unless the other conditions decide
+    /// assert_eq!(evaluate("font-tech(color-COLRv1)"), None);
+    /// assert_eq!(evaluate("not font-tech(color-COLRv1)"), None);
+    /// assert_eq!(evaluate("(font-tech(color-COLRv1))"), None);
+    /// assert_eq!(evaluate("font-tech(color-COLRv1) or (gap:1rem)"), None);
+    /// assert_eq!(evaluate("font-tech(color-COLRv1) or (display:grid)"), Some(true));
+    /// assert_eq!(evaluate("font-tech(color-COLRv1) and (gap:1rem)"), Some(false));
+    /// ```
+    pub fn evaluate<E: SupportsEvaluator>(
+        &self,
+        evaluator: &E,
+    ) -> Option<bool> {
+        match *self {
+            Not(ref condition) => {
+                condition.evaluate(evaluator).map(|supported| !supported)
+            }
+
+            Parenthesized(ref condition) => condition.evaluate(evaluator),
+
+            And(ref conditions) => {
+                Self::evaluate_all(conditions, evaluator, false)
+            }
+
+            Or(ref conditions) => {
+                Self::evaluate_all(conditions, evaluator, true)
+            }
+
+            Declaration(ref declaration) => evaluator.supports_declaration(
+                &declaration.property(),
+                declaration.value(),
+            ),
+
+            FutureSyntax(ref value) => {
+                let selector = value
+                    .get(..9)
+                    .filter(|function| {
+                        function.eq_ignore_ascii_case("selector(")
+                    })
+                    .and_then(|_| value[9..].strip_suffix(')'));
+                match selector {
+                    Some(selector) => {
+                        evaluator.supports_selector(selector.trim())
+                    }
+                    None => evaluator.supports_future_syntax(value),
+                }
+            }
+        }
+    }
+
+    /// Evaluates all conditions, the result is `decisive` as soon as one of them is.
+    fn evaluate_all<E: SupportsEvaluator>(
+        conditions: &[SupportsCondition],
+        evaluator: &E,
+        decisive: bool,
+    ) -> Option<bool> {
+        let mut result = Some(!decisive);
+        for condition in conditions {
+            match condition.evaluate(evaluator) {
+                Some(supported) if supported == decisive => {
+                    return Some(decisive)
+                }
+                Some(_) => {}
+                None => result = None,
+            }
+        }
+        result
+    }
+
     /// Parse a condition
     ///
     /// <https://drafts.csswg.org/css-conditional/#supports_condition>
@@ -194,3 +291,111 @@ impl SupportsCondition {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{domain::CssRule, Stylesheet},
+    };
+
+    /// Supports `display`, does not support `float` and can not decide any
+    /// other property. Selectors are recorded and can not be decided.
+    struct Evaluator;
+
+    impl SupportsEvaluator for Evaluator {
+        fn supports_declaration(
+            &self,
+            property: &str,
+            _value: &str,
+        ) -> Option<bool> {
+            match property {
+                "display" => Some(true),
+                "float" => Some(false),
+                _ => None,
+            }
+        }
+
+        fn supports_selector(&self, selector: &str) -> Option<bool> {
+            match selector {
+                "a" => Some(true),
+                "" => Some(false),
+                _ => None,
+            }
+        }
+
+        fn supports_future_syntax(&self, syntax: &str) -> Option<bool> {
+            match syntax {
+                "font-tech(color-colrv1)" => Some(true),
+                _ => None,
+            }
+        }
+    }
+
+    fn evaluate(condition: &str) -> Option<bool> {
+        let stylesheet =
+            Stylesheet::parse(&format!("@supports {condition}{{}}")).unwrap();
+        match stylesheet.rules.0[0] {
+            CssRule::Supports(ref rule) => rule.condition.evaluate(&Evaluator),
+            ref rule => panic!("Expected a @supports rule, got {rule:?}"),
+        }
+    }
+
+    #[test]
+    fn evaluate_not_with_undecided_condition() {
+        assert_eq!(evaluate("not (display:grid)"), Some(false));
+        assert_eq!(evaluate("not (float:left)"), Some(true));
+        assert_eq!(evaluate("not (gap:1rem)"), None);
+        assert_eq!(evaluate("not ((gap:1rem) or (display:grid))"), Some(false));
+        assert_eq!(evaluate("not ((gap:1rem) and (display:grid))"), None);
+    }
+
+    #[test]
+    fn evaluate_and_with_undecided_condition() {
+        assert_eq!(evaluate("(gap:1rem) and (display:grid)"), None);
+        assert_eq!(evaluate("(display:grid) and (gap:1rem)"), None);
+        assert_eq!(evaluate("(gap:1rem) and (float:left)"), Some(false));
+        assert_eq!(evaluate("(float:left) and (gap:1rem)"), Some(false));
+        assert_eq!(
+            evaluate("(display:grid) and (display:flex) and (display:block)"),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn evaluate_or_with_undecided_condition() {
+        assert_eq!(evaluate("(gap:1rem) or (float:left)"), None);
+        assert_eq!(evaluate("(float:left) or (gap:1rem)"), None);
+        assert_eq!(evaluate("(gap:1rem) or (display:grid)"), Some(true));
+        assert_eq!(evaluate("(display:grid) or (gap:1rem)"), Some(true));
+        assert_eq!(evaluate("(float:left) or (float:right)"), Some(false));
+    }
+
+    #[test]
+    fn evaluate_selector_function() {
+        assert_eq!(evaluate("selector(a)"), Some(true));
+        assert_eq!(evaluate("SELECTOR( a )"), Some(true));
+        assert_eq!(evaluate("selector()"), Some(false));
+        assert_eq!(evaluate("selector(a:hover)"), None);
+        // only `selector()` passes its argument to supports_selector
+        assert_eq!(evaluate("selectors(a)"), None);
+        assert_eq!(evaluate("(selector(a))"), Some(true));
+    }
+
+    #[test]
+    fn evaluate_unknown_syntax() {
+        assert_eq!(evaluate("font-tech(color-colrv1)"), Some(true));
+        assert_eq!(evaluate("font-format(woff2)"), None);
+        assert_eq!(evaluate("(unknown syntax)"), None);
+        assert_eq!(evaluate("séléctor(a)"), None);
+        assert_eq!(evaluate("not font-format(woff2)"), None);
+        assert_eq!(
+            evaluate("font-format(woff2) and (float:left)"),
+            Some(false)
+        );
+        assert_eq!(
+            evaluate("font-format(woff2) or (display:grid)"),
+            Some(true)
+        );
+    }
+}
@@ -0,0 +1,28 @@
+// This file is part of css. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/css/master/COPYRIGHT. No part of predicator, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2017 The developers of css. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/css/master/COPYRIGHT.
+
+/// A trait that is used when evaluating @supports rules for a target browser, similar to [Device](crate::domain::at_rules::media::Device) for @media rules.
+/// See [SupportedFeatures] for an implementation that uses a list of supported features.
+///
+/// Every method returns `None` if it is unknown whether the feature is supported, the @supports rule is then kept as it is.
+///
+/// [SupportedFeatures]: super::SupportedFeatures
+pub trait SupportsEvaluator {
+    /// Used when evaluating declarations like `(display: grid)`
+    /// The property is given in ASCII lowercase, the value without surrounding whitespace.
+    fn supports_declaration(&self, property: &str, value: &str)
+        -> Option<bool>;
+
+    /// Used when evaluating `selector(...)` functions, the selector is given without the function.
+    ///
+    /// <https://drafts.csswg.org/css-conditional-4/#at-supports-ext>
+    fn supports_selector(&self, selector: &str) -> Option<bool>;
+
+    /// Used when evaluating any other syntax, like `font-tech(color-COLRv1)`. Unknown syntax can not be decided by default.
+    ///
+    /// <https://drafts.csswg.org/css-conditional-3/#general_enclosed>
+    #[inline(always)]
+    fn supports_future_syntax(&self, _syntax: &str) -> Option<bool> {
+        None
+    }
+}
@@ -19,6 +19,24 @@ impl ToCss for SupportsPropertyDeclaration {
 }
 
 impl SupportsPropertyDeclaration {
+    /// The name of the property in ASCII lowercase.
+    pub fn property(&self) -> String {
+        self.0
+            .split(':')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase()
+    }
+
+    /// The value of the declaration without surrounding whitespace.
+    pub fn value(&self) -> &str {
+        self.0
+            .split_once(':')
+            .map(|(_, value)| value.trim())
+            .unwrap_or_default()
+    }
+
     /// Parse a declaration
     pub(crate) fn parse<'i, 't>(
         input: &mut Parser<'i, 't>,
@@ -92,6 +92,13 @@ impl Borrow<str> for Atom {
     }
 }
 
+impl AsRef<str> for Atom {
+    #[inline(always)]
+    fn as_ref(&self) -> &str {
+        self.deref()
+    }
+}
+
 impl PrecomputedHash for Atom {
     #[inline(always)]
     fn precomputed_hash(&self) -> u32 {
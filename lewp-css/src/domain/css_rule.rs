@@ -4,12 +4,14 @@
 use {
     super::{
         at_rules::{
+            container::ContainerAtRule,
             counter_style::CounterStyleAtRule,
             document::DocumentAtRule,
             font_face::FontFaceAtRule,
             font_feature_values::FontFeatureValuesAtRule,
             import::ImportAtRule,
             keyframes::KeyframesAtRule,
+            layer::LayerAtRule,
             media::MediaAtRule,
             namespace::NamespaceAtRule,
             page::PageAtRule,
@@ -26,6 +28,9 @@ use {
 /// No Charset here, CSSCharsetRule has been removed from CSSOM (<https://drafts.csswg.org/cssom/#changes-from-5-december-2013>) and Edge doesn't support it
 #[derive(Debug, Clone)]
 pub enum CssRule {
+    /// @container
+    Container(ContainerAtRule),
+
     /// @counter-style
     CounterStyle(CounterStyleAtRule),
 
@@ -44,6 +49,9 @@ pub enum CssRule {
     /// @keyframes
     Keyframes(KeyframesAtRule),
 
+    /// @layer
+    Layer(LayerAtRule),
+
     /// @media
     Media(MediaAtRule),
 
@@ -69,6 +77,8 @@ impl ToCss for CssRule {
         use self::CssRule::*;
 
         match *self {
+            Container(ref rule) => rule.to_css(dest),
+
             CounterStyle(ref rule) => rule.to_css(dest),
 
             Document(ref rule) => rule.to_css(dest),
@@ -81,6 +91,8 @@ impl ToCss for CssRule {
 
             Keyframes(ref rule) => rule.to_css(dest),
 
+            Layer(ref rule) => rule.to_css(dest),
+
             Media(ref rule) => rule.to_css(dest),
 
             Namespace(ref rule) => rule.to_css(dest),
@@ -103,6 +115,8 @@ impl CssRule {
         use self::CssRule::*;
 
         match *self {
+            Container(_) => CssRuleType::Container,
+
             CounterStyle(_) => CssRuleType::CounterStyle,
 
             Document(_) => CssRuleType::Document,
@@ -115,6 +129,8 @@ impl CssRule {
 
             Keyframes(_) => CssRuleType::Keyframes,
 
+            Layer(_) => CssRuleType::Layer,
+
             Media(_) => CssRuleType::Media,
 
             Namespace(_) => CssRuleType::Namespace,
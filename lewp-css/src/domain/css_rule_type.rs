@@ -41,6 +41,15 @@ pub enum CssRuleType {
 
     // <https://drafts.csswg.org/css-device-adapt/#css-rule-interface>
     Viewport = 15,
+
+    // <https://drafts.csswg.org/css-cascade-5/#layer-apis>
+    // The layer rules do not define a type constant, so this value is only
+    // used within this crate.
+    Layer = 16,
+
+    // <https://drafts.csswg.org/css-contain-3/#the-csscontainerrule-interface>
+    // Like the layer rules, the container rule does not define a type constant.
+    Container = 17,
 }
 
 impl CssRuleType {
@@ -3,11 +3,21 @@
 
 use {
     super::{
-        at_rules::VendorPrefixedAtRule,
+        at_rules::{
+            container::ContainerAtRule,
+            document::DocumentAtRule,
+            layer::LayerAtRule,
+            media::MediaAtRule,
+            supports::{SupportsAtRule, SupportsEvaluator},
+            VendorPrefixedAtRule,
+        },
+        selectors::DeduplicatedSelectors,
         CssRule::{self},
         HasCssRules,
         RulesMutateError::{self},
+        StyleRule,
     },
+    crate::autoprefixer::{prefix_rules, BrowserTargets},
     cssparser::ToCss,
     std::fmt,
 };
@@ -101,6 +111,242 @@ impl CssRules {
         }
     }
 
+    /// Lowers nested rules for browsers that do not support CSS nesting. Every
+    /// nested [StyleRule](super::StyleRule) is moved behind its parent and the
+    /// nesting selector `&` is replaced by the selectors of the parent. At-rules
+    /// nested in a style rule keep their position relative to the other nested
+    /// rules, so `.card{&:hover{color:red}@media print{color:blue}}` becomes
+    /// `.card:hover{color:red}@media print{.card{color:blue}}`.
+    ///
+    /// Style rules that only contained nested rules are removed. A nesting
+    /// selector in a top level rule is replaced by `:scope`. Nested rules whose
+    /// parent selectors all contain a pseudo-element, like `&:hover` in
+    /// `.card::before`, can not match anything and are removed.
+    pub fn flatten_nesting(&mut self) {
+        self.flatten_nesting_with_parent(None)
+    }
+
+    fn flatten_nesting_with_parent(
+        &mut self,
+        parent: Option<&DeduplicatedSelectors>,
+    ) {
+        let parent_selectors = parent.map(|p| &p.0[..]).unwrap_or_default();
+        for mut rule in std::mem::take(&mut self.0) {
+            match rule {
+                CssRule::Style(ref mut style_rule) => {
+                    style_rule.selectors.replace_nesting(parent_selectors);
+                    // `&` can not represent the pseudo-elements of the parent
+                    if style_rule.selectors.0.is_empty() {
+                        continue;
+                    }
+                    let mut nested = std::mem::take(&mut style_rule.rules);
+                    nested.flatten_nesting_with_parent(Some(
+                        &style_rule.selectors,
+                    ));
+                    if !style_rule.property_declarations.is_empty()
+                        || nested.is_empty()
+                    {
+                        self.0.push(rule);
+                    }
+                    self.0.extend(nested.0);
+                    continue;
+                }
+                CssRule::Media(MediaAtRule { ref mut rules, .. })
+                | CssRule::Document(DocumentAtRule { ref mut rules, .. })
+                | CssRule::Supports(SupportsAtRule { ref mut rules, .. })
+                | CssRule::Container(ContainerAtRule {
+                    ref mut rules, ..
+                })
+                | CssRule::Layer(LayerAtRule {
+                    rules: Some(ref mut rules),
+                    ..
+                }) => rules.flatten_nesting_with_parent(parent),
+                _ => {}
+            }
+            self.0.push(rule);
+        }
+    }
+
+    /// Resolves the `@supports` rules for a target browser. A rule whose
+    /// condition is supported is replaced by its child rules, a rule whose
+    /// condition is not supported is removed. Rules whose condition can not be
+    /// decided by the evaluator are kept. `@media`, `@document`, `@container`
+    /// and `@supports` rules that are empty afterwards are removed as well.
+    /// See [SupportedFeatures](super::at_rules::supports::SupportedFeatures)
+    /// for an example.
+    pub fn resolve_supports<E: SupportsEvaluator>(&mut self, evaluator: &E) {
+        for rule in std::mem::take(&mut self.0) {
+            match rule {
+                CssRule::Supports(mut supports_rule) => {
+                    let emptied =
+                        supports_rule.rules.resolve_supports_emptied(evaluator);
+                    match supports_rule.evaluate(evaluator) {
+                        Some(true) => self.0.extend(supports_rule.rules.0),
+                        None if !emptied => {
+                            self.0.push(CssRule::Supports(supports_rule))
+                        }
+                        _ => {}
+                    }
+                }
+                mut rule => {
+                    let emptied = match rule {
+                        CssRule::Media(MediaAtRule {
+                            ref mut rules, ..
+                        })
+                        | CssRule::Document(DocumentAtRule {
+                            ref mut rules,
+                            ..
+                        })
+                        | CssRule::Container(ContainerAtRule {
+                            ref mut rules,
+                            ..
+                        }) => rules.resolve_supports_emptied(evaluator),
+                        // an empty layer still defines the order of the layers
+                        CssRule::Style(StyleRule { ref mut rules, .. })
+                        | CssRule::Layer(LayerAtRule {
+                            rules: Some(ref mut rules),
+                            ..
+                        }) => {
+                            rules.resolve_supports(evaluator);
+                            false
+                        }
+                        _ => false,
+                    };
+                    if !emptied {
+                        self.0.push(rule);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Like [resolve_supports](Self::resolve_supports), returns `true` if
+    /// the rules have been emptied by it.
+    fn resolve_supports_emptied<E: SupportsEvaluator>(
+        &mut self,
+        evaluator: &E,
+    ) -> bool {
+        let was_empty = self.0.is_empty();
+        self.resolve_supports(evaluator);
+        !was_empty && self.0.is_empty()
+    }
+
+    /// Adds the vendor prefixed declarations, pseudo-classes, pseudo-elements
+    /// and `@keyframes` rules needed by the target browsers, and removes
+    /// prefixed ones that none of the targets needs anymore. Prefixed
+    /// declarations are inserted in front of the unprefixed declaration,
+    /// prefixed style and `@keyframes` rules in front of the unprefixed rule.
+    /// A `transition` listing a property that needs a prefix is followed by a
+    /// copy that lists the prefixed property name as well.
+    ///
+    /// Obsolete prefixes are only removed if the unprefixed declaration or
+    /// rule exists next to them, so stylesheets that only use the prefixed
+    /// version keep working.
+    ///
+    /// ```rust
+    /// use lewp_css::{autoprefixer::BrowserTargets, cssparser::ToCss, Stylesheet};
+    ///
+    /// let targets = BrowserTargets::parse("chrome >= 100, safari >= 15").unwrap();
+    /// let mut stylesheet = Stylesheet::parse(
+    ///     "a{-webkit-transform:none;transform:none;user-select:none;position:sticky}b::selection{color:gray}",
+    /// )
+    /// .unwrap();
+    /// stylesheet.rules.autoprefix(&targets);
+    /// assert_eq!(
+    ///     stylesheet.rules.to_css_string(),
+    ///     "a{transform:none;-webkit-user-select:none;user-select:none;position:sticky}b::selection{color:gray}",
+    /// );
+    ///
+    /// let targets = BrowserTargets::parse("firefox >= 60, safari >= 12").unwrap();
+    /// stylesheet.rules.autoprefix(&targets);
+    /// assert_eq!(
+    ///     stylesheet.rules.to_css_string(),
+    ///     "a{transform:none;-webkit-user-select:none;-moz-user-select:none;user-select:none;position:-webkit-sticky;position:sticky}b::-moz-selection{color:gray}b::selection{color:gray}",
+    /// );
+    /// ```
+    ///
+    /// Values, property names in `transition`, selectors and `@keyframes` are prefixed for every target that needs them:
+    ///
+    /// ```rust
+    /// use lewp_css::{autoprefixer::BrowserTargets, cssparser::ToCss, Stylesheet};
+    ///
+    /// for (targets, css, prefixed) in [
+    ///     (
+    ///         "chrome >= 20",
+    ///         "a{transition:transform 1s,color 1s}",
+    ///         "a{-webkit-transition:-webkit-transform 1s,color 1s;transition:transform 1s,color 1s;transition:-webkit-transform 1s,transform 1s,color 1s}",
+    ///     ),
+    ///     (
+    ///         "chrome >= 30",
+    ///         "a{transition-property:transform}",
+    ///         "a{transition-property:transform;transition-property:-webkit-transform,transform}",
+    ///     ),
+    ///     (
+    ///         "chrome >= 100",
+    ///         "a{transition-property:transform;transition-property:-webkit-transform,transform}",
+    ///         "a{transition-property:transform}",
+    ///     ),
+    ///     (
+    ///         "edge >= 12",
+    ///         "a{user-select:none}",
+    ///         "a{-ms-user-select:none;user-select:none}",
+    ///     ),
+    ///     (
+    ///         "edge >= 79",
+    ///         "a{-ms-user-select:none;user-select:none}",
+    ///         "a{user-select:none}",
+    ///     ),
+    ///     (
+    ///         "safari >= 12",
+    ///         "a{display:flex;position:sticky;width:fit-content}",
+    ///         "a{display:flex;position:-webkit-sticky;position:sticky;width:fit-content}",
+    ///     ),
+    ///     (
+    ///         "firefox >= 60",
+    ///         "a{width:fit-content;cursor:grab}",
+    ///         "a{width:-moz-fit-content;width:fit-content;cursor:grab}",
+    ///     ),
+    ///     (
+    ///         "safari >= 14",
+    ///         "a{position:-webkit-sticky;position:sticky}",
+    ///         "a{position:sticky}",
+    ///     ),
+    ///     (
+    ///         "chrome >= 50, firefox >= 40",
+    ///         "a:any-link{color:red}",
+    ///         "a:-moz-any-link{color:red}a:-webkit-any-link{color:red}a:any-link{color:red}",
+    ///     ),
+    ///     (
+    ///         "ie >= 11",
+    ///         "input::placeholder{color:gray}",
+    ///         "input::-ms-input-placeholder{color:gray}input::placeholder{color:gray}",
+    ///     ),
+    ///     (
+    ///         "firefox >= 100",
+    ///         "a::-moz-selection{color:gray}a::selection{color:gray}",
+    ///         "a::selection{color:gray}",
+    ///     ),
+    ///     (
+    ///         "chrome >= 30",
+    ///         "@keyframes spin{to{transform:rotate(1turn)}}",
+    ///         "@-webkit-keyframes spin{100%{-webkit-transform:rotate(1turn);transform:rotate(1turn)}}@keyframes spin{100%{-webkit-transform:rotate(1turn);transform:rotate(1turn)}}",
+    ///     ),
+    ///     (
+    ///         "chrome >= 100",
+    ///         "@-webkit-keyframes spin{to{opacity:0}}@keyframes spin{to{opacity:0}}",
+    ///         "@keyframes spin{100%{opacity:0}}",
+    ///     ),
+    /// ] {
+    ///     let targets = BrowserTargets::parse(targets).unwrap();
+    ///     let mut stylesheet = Stylesheet::parse(css).unwrap();
+    ///     stylesheet.rules.autoprefix(&targets);
+    ///     assert_eq!(stylesheet.rules.to_css_string(), prefixed, "{css}");
+    /// }
+    /// ```
+    pub fn autoprefix(&mut self, targets: &BrowserTargets) {
+        prefix_rules(self, targets)
+    }
+
     /// Whether this CSS rules is empty.
     pub fn is_empty(&self) -> bool {
         self.0.is_empty()
@@ -146,3 +392,145 @@ impl CssRules {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{domain::at_rules::supports::SupportedFeatures, Stylesheet},
+    };
+
+    fn flatten(css: &str) -> String {
+        let mut stylesheet = Stylesheet::parse(css).unwrap();
+        stylesheet.rules.flatten_nesting();
+        stylesheet.rules.to_css_string()
+    }
+
+    #[test]
+    fn flatten_nesting_in_pseudo_element_parent() {
+        assert_eq!(
+            flatten(".a::before{color:red;&:hover{color:blue}.b{color:green}}"),
+            ".a::before{color:red}"
+        );
+        assert_eq!(
+            flatten(".a::before,.c{color:red;&:hover{color:blue}}"),
+            ".a::before,.c{color:red}.c:hover{color:blue}"
+        );
+    }
+
+    #[test]
+    fn nested_declarations_keep_source_order() {
+        let css = ".a{color:red;.b{color:blue}color:green;@media print{color:black}margin:0}";
+        let stylesheet = Stylesheet::parse(css).unwrap();
+        assert_eq!(
+            stylesheet.rules.to_css_string(),
+            ".a{color:red;& .b{color:blue}&{color:green}@media print{&{color:black}}&{margin:0}}"
+        );
+        assert_eq!(
+            flatten(css),
+            ".a{color:red}.a .b{color:blue}.a{color:green}@media print{.a{color:black}}.a{margin:0}"
+        );
+    }
+
+    fn resolve_supports(css: &str) -> String {
+        let features = SupportedFeatures {
+            properties: ["display"].into_iter().map(String::from).collect(),
+            values: [(
+                "display".to_string(),
+                ["block", "flex"].into_iter().map(String::from).collect(),
+            )]
+            .into(),
+            selectors: [":hover"].into_iter().map(String::from).collect(),
+        };
+        let mut stylesheet = Stylesheet::parse(css).unwrap();
+        stylesheet.rules.resolve_supports(&features);
+        stylesheet.rules.to_css_string()
+    }
+
+    #[test]
+    fn resolve_supports_nested_in_at_rules() {
+        assert_eq!(
+            resolve_supports(
+                "@media print{@supports (display:flex){a{color:red}}b{color:blue}}"
+            ),
+            "@media print{a{color:red}b{color:blue}}"
+        );
+        assert_eq!(
+            resolve_supports(
+                "@media print{@supports (display:grid){a{color:red}}b{color:blue}}"
+            ),
+            "@media print{b{color:blue}}"
+        );
+        assert_eq!(
+            resolve_supports(
+                "@container card (min-width:400px){@supports selector(:hover){a{color:red}}}"
+            ),
+            "@container card (width>=400px){a{color:red}}"
+        );
+        assert_eq!(
+            resolve_supports(
+                "@layer base{@supports (display:flex){@supports (display:block){a{color:red}}}}"
+            ),
+            "@layer base{a{color:red}}"
+        );
+        assert_eq!(
+            resolve_supports(
+                "a{color:red;@supports (display:flex){color:blue}@supports (display:grid){color:green}}"
+            ),
+            "a{color:red;&{color:blue}}"
+        );
+    }
+
+    #[test]
+    fn resolve_supports_removes_emptied_at_rules() {
+        assert_eq!(
+            resolve_supports(
+                "@media print{@container (min-width:400px){@supports (display:grid){a{color:red}}}}b{color:blue}"
+            ),
+            "b{color:blue}"
+        );
+        // an undecided rule is removed if its rules are removed
+        assert_eq!(
+            resolve_supports(
+                "@supports font-tech(color-COLRv1){@supports (display:grid){a{color:red}}}"
+            ),
+            ""
+        );
+        // rules that have been empty before are kept
+        assert_eq!(
+            resolve_supports(
+                "@media print{}@supports font-tech(color-COLRv1){}"
+            ),
+            "@media print{}@supports font-tech(color-COLRv1){}"
+        );
+        // an empty layer still defines the order of the layers
+        assert_eq!(
+            resolve_supports(
+                "@layer base{@supports (display:grid){a{color:red}}}"
+            ),
+            "@layer base{}"
+        );
+    }
+
+    #[test]
+    fn resolve_supports_keeps_undecided_rules() {
+        assert_eq!(
+            resolve_supports(
+                "@supports font-tech(color-COLRv1){@supports (display:flex){a{color:red}}}"
+            ),
+            "@supports font-tech(color-COLRv1){a{color:red}}"
+        );
+        assert_eq!(
+            resolve_supports(
+                "@supports (not font-tech(color-COLRv1)) and (display:flex){a{color:red}}"
+            ),
+            "@supports (not font-tech(color-COLRv1)) and (display:flex){a{color:red}}"
+        );
+        assert_eq!(
+            resolve_supports(
+                "@supports font-tech(color-COLRv1) and (display:grid){a{color:red}}"
+            ),
+            ""
+        );
+    }
+}
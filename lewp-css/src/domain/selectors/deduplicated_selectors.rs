@@ -18,3 +18,24 @@ impl ToCss for DeduplicatedSelectors {
         Ok(())
     }
 }
+
+impl DeduplicatedSelectors {
+    /// Replaces the nesting selector `&` in all selectors by the given parent
+    /// selectors, see [Selector::replace_nesting]. Selectors that can not
+    /// match anything, because all parent selectors contain a pseudo-element,
+    /// are removed.
+    pub fn replace_nesting(&mut self, parent: &[Selector<OurSelectorImpl>]) {
+        self.0.retain_mut(|selector| {
+            if !selector.has_nesting() {
+                return true;
+            }
+            match selector.replace_nesting(parent) {
+                Some(replaced) => {
+                    *selector = replaced;
+                    true
+                }
+                None => false,
+            }
+        });
+    }
+}
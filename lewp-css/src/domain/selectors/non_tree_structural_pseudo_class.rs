@@ -586,6 +586,30 @@ impl NonTreeStructuralPseudoClass {
         matches!(*self, active | focus | hover)
     }
 
+    /// The name and vendor prefix of this pseudo-class if it can be vendor prefixed across more than one vendor.
+    pub fn vendor_prefixable_name(
+        &self,
+    ) -> Option<(VendorPrefixablePseudoClassName, Option<&VendorPrefix>)> {
+        use self::{
+            NonTreeStructuralPseudoClass::*,
+            VendorPrefixablePseudoClassName as Name,
+        };
+
+        let (name, vendorPrefix) = match *self {
+            any(ref vendorPrefix, _) => (Name::any, vendorPrefix),
+            any_link(ref vendorPrefix) => (Name::any_link, vendorPrefix),
+            dir(ref vendorPrefix, _) => (Name::dir, vendorPrefix),
+            fullscreen(ref vendorPrefix) => (Name::fullscreen, vendorPrefix),
+            placeholder_shown(ref vendorPrefix) => {
+                (Name::placeholder_shown, vendorPrefix)
+            }
+            read_only(ref vendorPrefix) => (Name::read_only, vendorPrefix),
+            read_write(ref vendorPrefix) => (Name::read_write, vendorPrefix),
+            _ => return None,
+        };
+        Some((name, vendorPrefix.as_ref()))
+    }
+
     #[inline(always)]
     fn applyVendorPrefix(
         pseudoClassName: VendorPrefixablePseudoClassName,
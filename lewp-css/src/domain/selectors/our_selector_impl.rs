@@ -6,6 +6,7 @@ use {
         domain::{
             at_rules::namespace::{NamespacePrefix, NamespaceUrl, Namespaces},
             selectors::{
+                DeduplicatedSelectors,
                 NonTreeStructuralPseudoClass,
                 OurSelector,
                 PseudoElement,
@@ -79,6 +80,31 @@ impl OurSelectorImpl {
         }
     }
 
+    /// Parses a comma separated list of selectors
+    pub fn parse_selector_list(
+        selector_css: &str,
+    ) -> Result<DeduplicatedSelectors, ParseError<'_, CustomParseError<'_>>>
+    {
+        const LineNumberingIsZeroBased: u32 = 0;
+
+        let mut parserInput = ParserInput::new_with_line_number_offset(
+            selector_css,
+            LineNumberingIsZeroBased,
+        );
+        let mut input = Parser::new(&mut parserInput);
+
+        let applyVendorPrefixToPseudoClasses = HashMap::default();
+        let applyVendorPrefixToPseudoElements = HashMap::default();
+        let ourSelectorParser = OurSelectorParser {
+            namespaces: Namespaces::empty(),
+            applyVendorPrefixToPseudoClasses: &applyVendorPrefixToPseudoClasses,
+            applyVendorPrefixToPseudoElements:
+                &applyVendorPrefixToPseudoElements,
+        };
+
+        ourSelectorParser.parse(&mut input)
+    }
+
     /// Applies a vendor prefix to a CSS selector
     #[inline(always)]
     pub fn reparse_with_vendor_prefix<'a>(
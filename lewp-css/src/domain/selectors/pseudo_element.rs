@@ -670,6 +670,30 @@ impl PseudoElement {
         }
     }
 
+    /// The name and vendor prefix of this pseudo-element if it can be vendor prefixed across more than one vendor.
+    pub fn vendor_prefixable_name(
+        &self,
+    ) -> Option<(VendorPrefixablePseudoElementName, Option<&VendorPrefix>)>
+    {
+        use self::VendorPrefixablePseudoElementName as Name;
+
+        let (name, vendorPrefix) = match *self {
+            backdrop(ref vendorPrefix) => (Name::backdrop, vendorPrefix),
+            placeholder(ref vendorPrefix) => (Name::placeholder, vendorPrefix),
+            selection(ref vendorPrefix) => (Name::selection, vendorPrefix),
+            progress_bar(ref vendorPrefix) => {
+                (Name::progress_bar, vendorPrefix)
+            }
+            range_progress(ref vendorPrefix) => {
+                (Name::range_progress, vendorPrefix)
+            }
+            range_thumb(ref vendorPrefix) => (Name::range_thumb, vendorPrefix),
+            range_track(ref vendorPrefix) => (Name::range_track, vendorPrefix),
+            _ => return None,
+        };
+        Some((name, vendorPrefix.as_ref()))
+    }
+
     #[inline(always)]
     fn applyVendorPrefix(
         pseudoElementName: VendorPrefixablePseudoElementName,
@@ -719,9 +743,11 @@ impl PseudoElement {
 
             "placeholder" => Ok(placeholder(Self::applyVendorPrefix(VendorPrefixablePseudoElementName::placeholder, applyVendorPrefixToPseudoElements))),
 
-            "-ms-placeholder" => Ok(placeholder(Some(ms))),
+            "-moz-placeholder" => Ok(placeholder(Some(moz))),
+
+            "-ms-input-placeholder" => Ok(placeholder(Some(ms))),
 
-            "-webkit-input-placeholder" => Ok(placeholder(Some(ms))),
+            "-webkit-input-placeholder" => Ok(placeholder(Some(webkit))),
 
             "selection" => Ok(selection(Self::applyVendorPrefix(VendorPrefixablePseudoElementName::selection, applyVendorPrefixToPseudoElements))),
 
@@ -5,13 +5,35 @@ use {
     super::{
         properties::{Importance, PropertyDeclaration, PropertyDeclarations},
         selectors::DeduplicatedSelectors,
+        CssRule,
+        CssRules,
+        HasCssRules,
         HasPropertyDeclarations,
     },
     cssparser::ToCss,
     std::fmt,
 };
 
-/// A style rule, with selectors and declarations.
+/// A style rule, with selectors, declarations and nested rules.
+///
+/// ```rust
+/// use lewp_css::{cssparser::ToCss, Stylesheet};
+///
+/// let mut stylesheet = Stylesheet::parse(
+///     ".card{color:red;.title{margin:0}&:hover{color:blue}@media (min-width:400px){padding:0}}",
+/// )
+/// .unwrap();
+/// assert_eq!(
+///     stylesheet.rules.to_css_string(),
+///     ".card{color:red;& .title{margin:0}&:hover{color:blue}@media (min-width:400px){&{padding:0}}}"
+/// );
+///
+/// stylesheet.rules.flatten_nesting();
+/// assert_eq!(
+///     stylesheet.rules.to_css_string(),
+///     ".card{color:red}.card .title{margin:0}.card:hover{color:blue}@media (min-width:400px){.card{padding:0}}"
+/// );
+/// ```
 #[derive(Debug, Clone)]
 pub struct StyleRule {
     /// The list of selectors in this rule.
@@ -19,6 +41,13 @@ pub struct StyleRule {
 
     /// The declaration block with the properties it contains.
     pub property_declarations: PropertyDeclarations<Importance>,
+
+    /// The rules that are nested in this rule, see
+    /// <https://drafts.csswg.org/css-nesting/>. Their selectors are relative
+    /// to the selectors of this rule and contain the nesting selector `&`.
+    /// Declarations following a nested rule are kept in a `&` rule at their
+    /// position.
+    pub rules: CssRules,
 }
 
 impl ToCss for StyleRule {
@@ -30,10 +59,44 @@ impl ToCss for StyleRule {
 
         self.property_declarations.to_css(dest)?;
 
+        if !self.rules.is_empty() {
+            if !self.property_declarations.is_empty() {
+                dest.write_char(';')?;
+            }
+            self.rules.to_css(dest)?;
+        }
+
         dest.write_char('}')
     }
 }
 
+impl HasCssRules for StyleRule {
+    #[inline(always)]
+    fn css_rules(&self) -> &CssRules {
+        &self.rules
+    }
+
+    #[inline(always)]
+    fn css_rules_mut(&mut self) -> &mut CssRules {
+        &mut self.rules
+    }
+
+    #[inline(always)]
+    fn css_rules_slice(&self) -> &[CssRule] {
+        &self.rules.0[..]
+    }
+
+    #[inline(always)]
+    fn css_rules_vec(&self) -> &Vec<CssRule> {
+        &self.rules.0
+    }
+
+    #[inline(always)]
+    fn css_rules_vec_mut(&mut self) -> &mut Vec<CssRule> {
+        &mut self.rules.0
+    }
+}
+
 impl HasPropertyDeclarations<Importance> for StyleRule {
     #[inline(always)]
     fn property_declarations(&self) -> &PropertyDeclarations<Importance> {
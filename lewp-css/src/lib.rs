@@ -74,6 +74,7 @@ pub extern crate servo_arc;
 pub extern crate smallvec;
 
 /// Contains definitions of objects used in Stylesheet.
+pub mod autoprefixer;
 pub mod domain;
 pub(crate) mod parsers;
 pub(crate) mod serializers;
@@ -3,7 +3,11 @@
 
 use {
     crate::{
-        domain::selectors::{OurSelector, OurSelectorImpl},
+        domain::selectors::{
+            DeduplicatedSelectors,
+            OurSelector,
+            OurSelectorImpl,
+        },
         CustomParseError,
     },
     cssparser::ParseError,
@@ -16,3 +20,11 @@ pub fn parse_css_selector(
 ) -> Result<OurSelector, ParseError<CustomParseError>> {
     OurSelectorImpl::parse_selector(selector_css)
 }
+
+/// Parses a comma separated list of selectors, eg `h1, .nav > a`.
+#[inline(always)]
+pub fn parse_css_selector_list(
+    selector_css: &str,
+) -> Result<DeduplicatedSelectors, ParseError<'_, CustomParseError<'_>>> {
+    OurSelectorImpl::parse_selector_list(selector_css)
+}
@@ -3,21 +3,27 @@
 
 use crate::domain::{
     at_rules::{
+        container::ContainerCondition,
         document::DocumentCondition,
         font_face::FamilyName,
         import::ImportAtRule,
         keyframes::KeyframesName,
+        layer::LayerName,
         media::MediaList,
         namespace::NamespaceAtRule,
         page::PageSelectorPseudoClass,
         supports::SupportsCondition,
     },
     CounterStyleIdent,
+    CustomIdent,
     VendorPrefix,
 };
 
 /// A rule prelude for at-rule with block.
 pub enum AtRuleBlockPrelude {
+    /// A @container rule prelude, with its container name and condition. At least one of both is given.
+    Container(Option<CustomIdent>, Option<ContainerCondition>),
+
     /// A @counter-style rule prelude, with its counter style name.
     CounterStyle(CounterStyleIdent),
 
@@ -36,6 +42,9 @@ pub enum AtRuleBlockPrelude {
     /// A @keyframes rule, with its animation name and vendor prefix if exists.
     Keyframes(Option<VendorPrefix>, KeyframesName),
 
+    /// A @layer rule prelude, with its layer names.
+    Layer(Vec<LayerName>),
+
     /// A @media rule prelude, with its media queries.
     Media(MediaList),
 
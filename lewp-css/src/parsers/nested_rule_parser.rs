@@ -3,6 +3,7 @@
 
 use {
     super::{
+        property_declaration_parser::PropertyDeclarationParser,
         qualified_rule_parser_prelude::QualifiedRuleParserPrelude,
         AtRuleBlockPrelude::{self, *},
         KeyframeListParser,
@@ -12,18 +13,25 @@ use {
     crate::{
         domain::{
             at_rules::{
+                container::{ContainerAtRule, ContainerCondition},
                 counter_style::CounterStyleAtRule,
                 document::{DocumentAtRule, DocumentCondition},
                 font_face::{FamilyName, FontFaceAtRule},
                 font_feature_values::FontFeatureValuesAtRule,
                 keyframes::{KeyframesAtRule, KeyframesName},
+                layer::{LayerAtRule, LayerName},
                 media::{MediaAtRule, MediaList},
                 namespace::Namespaces,
                 page::{PageAtRule, PageSelectorPseudoClass},
                 supports::{SupportsAtRule, SupportsCondition},
                 viewport::ViewportAtRule,
             },
-            properties::PropertyDeclarations,
+            properties::{
+                Importance,
+                PropertyDeclaration,
+                PropertyDeclarations,
+            },
+            selectors::DeduplicatedSelectors,
             CounterStyleIdent,
             CssRule,
             CssRuleType,
@@ -35,20 +43,29 @@ use {
     },
     cssparser::{
         AtRuleParser,
+        BasicParseErrorKind,
         CowRcStr,
+        DeclarationParser,
+        Delimiter,
         ParseError,
         Parser,
         ParserState,
         QualifiedRuleParser,
         RuleListParser,
+        Token,
     },
-    std::{collections::HashMap, rc::Rc},
+    selectors::parser::Selector,
+    std::{collections::HashMap, marker::PhantomData, mem, rc::Rc},
 };
 
 #[derive(Clone)]
 pub(crate) struct NestedRuleParser<'a> {
     pub context: &'a ParserContext,
     pub namespaces: Rc<Namespaces>,
+    /// Whether the rules are nested in a style rule. Their selectors are
+    /// relative to the parent and conditional group rules may contain
+    /// property declarations.
+    pub in_style_rule: bool,
 }
 
 impl<'a, 'i> AtRuleParser<'i> for NestedRuleParser<'a> {
@@ -66,6 +83,16 @@ impl<'a, 'i> AtRuleParser<'i> for NestedRuleParser<'a> {
         match_ignore_ascii_case! {
             &name,
 
+            "container" => {
+                let name = ContainerAtRule::parse_name(input);
+                // a named container does not need a query
+                let condition = match name.is_some() && input.is_exhausted() {
+                    true => None,
+                    false => Some(ContainerCondition::parse(self.context, input)?),
+                };
+                Ok(Self::Prelude::Container(name, condition))
+            },
+
             "counter-style" => Ok(Self::Prelude::CounterStyle(CounterStyleIdent::parseForCounterStyleAtRule(input)?)),
 
             "document" => Ok(Self::Prelude::Document(None, DocumentCondition::parse(self.context, input)?)),
@@ -82,6 +109,8 @@ impl<'a, 'i> AtRuleParser<'i> for NestedRuleParser<'a> {
 
             "-moz-keyframes" => Ok(Self::Prelude::Keyframes(Some(moz), KeyframesName::parse(input)?)),
 
+            "layer" => Ok(Self::Prelude::Layer(if input.is_exhausted() { vec![] } else { LayerName::parse_list(input)? })),
+
             "media" => Ok(Self::Prelude::Media(MediaList::parse_media_query_list(self.context, input, false)?)),
 
             "page" => Ok(Self::Prelude::Page(PageSelectorPseudoClass::parse(input)?)),
@@ -98,6 +127,20 @@ impl<'a, 'i> AtRuleParser<'i> for NestedRuleParser<'a> {
         }
     }
 
+    fn rule_without_block(
+        &mut self,
+        prelude: AtRuleBlockPrelude,
+        _start: &ParserState,
+    ) -> Result<Self::AtRule, ()> {
+        match prelude {
+            // the statement form declares the order of at least one layer
+            Layer(names) if !names.is_empty() => {
+                Ok(CssRule::Layer(LayerAtRule { names, rules: None }))
+            }
+            _ => Err(()),
+        }
+    }
+
     fn parse_block<'t>(
         &mut self,
         prelude: AtRuleBlockPrelude,
@@ -105,6 +148,13 @@ impl<'a, 'i> AtRuleParser<'i> for NestedRuleParser<'a> {
         input: &mut Parser<'i, 't>,
     ) -> Result<Self::AtRule, ParseError<'i, Self::Error>> {
         let cssRule = match prelude {
+            Container(name, condition) => CssRule::Container(ContainerAtRule {
+                name,
+                condition,
+                rules: self
+                    .parse_nested_rules(input, CssRuleType::Container)?,
+            }),
+
             CounterStyle(name) => {
                 CssRule::CounterStyle(CounterStyleAtRule::parse_body(
                     name,
@@ -148,6 +198,20 @@ impl<'a, 'i> AtRuleParser<'i> for NestedRuleParser<'a> {
                 })
             }
 
+            Layer(names) => {
+                if names.len() > 1 {
+                    return Err(ParseError::from(
+                        CustomParseError::LayerBlockCanNotHaveMoreThanOneName,
+                    ));
+                }
+                CssRule::Layer(LayerAtRule {
+                    names,
+                    rules: Some(
+                        self.parse_nested_rules(input, CssRuleType::Layer)?,
+                    ),
+                })
+            }
+
             Media(media_queries) => CssRule::Media(MediaAtRule {
                 media_queries,
                 rules: self.parse_nested_rules(input, CssRuleType::Media)?,
@@ -202,7 +266,11 @@ impl<'a, 'i> QualifiedRuleParser<'i> for NestedRuleParser<'a> {
                 &applyVendorPrefixToPseudoElements,
         };
 
-        let selectors = ourSelectorParser.parse(input)?;
+        let selectors = if self.in_style_rule {
+            ourSelectorParser.parse_relative(input)?
+        } else {
+            ourSelectorParser.parse(input)?
+        };
 
         Ok(QualifiedRuleParserPrelude { selectors })
     }
@@ -216,12 +284,18 @@ impl<'a, 'i> QualifiedRuleParser<'i> for NestedRuleParser<'a> {
         let context =
             ParserContext::new_with_rule_type(self.context, CssRuleType::Style);
 
+        let mut nested_parser = NestedRuleParser {
+            context: &context,
+            namespaces: self.namespaces.clone(),
+            in_style_rule: true,
+        };
+        let (property_declarations, rules) =
+            nested_parser.parse_style_block(input)?;
+
         let styleRule = StyleRule {
             selectors: prelude.selectors,
-            property_declarations:
-                PropertyDeclarations::parse_property_declaration_list(
-                    &context, input,
-                )?,
+            property_declarations,
+            rules,
         };
 
         Ok(CssRule::Style(styleRule))
@@ -244,13 +318,32 @@ impl<'a> NestedRuleParser<'a> {
     ) -> Result<CssRules, ParseError<'i, CustomParseError<'i>>> {
         let context = rule_type.context(self);
 
-        let nested_parser = NestedRuleParser {
+        let mut nested_parser = NestedRuleParser {
             context: &context,
             namespaces: self.namespaces.clone(),
+            in_style_rule: self.in_style_rule,
         };
 
-        let iter =
-            RuleListParser::new_for_nested_rule(input, nested_parser);
+        if self.in_style_rule {
+            // property declarations apply to the elements of the parent rule
+            let (property_declarations, mut rules) =
+                nested_parser.parse_style_block(input)?;
+            if !property_declarations.is_empty() {
+                rules.0.insert(
+                    0,
+                    CssRule::Style(StyleRule {
+                        selectors: DeduplicatedSelectors(vec![
+                            Selector::nesting(),
+                        ]),
+                        property_declarations,
+                        rules: CssRules::default(),
+                    }),
+                );
+            }
+            return Ok(rules);
+        }
+
+        let iter = RuleListParser::new_for_nested_rule(input, nested_parser);
         let mut rules = Vec::new();
         for result in iter {
             match result {
@@ -260,4 +353,137 @@ impl<'a> NestedRuleParser<'a> {
         }
         Ok(CssRules(rules))
     }
+
+    /// Parses the content of a style rule, consisting of property declarations
+    /// and nested rules, see <https://drafts.csswg.org/css-nesting/#syntax>.
+    /// The parser has to be created with `in_style_rule` set.
+    fn parse_style_block<'i: 't, 't>(
+        &mut self,
+        input: &mut Parser<'i, 't>,
+    ) -> Result<
+        (PropertyDeclarations<Importance>, CssRules),
+        ParseError<'i, CustomParseError<'i>>,
+    > {
+        let mut property_declarations = Vec::new();
+        let mut rules = Vec::new();
+        // declarations following a nested rule, they keep their position
+        let mut nested_declarations = Vec::new();
+        loop {
+            let start = input.state();
+            let name = match input.next() {
+                Err(_) => break,
+                Ok(&Token::Semicolon) => continue,
+                Ok(Token::AtKeyword(name)) => Some(name.clone()),
+                Ok(_) => None,
+            };
+            if let Some(name) = name {
+                Self::push_nested_declarations(
+                    &mut rules,
+                    &mut nested_declarations,
+                );
+                rules.push(self.parse_nested_at_rule(name, &start, input)?);
+                continue;
+            }
+            input.reset(&start);
+            if Self::is_property_declaration(input) {
+                let mut parser = PropertyDeclarationParser {
+                    context: self.context,
+                    marker: PhantomData,
+                };
+                let property_declaration =
+                    input.parse_until_after(Delimiter::Semicolon, |input| {
+                        let name = input.expect_ident()?.clone();
+                        input.expect_colon()?;
+                        parser.parse_value(name, input)
+                    })?;
+                if rules.is_empty() {
+                    property_declarations.push(property_declaration);
+                } else {
+                    nested_declarations.push(property_declaration);
+                }
+                continue;
+            }
+            Self::push_nested_declarations(
+                &mut rules,
+                &mut nested_declarations,
+            );
+            let prelude = input
+                .parse_until_before(Delimiter::CurlyBracketBlock, |input| {
+                    QualifiedRuleParser::parse_prelude(self, input)
+                })?;
+            input.expect_curly_bracket_block()?;
+            rules.push(input.parse_nested_block(|input| {
+                QualifiedRuleParser::parse_block(self, prelude, &start, input)
+            })?);
+        }
+        Self::push_nested_declarations(&mut rules, &mut nested_declarations);
+        Ok((PropertyDeclarations(property_declarations), CssRules(rules)))
+    }
+
+    /// Wraps the declarations that followed a nested rule into a `&` rule, so
+    /// they are applied after it, see
+    /// <https://drafts.csswg.org/css-nesting/#nested-declarations-rule>.
+    fn push_nested_declarations(
+        rules: &mut Vec<CssRule>,
+        nested_declarations: &mut Vec<PropertyDeclaration<Importance>>,
+    ) {
+        if nested_declarations.is_empty() {
+            return;
+        }
+        rules.push(CssRule::Style(StyleRule {
+            selectors: DeduplicatedSelectors(vec![Selector::nesting()]),
+            property_declarations: PropertyDeclarations(mem::take(
+                nested_declarations,
+            )),
+            rules: CssRules::default(),
+        }));
+    }
+
+    /// Parses an at-rule nested in a style rule, the at-keyword has already
+    /// been consumed.
+    fn parse_nested_at_rule<'i: 't, 't>(
+        &mut self,
+        name: CowRcStr<'i>,
+        start: &ParserState,
+        input: &mut Parser<'i, 't>,
+    ) -> Result<CssRule, ParseError<'i, CustomParseError<'i>>> {
+        let prelude = input.parse_until_before(
+            Delimiter::Semicolon | Delimiter::CurlyBracketBlock,
+            |input| AtRuleParser::parse_prelude(self, name, input),
+        )?;
+        match input.next() {
+            Ok(&Token::CurlyBracketBlock) => {
+                input.parse_nested_block(|input| {
+                    AtRuleParser::parse_block(self, prelude, start, input)
+                })
+            }
+            _ => self.rule_without_block(prelude, start).map_err(|()| {
+                input.new_error(BasicParseErrorKind::AtRuleBodyInvalid)
+            }),
+        }
+    }
+
+    /// Whether the input starts with a property declaration instead of a
+    /// nested style rule. Like in `a:hover{}`, a nested rule can start with an
+    /// identifier and a colon, so the declaration must not contain a block,
+    /// except for custom properties.
+    fn is_property_declaration(input: &mut Parser) -> bool {
+        let start = input.state();
+        let result: Result<bool, ParseError<()>> =
+            input.parse_until_before(Delimiter::Semicolon, |input| {
+                let is_custom_property = match input.next()? {
+                    Token::Ident(name) => name.starts_with("--"),
+                    _ => return Ok(false),
+                };
+                input.expect_colon()?;
+                while let Ok(token) = input.next() {
+                    if matches!(token, Token::CurlyBracketBlock) {
+                        return Ok(is_custom_property);
+                    }
+                }
+                Ok(true)
+            });
+        input.reset(&start);
+        result.unwrap_or(false)
+    }
 }
@@ -102,6 +102,11 @@ impl<'a, 'i> Parser<'i> for OurSelectorParser<'a> {
         )
     }
 
+    #[inline(always)]
+    fn parse_nesting_selector(&self) -> bool {
+        true
+    }
+
     #[inline(always)]
     fn default_namespace(
         &self,
@@ -128,6 +133,18 @@ impl<'a> OurSelectorParser<'a> {
         self.parse_internal(input, |_| false)
     }
 
+    /// Parses the selectors of a rule that is nested in a style rule, see
+    /// [SelectorList::parse_relative].
+    #[inline(always)]
+    pub(crate) fn parse_relative<'i, 't>(
+        &self,
+        input: &mut CssParser<'i, 't>,
+    ) -> Result<DeduplicatedSelectors, ParseError<'i, CustomParseError<'i>>>
+    {
+        let selectors = SelectorList::parse_relative(self, input)?.0;
+        Self::deduplicate(selectors, |_| false)
+    }
+
     #[inline(always)]
     pub(crate) fn parse_internal<'i, 't, F: Fn(&OurSelector) -> bool>(
         &self,
@@ -136,7 +153,15 @@ impl<'a> OurSelectorParser<'a> {
     ) -> Result<DeduplicatedSelectors, ParseError<'i, CustomParseError<'i>>>
     {
         let selectors = self.parse_selectors(input)?;
+        Self::deduplicate(selectors, isInvalidSelector)
+    }
 
+    #[inline(always)]
+    fn deduplicate<'i, F: Fn(&OurSelector) -> bool>(
+        selectors: smallvec::SmallVec<[OurSelector; 1]>,
+        isInvalidSelector: F,
+    ) -> Result<DeduplicatedSelectors, ParseError<'i, CustomParseError<'i>>>
+    {
         if selectors.is_empty() {
             return Err(ParseError::from(
                 CustomParseError::ThereAreNoSelectors,
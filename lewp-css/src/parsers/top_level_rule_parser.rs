@@ -93,6 +93,19 @@ impl<'i> AtRuleParser<'i> for TopLevelRuleParser {
                 Ok(Self::Prelude::Namespace(self.parseNamespaceAtRule(input)?))
             },
 
+            "layer" =>
+            {
+                if self.state > State::Body
+                {
+                    self.state = State::Invalid;
+                    return Err(ParseError::from(CustomParseError::InvalidParseState));
+                }
+
+                // Statements are allowed before @import, so the state is only changed by the block form.
+                let mut nested = self.nested();
+                <NestedRuleParser as AtRuleParser>::parse_prelude(&mut nested, name.clone(), input)
+            },
+
             _ =>
             {
                 // Don't allow starting with an invalid state
@@ -109,24 +122,31 @@ impl<'i> AtRuleParser<'i> for TopLevelRuleParser {
         }
     }
 
-    //#[inline]
-    //fn rule_without_block(
-    //    &mut self,
-    //    prelude: Self::Prelude,
-    //    _start: &ParserState,
-    //) -> Result<Self::AtRule, ()> {
-    //    use self::State::*;
-
-    //    match prelude {
-    //        Import(..) => self.state = Imports,
-
-    //        Namespace(..) => self.state = Namespaces,
-
-    //        _ => {}
-    //    }
-
-    //    Ok(prelude)
-    //}
+    #[inline]
+    fn rule_without_block(
+        &mut self,
+        prelude: Self::Prelude,
+        start: &ParserState,
+    ) -> Result<Self::AtRule, ()> {
+        match prelude {
+            AtRuleBlockPrelude::Import(rule) => {
+                self.state = State::Imports;
+                Ok(CssRule::Import(rule))
+            }
+            AtRuleBlockPrelude::Namespace(rule) => {
+                self.state = State::Namespaces;
+                Ok(CssRule::Namespace(rule))
+            }
+            prelude => {
+                let mut nested = self.nested();
+                <NestedRuleParser as AtRuleParser>::rule_without_block(
+                    &mut nested,
+                    prelude,
+                    start,
+                )
+            }
+        }
+    }
 
     #[inline]
     fn parse_block<'t>(
@@ -205,6 +225,7 @@ impl TopLevelRuleParser {
         NestedRuleParser {
             context: &self.context,
             namespaces: self.namespaces.clone(),
+            in_style_rule: false,
         }
     }
 
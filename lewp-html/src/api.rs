@@ -3,9 +3,11 @@ use {
         BrowsingContext,
         Charset,
         Document,
+        ImageCandidate,
         Node,
         NodeExt,
         NodeList,
+        ResponsiveImage,
         Script,
     },
     html5ever::{namespace_url, ns, tendril::Tendril, LocalName, QualName},
@@ -262,24 +264,17 @@ pub fn document(language: LanguageTag, head: Node, body: Node) -> Document {
         public_id: Tendril::from(""),
         system_id: Tendril::from(""),
     });
-    dom.document.children.borrow_mut().push(doctype);
-    dom.document
-        .children
-        .borrow_mut()
-        .push(html(language, head, body));
+    dom.document.append_child(doctype);
+    dom.document.append_child(html(language, head, body));
     dom
 }
 
 /// Creates a [html](https://html.spec.whatwg.org/dev/semantics.html#the-html-element) element.
+///
+/// The complete language tag is written to the `lang` attribute, for example
+/// `de-AT` or `zh-Hant-TW`.
 pub fn html(language: LanguageTag, head: Node, body: Node) -> Node {
-    let language = match language.language() {
-        Some(v) => v.primary().as_str().to_owned(),
-        None => {
-            crate::error!("Could not find language for creating <html> tag!");
-            String::new()
-        }
-    };
-    new_element("html", vec![head, body]).attr("lang", &language)
+    new_element("html", vec![head, body]).attr("lang", language.as_str())
 }
 
 /// Creates a [title](https://html.spec.whatwg.org/dev/semantics.html#the-title-element) element.
@@ -359,6 +354,94 @@ pub fn img(src: &Path, title: impl ToString, alt: impl ToString) -> Node {
     ])
 }
 
+/// Creates a [picture](https://html.spec.whatwg.org/dev/embedded-content.html#the-picture-element)
+/// element that lets the browser choose the best fitting format and size of
+/// the given image.
+///
+/// Every [ImageSource] results in a `<source>` element. The `<img>` element
+/// contains the candidates in the fallback format as well as the intrinsic
+/// `width` and `height` of the image to prevent layout shifts.
+///
+/// ```
+/// use {
+///     lewp_html::{api::responsive_image, *},
+///     std::path::PathBuf,
+/// };
+///
+/// let image = ResponsiveImage {
+///     src: PathBuf::from("/img/logo.png"),
+///     width: 512,
+///     height: 256,
+///     alt: String::from("Logo"),
+///     sizes: String::from("50vw"),
+///     sources: vec![ImageSource {
+///         mime_type: String::from("image/webp"),
+///         candidates: vec![ImageCandidate {
+///             url: PathBuf::from("/img/logo.256w.webp"),
+///             width: 256,
+///         }],
+///     }],
+///     candidates: vec![ImageCandidate {
+///         url: PathBuf::from("/img/logo.256w.png"),
+///         width: 256,
+///     }],
+/// };
+/// let picture = responsive_image(&image);
+/// let source = picture.query_selector("source").unwrap().unwrap();
+/// assert_eq!(
+///     source.attribute_value("srcset").as_deref(),
+///     Some("/img/logo.256w.webp 256w")
+/// );
+/// let img = picture.query_selector("img").unwrap().unwrap();
+/// assert_eq!(
+///     img.attribute_value("srcset").as_deref(),
+///     Some("/img/logo.256w.png 256w, /img/logo.png 512w")
+/// );
+/// assert_eq!(img.attribute_value("width").as_deref(), Some("512"));
+/// ```
+pub fn responsive_image(image: &ResponsiveImage) -> Node {
+    let mut children = vec![];
+    for source in &image.sources {
+        if source.candidates.is_empty() {
+            continue;
+        }
+        let mut e = new_element("source", vec![]).attrs(vec![
+            ("type", &source.mime_type),
+            ("srcset", &ImageCandidate::srcset(&source.candidates)),
+        ]);
+        if !image.sizes.is_empty() {
+            e = e.attr("sizes", &image.sizes);
+        }
+        children.push(e);
+    }
+
+    let mut candidates = image.candidates.clone();
+    if image.width > 0 && !candidates.iter().any(|c| c.url == image.src) {
+        candidates.push(ImageCandidate {
+            url: image.src.clone(),
+            width: image.width,
+        });
+    }
+    candidates.sort_by_key(|c| c.width);
+
+    let mut img = new_element("img", vec![])
+        .attr("src", &format!("{}", image.src.display()));
+    if candidates.len() > 1 {
+        img = img.attr("srcset", &ImageCandidate::srcset(&candidates));
+        if !image.sizes.is_empty() {
+            img = img.attr("sizes", &image.sizes);
+        }
+    }
+    if image.width > 0 && image.height > 0 {
+        img = img.attrs(vec![
+            ("width", &image.width.to_string()),
+            ("height", &image.height.to_string()),
+        ]);
+    }
+    children.push(img.attr("alt", &image.alt));
+    new_element("picture", children)
+}
+
 /// Creates an [param](https://html.spec.whatwg.org/dev/iframe-embed-object.html#the-param-element) element.
 pub fn param(name: impl ToString, value: impl ToString) -> Node {
     new_element("param", vec![]).attrs(vec![
@@ -406,6 +489,24 @@ pub fn description(description: impl ToString) -> Node {
     ])
 }
 
+/// Creates a `<link rel="alternate">` node that references the version of the
+/// page in the given language. Use `x-default` as `hreflang` to reference the
+/// version for all languages that are not listed.
+///
+/// ```
+/// use lewp_html::{api::alternate, NodeExt};
+///
+/// let link = alternate("de-AT", "https://example.com/de-at/");
+/// assert_eq!(link.attribute_value("hreflang").as_deref(), Some("de-AT"));
+/// ```
+pub fn alternate(hreflang: impl ToString, href: impl ToString) -> Node {
+    new_element("link", vec![]).attrs(vec![
+        ("rel", "alternate"),
+        ("hreflang", &hreflang.to_string()),
+        ("href", &href.to_string()),
+    ])
+}
+
 /// Creates a `<meta>` viewport tag node.
 pub fn viewport() -> Node {
     meta().attrs(vec![
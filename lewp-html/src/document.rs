@@ -1,6 +1,12 @@
 //! Document definition.
 
 use {
+    crate::{
+        selectable_node::{parse_selectors, select},
+        Node,
+        NodeList,
+        SelectorError,
+    },
     html5ever::{
         parse_document,
         serialize::{serialize, SerializeOpts},
@@ -34,4 +40,22 @@ impl crate::DocumentExt for Document {
             .from_utf8()
             .read_from(&mut s.as_bytes())
     }
+
+    fn query_selector(
+        &self,
+        selectors: &str,
+    ) -> Result<Option<Node>, SelectorError> {
+        let selectors = parse_selectors(selectors)?;
+        Ok(select(&self.document.children.borrow(), &selectors, true)
+            .into_iter()
+            .next())
+    }
+
+    fn query_selector_all(
+        &self,
+        selectors: &str,
+    ) -> Result<NodeList, SelectorError> {
+        let selectors = parse_selectors(selectors)?;
+        Ok(select(&self.document.children.borrow(), &selectors, false))
+    }
 }
@@ -1,5 +1,7 @@
 //! Easy handling of an HTML document.
 
+use crate::{Node, NodeList, SelectorError};
+
 /// Methods for easy handling of an HTML document.
 pub trait DocumentExt {
     /// Converts the given node to an HTML string.
@@ -8,4 +10,41 @@ pub trait DocumentExt {
     fn from_string(s: String) -> Result<Self, std::io::Error>
     where
         Self: Sized;
+    /// Returns the first element in document order that matches the given
+    /// comma separated list of CSS selectors, or `None` if nothing matches.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [SelectorError] if the selectors are invalid.
+    ///
+    /// ```
+    /// use lewp_html::{api::*, DocumentExt, LanguageTag, NodeExt};
+    ///
+    /// let document = document(
+    ///     LanguageTag::parse("en").unwrap(),
+    ///     head(vec![]),
+    ///     body(vec![h1(vec![text("Hello World")]).attr("id", "title")]),
+    /// );
+    /// let title = document
+    ///     .query_selector("html > body > #title")
+    ///     .unwrap()
+    ///     .unwrap();
+    /// assert_eq!(title.tag_name(), Some(String::from("h1")));
+    /// assert!(document.query_selector("main").unwrap().is_none());
+    /// assert!(document.query_selector("main >").is_err());
+    /// ```
+    fn query_selector(
+        &self,
+        selectors: &str,
+    ) -> Result<Option<Node>, SelectorError>;
+    /// Returns all elements in document order that match the given comma
+    /// separated list of CSS selectors.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [SelectorError] if the selectors are invalid.
+    fn query_selector_all(
+        &self,
+        selectors: &str,
+    ) -> Result<NodeList, SelectorError>;
 }
@@ -29,7 +29,7 @@
 //!        ])
 //! ).into_html();
 //!
-//! let expected_html = "<!DOCTYPE html><html lang=\"de\"><head><script src=\"/my-javascript\"></script><script>console.log(\"hello world!\");</script><link href=\"/static/css/main.css\" type=\"text/css\"></head><body><h1>Hello World</h1><p class=\"prelude\" id=\"first-paragraph\">This is a paragraph!</p><h2>The elegant way to create a DOM!</h2><p class=\"highlighted\">Creating DOM has never been easier.</p></body></html>";
+//! let expected_html = "<!DOCTYPE html><html lang=\"de-DE\"><head><script src=\"/my-javascript\"></script><script>console.log(\"hello world!\");</script><link href=\"/static/css/main.css\" type=\"text/css\"></head><body><h1>Hello World</h1><p class=\"prelude\" id=\"first-paragraph\">This is a paragraph!</p><h2>The elegant way to create a DOM!</h2><p class=\"highlighted\">Creating DOM has never been easier.</p></body></html>";
 //!
 //! assert_eq!(&valid_html, expected_html);
 //! ```
@@ -44,6 +44,9 @@ mod node;
 mod node_ext;
 mod node_list;
 mod node_list_ext;
+mod owned_node;
+mod selectable_node;
+mod selector_error;
 mod types;
 
 pub use {
@@ -55,6 +58,9 @@ pub use {
     node_ext::NodeExt,
     node_list::NodeList,
     node_list_ext::NodeListExt,
+    owned_node::OwnedNode,
+    selectable_node::SelectableNode,
+    selector_error::SelectorError,
     types::*,
 };
 
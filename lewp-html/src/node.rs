@@ -18,6 +18,7 @@ impl crate::NodeExt for Node {
         }
     }
     fn append_child(&self, child: Node) {
+        child.parent.set(Some(Rc::downgrade(self)));
         self.children.borrow_mut().push(child);
     }
 }
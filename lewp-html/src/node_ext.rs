@@ -1,7 +1,12 @@
 //! Trait for DOM node interactions.
 
 use {
-    crate::Node,
+    crate::{
+        selectable_node::{parse_selectors, select},
+        Node,
+        NodeList,
+        SelectorError,
+    },
     html5ever::{
         namespace_url,
         ns,
@@ -103,6 +108,16 @@ where
         }
         None
     }
+    /// Returns the value of the given attribute if present.
+    fn attribute_value(&self, attribute_name: &str) -> Option<String> {
+        let index = self.find_attribute(attribute_name)?;
+        match &self.data() {
+            NodeData::Element { attrs, .. } => {
+                Some(attrs.borrow()[index].value.to_string())
+            }
+            _ => None,
+        }
+    }
     /// Checks if the given attribute matches the value.
     fn attribute_eq(&self, attribute_name: &str, value: &str) -> bool {
         if let Some(index) = self.find_attribute(attribute_name) {
@@ -217,4 +232,62 @@ where
             attrs.remove(index);
         };
     }
+
+    /// Returns the first descendant element in document order that matches
+    /// the given comma separated list of CSS selectors, or `None` if nothing
+    /// matches.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [SelectorError] if the selectors are invalid.
+    ///
+    /// ```
+    /// use lewp_html::{api::*, NodeExt};
+    ///
+    /// let nav = nav(vec![
+    ///     a("/", vec![text("Home")]),
+    ///     a("/about", vec![text("About")]).attr("class", "active"),
+    /// ]);
+    /// let active = nav.query_selector("nav > a.active").unwrap().unwrap();
+    /// assert!(active.attribute_eq("href", "/about"));
+    /// ```
+    fn query_selector(
+        &self,
+        selectors: &str,
+    ) -> Result<Option<Node>, SelectorError> {
+        let selectors = parse_selectors(selectors)?;
+        Ok(select(&self.children().borrow(), &selectors, true)
+            .into_iter()
+            .next())
+    }
+
+    /// Returns all descendant elements in document order that match the
+    /// given comma separated list of CSS selectors. The list is empty if
+    /// nothing matches.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [SelectorError] if the selectors are invalid.
+    ///
+    /// ```
+    /// use lewp_html::{api::*, NodeExt};
+    ///
+    /// let list = ul(vec![
+    ///     li(vec![text("One")]),
+    ///     li(vec![text("Two")]).attr("class", "highlighted"),
+    ///     li(vec![text("Three")]),
+    /// ]);
+    /// assert_eq!(list.query_selector_all("li").unwrap().len(), 3);
+    /// assert_eq!(list.query_selector_all("li:not(.highlighted)").unwrap().len(), 2);
+    /// assert_eq!(list.query_selector_all("li + li").unwrap().len(), 2);
+    /// assert_eq!(list.query_selector_all(".highlighted ~ li").unwrap().len(), 1);
+    /// assert_eq!(list.query_selector_all("li:last-child").unwrap().len(), 1);
+    /// ```
+    fn query_selector_all(
+        &self,
+        selectors: &str,
+    ) -> Result<NodeList, SelectorError> {
+        let selectors = parse_selectors(selectors)?;
+        Ok(select(&self.children().borrow(), &selectors, false))
+    }
 }
@@ -1,4 +1,26 @@
-use crate::Node;
+use crate::{
+    selectable_node::{parse_selectors, select},
+    Node,
+    SelectorError,
+};
 
 /// A list of nodes.
 pub type NodeList = Vec<Node>;
+
+impl crate::NodeListExt for NodeList {
+    fn query_selector(
+        &self,
+        selectors: &str,
+    ) -> Result<Option<Node>, SelectorError> {
+        let selectors = parse_selectors(selectors)?;
+        Ok(select(self, &selectors, true).into_iter().next())
+    }
+
+    fn query_selector_all(
+        &self,
+        selectors: &str,
+    ) -> Result<NodeList, SelectorError> {
+        let selectors = parse_selectors(selectors)?;
+        Ok(select(self, &selectors, false))
+    }
+}
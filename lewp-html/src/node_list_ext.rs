@@ -1,4 +1,4 @@
-use crate::{Document, DocumentExt, NodeList};
+use crate::{Document, DocumentExt, Node, NodeList, SelectorError};
 
 /// Useful functions and methods for working with a [NodeList].
 pub trait NodeListExt
@@ -10,4 +10,26 @@ where
         let s = Document::from_string(s)?;
         Ok(s.document.children.take())
     }
+    /// Returns the first element in document order that matches the given
+    /// comma separated list of CSS selectors, or `None` if nothing matches.
+    /// The nodes of the list are matched as well as their descendants.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [SelectorError] if the selectors are invalid.
+    fn query_selector(
+        &self,
+        selectors: &str,
+    ) -> Result<Option<Node>, SelectorError>;
+    /// Returns all elements in document order that match the given comma
+    /// separated list of CSS selectors. The nodes of the list are matched as
+    /// well as their descendants.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [SelectorError] if the selectors are invalid.
+    fn query_selector_all(
+        &self,
+        selectors: &str,
+    ) -> Result<NodeList, SelectorError>;
 }
@@ -0,0 +1,172 @@
+//! A thread safe copy of a [Node] tree.
+
+use {
+    crate::{Node, NodeExt},
+    html5ever::{tendril::Tendril, Attribute, QualName},
+    rcdom::NodeData,
+    std::cell::RefCell,
+};
+
+/// An owned copy of a [Node] and all its descendants that can be sent to
+/// other threads.
+///
+/// A [Node] is reference counted and can not leave the thread it has been
+/// created on. Convert it into an [OwnedNode] to move it to another thread or
+/// to hold it across an `.await` of a multi-threaded runtime, and back into a
+/// [Node] using [OwnedNode::to_node].
+///
+/// ```
+/// use lewp_html::{api::*, NodeExt, OwnedNode};
+///
+/// let node = p(vec![text("Hello World!")]).attr("class", "greeting");
+/// let owned = OwnedNode::from(&node);
+/// let class = std::thread::spawn(move || {
+///     let node = owned.to_node();
+///     node.attribute_value("class")
+/// })
+/// .join()
+/// .unwrap();
+/// assert_eq!(class.as_deref(), Some("greeting"));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct OwnedNode {
+    data: OwnedNodeData,
+    children: Vec<OwnedNode>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum OwnedNodeData {
+    Document,
+    Doctype {
+        name: String,
+        public_id: String,
+        system_id: String,
+    },
+    Text(String),
+    Comment(String),
+    Element {
+        name: QualName,
+        attrs: Vec<(QualName, String)>,
+        template_contents: Option<Box<OwnedNode>>,
+        mathml_annotation_xml_integration_point: bool,
+    },
+    ProcessingInstruction {
+        target: String,
+        contents: String,
+    },
+}
+
+impl OwnedNode {
+    /// Creates a new [Node] tree from this copy.
+    pub fn to_node(&self) -> Node {
+        let data = match &self.data {
+            OwnedNodeData::Document => NodeData::Document,
+            OwnedNodeData::Doctype {
+                name,
+                public_id,
+                system_id,
+            } => NodeData::Doctype {
+                name: Tendril::from(name.as_str()),
+                public_id: Tendril::from(public_id.as_str()),
+                system_id: Tendril::from(system_id.as_str()),
+            },
+            OwnedNodeData::Text(contents) => NodeData::Text {
+                contents: RefCell::new(Tendril::from(contents.as_str())),
+            },
+            OwnedNodeData::Comment(contents) => NodeData::Comment {
+                contents: Tendril::from(contents.as_str()),
+            },
+            OwnedNodeData::Element {
+                name,
+                attrs,
+                template_contents,
+                mathml_annotation_xml_integration_point,
+            } => NodeData::Element {
+                name: name.clone(),
+                attrs: RefCell::new(
+                    attrs
+                        .iter()
+                        .map(|(name, value)| Attribute {
+                            name: name.clone(),
+                            value: Tendril::from(value.as_str()),
+                        })
+                        .collect(),
+                ),
+                template_contents: RefCell::new(
+                    template_contents.as_ref().map(|t| t.to_node()),
+                ),
+                mathml_annotation_xml_integration_point:
+                    *mathml_annotation_xml_integration_point,
+            },
+            OwnedNodeData::ProcessingInstruction { target, contents } => {
+                NodeData::ProcessingInstruction {
+                    target: Tendril::from(target.as_str()),
+                    contents: Tendril::from(contents.as_str()),
+                }
+            }
+        };
+        let node = rcdom::Node::new(data);
+        for child in &self.children {
+            node.append_child(child.to_node());
+        }
+        node
+    }
+}
+
+impl From<&Node> for OwnedNode {
+    fn from(node: &Node) -> Self {
+        let data = match &node.data {
+            NodeData::Document => OwnedNodeData::Document,
+            NodeData::Doctype {
+                name,
+                public_id,
+                system_id,
+            } => OwnedNodeData::Doctype {
+                name: name.to_string(),
+                public_id: public_id.to_string(),
+                system_id: system_id.to_string(),
+            },
+            NodeData::Text { contents } => {
+                OwnedNodeData::Text(contents.borrow().to_string())
+            }
+            NodeData::Comment { contents } => {
+                OwnedNodeData::Comment(contents.to_string())
+            }
+            NodeData::Element {
+                name,
+                attrs,
+                template_contents,
+                mathml_annotation_xml_integration_point,
+            } => OwnedNodeData::Element {
+                name: name.clone(),
+                attrs: attrs
+                    .borrow()
+                    .iter()
+                    .map(|a| (a.name.clone(), a.value.to_string()))
+                    .collect(),
+                template_contents: template_contents
+                    .borrow()
+                    .as_ref()
+                    .map(|t| Box::new(Self::from(t))),
+                mathml_annotation_xml_integration_point:
+                    *mathml_annotation_xml_integration_point,
+            },
+            NodeData::ProcessingInstruction { target, contents } => {
+                OwnedNodeData::ProcessingInstruction {
+                    target: target.to_string(),
+                    contents: contents.to_string(),
+                }
+            }
+        };
+        Self {
+            data,
+            children: node.children.borrow().iter().map(Self::from).collect(),
+        }
+    }
+}
+
+impl From<Node> for OwnedNode {
+    fn from(node: Node) -> Self {
+        Self::from(&node)
+    }
+}
@@ -0,0 +1,475 @@
+//! Connects the [Node] to the CSS selector engine.
+
+use {
+    crate::{Node, NodeExt, NodeList, SelectorError},
+    html5ever::{namespace_url, ns, QualName},
+    lewp_css::domain::{
+        selectors::{
+            matches::matches,
+            DeduplicatedSelectors,
+            NonTreeStructuralPseudoClass,
+            OurSelectorImpl,
+            PseudoElement,
+        },
+        Atom,
+    },
+    rcdom::NodeData,
+    selectors::{
+        attr::{AttrSelectorOperation, CaseSensitivity, NamespaceConstraint},
+        matching::{
+            matches_complex_selector,
+            ElementSelectorFlags,
+            MatchingContext,
+        },
+        Element,
+        OpaqueElement,
+    },
+    std::{borrow::Borrow, cell::Cell, rc::Rc},
+};
+
+/// An element [Node] that can be matched against CSS selectors parsed by
+/// [lewp_css].
+///
+/// Only nodes of type [NodeData::Element] can be wrapped. The tree is
+/// traversed using the parent of the node, so combinators like `div > p`
+/// work as long as the tree has been built with [NodeExt::append_child]
+/// or parsed from a string.
+#[derive(Debug, Clone)]
+pub struct SelectableNode {
+    node: Node,
+    ignore_dynamic_state: bool,
+    /// The index within the children of the parent, if known.
+    position: Cell<Option<usize>>,
+}
+
+impl SelectableNode {
+    /// Wraps the given node. Returns `None` if the node is not an element.
+    pub fn new(node: Node) -> Option<Self> {
+        match node.data {
+            NodeData::Element { .. } => Some(Self {
+                node,
+                ignore_dynamic_state: false,
+                position: Cell::new(None),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Assumes that every state that can change on the client, for example
+    /// `:hover` or `:focus`, as well as every pseudo element like `::before`
+    /// matches. Within `:not()` these states are assumed to not match, so
+    /// `a:not(:hover)` matches every link. States that are set by HTML
+    /// attributes, like `:disabled` or `:checked`, are still matched against
+    /// the attributes. Use this to find out whether a selector can apply to
+    /// the node at all.
+    pub fn with_ignored_dynamic_state(mut self) -> Self {
+        self.ignore_dynamic_state = true;
+        self
+    }
+
+    fn wrap(&self, node: Node) -> Option<Self> {
+        let ignore_dynamic_state = self.ignore_dynamic_state;
+        Self::new(node).map(|n| Self {
+            ignore_dynamic_state,
+            ..n
+        })
+    }
+
+    /// Returns the wrapped [Node].
+    pub fn node(&self) -> &Node {
+        &self.node
+    }
+
+    /// Consumes the wrapper and returns the wrapped [Node].
+    pub fn into_node(self) -> Node {
+        self.node
+    }
+
+    /// True if the node matches at least one of the given selectors.
+    pub fn matches(&self, selectors: &DeduplicatedSelectors) -> bool {
+        selectors.0.iter().any(|s| matches(s, self))
+    }
+
+    fn name(&self) -> Option<&QualName> {
+        match &self.node.data {
+            NodeData::Element { name, .. } => Some(name),
+            _ => None,
+        }
+    }
+
+    fn attribute(&self, local_name: &str) -> Option<String> {
+        match &self.node.data {
+            NodeData::Element { attrs, .. } => attrs
+                .borrow()
+                .iter()
+                .find(|a| a.name.ns == ns!() && &*a.name.local == local_name)
+                .map(|a| a.value.to_string()),
+            _ => None,
+        }
+    }
+
+    fn parent_node(&self) -> Option<Node> {
+        let parent = self.node.parent.take();
+        self.node.parent.set(parent.clone());
+        parent.and_then(|p| p.upgrade())
+    }
+
+    /// Returns the index of the node within the given children of its parent.
+    fn position_in(&self, children: &[Node]) -> Option<usize> {
+        if let Some(p) = self.position.get() {
+            if children.get(p).is_some_and(|c| Rc::ptr_eq(c, &self.node)) {
+                return Some(p);
+            }
+        }
+        let p = children.iter().position(|c| Rc::ptr_eq(c, &self.node))?;
+        self.position.set(Some(p));
+        Some(p)
+    }
+
+    /// Returns the first element of the parent's children at the given
+    /// indexes. The index is kept, so walking along the siblings does not
+    /// need to search the node again.
+    fn sibling_element(
+        &self,
+        children: &[Node],
+        mut indexes: impl Iterator<Item = usize>,
+    ) -> Option<Self> {
+        indexes.find_map(|i| {
+            let sibling = self.wrap(Rc::clone(&children[i]))?;
+            sibling.position.set(Some(i));
+            Some(sibling)
+        })
+    }
+
+    fn matches_non_ts_pseudo_class<F>(
+        &self,
+        pc: &NonTreeStructuralPseudoClass,
+        context: &mut MatchingContext<OurSelectorImpl>,
+        flags_setter: &mut F,
+    ) -> bool
+    where
+        F: FnMut(&Self, ElementSelectorFlags),
+    {
+        use NonTreeStructuralPseudoClass::*;
+        match pc {
+            is(selectors) | where_(selectors) | any(_, selectors) => {
+                // keep the context, a dynamic state inside of :not(:is(..))
+                // still has to be assumed to not match
+                context.nest(|context| {
+                    selectors.0.iter().any(|s| {
+                        matches_complex_selector(
+                            s.iter(),
+                            self,
+                            context,
+                            flags_setter,
+                        )
+                    })
+                })
+            }
+            link | any_link(_) => self.is_link(),
+            checked => {
+                self.attribute("checked").is_some()
+                    || self.attribute("selected").is_some()
+            }
+            disabled => self.attribute("disabled").is_some(),
+            enabled => {
+                self.is_form_element() && self.attribute("disabled").is_none()
+            }
+            required => self.attribute("required").is_some(),
+            optional => {
+                self.is_form_element() && self.attribute("required").is_none()
+            }
+            _ if self.ignore_dynamic_state => !context.is_negated(),
+            _ => false,
+        }
+    }
+
+    fn is_form_element(&self) -> bool {
+        [
+            "button", "input", "select", "textarea", "optgroup", "option",
+            "fieldset",
+        ]
+        .iter()
+        .any(|n| self.has_local_name(n))
+    }
+}
+
+impl Element for SelectableNode {
+    type Impl = OurSelectorImpl;
+
+    fn opaque(&self) -> OpaqueElement {
+        OpaqueElement::new::<rcdom::Node>(&self.node)
+    }
+
+    fn parent_element(&self) -> Option<Self> {
+        self.parent_node().and_then(|p| self.wrap(p))
+    }
+
+    fn parent_node_is_shadow_root(&self) -> bool {
+        false
+    }
+
+    fn containing_shadow_host(&self) -> Option<Self> {
+        None
+    }
+
+    fn pseudo_element_originating_element(&self) -> Option<Self> {
+        Some(self.clone())
+    }
+
+    fn is_pseudo_element(&self) -> bool {
+        false
+    }
+
+    fn prev_sibling_element(&self) -> Option<Self> {
+        let parent = self.parent_node()?;
+        let children = parent.children.borrow();
+        let position = self.position_in(&children)?;
+        self.sibling_element(&children, (0..position).rev())
+    }
+
+    fn next_sibling_element(&self) -> Option<Self> {
+        let parent = self.parent_node()?;
+        let children = parent.children.borrow();
+        let position = self.position_in(&children)?;
+        self.sibling_element(&children, position + 1..children.len())
+    }
+
+    fn is_html_element_in_html_document(&self) -> bool {
+        self.name().map(|n| n.ns == ns!(html)).unwrap_or(false)
+    }
+
+    fn has_local_name(&self, local_name: &str) -> bool {
+        self.name()
+            .map(|n| &*n.local == local_name)
+            .unwrap_or(false)
+    }
+
+    fn has_namespace(&self, ns: &str) -> bool {
+        self.name().map(|n| &*n.ns == ns).unwrap_or(false)
+    }
+
+    fn is_same_type(&self, other: &Self) -> bool {
+        match (self.name(), other.name()) {
+            (Some(a), Some(b)) => a.local == b.local && a.ns == b.ns,
+            _ => false,
+        }
+    }
+
+    fn attr_matches(
+        &self,
+        ns: &NamespaceConstraint<
+            &lewp_css::domain::at_rules::namespace::NamespaceUrl,
+        >,
+        local_name: &Atom,
+        operation: &AttrSelectorOperation<&Atom>,
+    ) -> bool {
+        let attrs = match &self.node.data {
+            NodeData::Element { attrs, .. } => attrs.borrow(),
+            _ => return false,
+        };
+        attrs.iter().any(|a| {
+            let ns_matches = match ns {
+                NamespaceConstraint::Any => true,
+                NamespaceConstraint::Specific(url) => {
+                    let url: &str = (*url).borrow();
+                    &*a.name.ns == url
+                }
+            };
+            ns_matches
+                && *a.name.local == **local_name
+                && operation.eval_str(&a.value)
+        })
+    }
+
+    fn match_non_ts_pseudo_class<F>(
+        &self,
+        pc: &NonTreeStructuralPseudoClass,
+        context: &mut MatchingContext<Self::Impl>,
+        flags_setter: &mut F,
+    ) -> bool
+    where
+        F: FnMut(&Self, ElementSelectorFlags),
+    {
+        self.matches_non_ts_pseudo_class(pc, context, flags_setter)
+    }
+
+    fn match_pseudo_element(
+        &self,
+        _pe: &PseudoElement,
+        context: &mut MatchingContext<Self::Impl>,
+    ) -> bool {
+        self.ignore_dynamic_state && !context.is_negated()
+    }
+
+    fn is_link(&self) -> bool {
+        (self.has_local_name("a")
+            || self.has_local_name("area")
+            || self.has_local_name("link"))
+            && self.attribute("href").is_some()
+    }
+
+    fn is_html_slot_element(&self) -> bool {
+        self.is_html_element_in_html_document() && self.has_local_name("slot")
+    }
+
+    fn has_id(&self, id: &Atom, case_sensitivity: CaseSensitivity) -> bool {
+        self.attribute("id")
+            .map(|v| case_sensitivity.eq(v.as_bytes(), id.as_bytes()))
+            .unwrap_or(false)
+    }
+
+    fn has_class(
+        &self,
+        name: &Atom,
+        case_sensitivity: CaseSensitivity,
+    ) -> bool {
+        self.attribute("class")
+            .map(|v| {
+                v.split_whitespace()
+                    .any(|c| case_sensitivity.eq(c.as_bytes(), name.as_bytes()))
+            })
+            .unwrap_or(false)
+    }
+
+    fn imported_part(&self, _name: &Atom) -> Option<Atom> {
+        None
+    }
+
+    fn is_part(&self, _name: &Atom) -> bool {
+        false
+    }
+
+    fn is_empty(&self) -> bool {
+        self.node.children.borrow().iter().all(|c| match &c.data {
+            NodeData::Element { .. } => false,
+            NodeData::Text { contents } => contents.borrow().is_empty(),
+            _ => true,
+        })
+    }
+
+    fn is_root(&self) -> bool {
+        matches!(
+            self.parent_node()
+                .map(|p| matches!(p.data, NodeData::Document)),
+            Some(true)
+        )
+    }
+}
+
+/// Collects the given nodes and all their descendants in document order that
+/// match the selectors. Stops after the first match if `first_only` is true.
+pub(crate) fn select(
+    nodes: &[Node],
+    selectors: &DeduplicatedSelectors,
+    first_only: bool,
+) -> NodeList {
+    let mut found = vec![];
+    collect(nodes, selectors, first_only, &mut found);
+    found
+}
+
+fn collect(
+    nodes: &[Node],
+    selectors: &DeduplicatedSelectors,
+    first_only: bool,
+    found: &mut NodeList,
+) {
+    for node in nodes {
+        if first_only && !found.is_empty() {
+            return;
+        }
+        if let Some(element) = SelectableNode::new(Rc::clone(node)) {
+            if element.matches(selectors) {
+                found.push(element.into_node());
+            }
+        }
+        collect(&node.children().borrow(), selectors, first_only, found);
+    }
+}
+
+/// Parses the given comma separated list of selectors.
+pub(crate) fn parse_selectors(
+    selectors: &str,
+) -> Result<DeduplicatedSelectors, SelectorError> {
+    lewp_css::parse_css_selector_list(selectors)
+        .map_err(|e| SelectorError::new(selectors, format!("{:?}", e.kind)))
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{api::*, DocumentExt, LanguageTag, NodeListExt},
+    };
+
+    fn list() -> Node {
+        ul(vec![
+            li(vec![text("One")]).attr("class", "first"),
+            li(vec![a("/two", vec![text("Two")])]),
+            li(vec![]).attr("data-state", "empty"),
+        ])
+    }
+
+    fn query_all(node: &Node, selectors: &str) -> usize {
+        node.query_selector_all(selectors).unwrap().len()
+    }
+
+    #[test]
+    fn match_combinators_and_pseudo_classes() {
+        let list = list();
+        assert_eq!(query_all(&list, "ul > li"), 3);
+        assert_eq!(query_all(&list, "li a"), 1);
+        assert_eq!(query_all(&list, ".first + li"), 1);
+        assert_eq!(query_all(&list, ".first ~ li"), 2);
+        assert_eq!(query_all(&list, "li:first-child, li:last-child"), 2);
+        assert_eq!(query_all(&list, "li:nth-child(2n+1)"), 2);
+        assert_eq!(query_all(&list, "li:empty"), 1);
+        assert_eq!(query_all(&list, "[data-state=empty]"), 1);
+        assert_eq!(query_all(&list, "li:not(.first)"), 2);
+        assert_eq!(query_all(&list, "li:is(.first, :empty)"), 2);
+        assert_eq!(query_all(&list, ":any-link"), 1);
+        assert_eq!(query_all(&list, "li:hover"), 0);
+        // the node itself is not a descendant
+        assert_eq!(query_all(&list, "ul"), 0);
+    }
+
+    #[test]
+    fn return_the_first_match_in_document_order() {
+        let list = list();
+        let first = list.query_selector("li").unwrap().unwrap();
+        assert!(first.attribute_eq("class", "first"));
+        assert!(list.query_selector("p").unwrap().is_none());
+    }
+
+    #[test]
+    fn match_the_nodes_of_a_list() {
+        let nodes = vec![list(), p(vec![])];
+        assert_eq!(nodes.query_selector_all("ul, p, li").unwrap().len(), 5);
+        let p = nodes.query_selector("p").unwrap().unwrap();
+        assert_eq!(p.tag_name(), Some(String::from("p")));
+    }
+
+    #[test]
+    fn match_the_root_of_a_document() {
+        let document = document(
+            LanguageTag::parse("en").unwrap(),
+            head(vec![]),
+            body(vec![list()]),
+        );
+        let root = document.query_selector(":root").unwrap().unwrap();
+        assert_eq!(root.tag_name(), Some(String::from("html")));
+        assert_eq!(document.query_selector_all("body li").unwrap().len(), 3);
+    }
+
+    #[test]
+    fn report_invalid_selectors() {
+        let list = list();
+        for selectors in ["li >", "li:unknown", "", "li,,a"] {
+            let error = list.query_selector_all(selectors).unwrap_err();
+            assert_eq!(error.selectors(), selectors);
+            assert!(list.query_selector(selectors).is_err());
+            assert!(vec![list.clone()].query_selector(selectors).is_err());
+        }
+    }
+}
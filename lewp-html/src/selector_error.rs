@@ -0,0 +1,45 @@
+//! The error of an invalid CSS selector.
+
+use std::fmt::{self, Display, Formatter};
+
+/// Returned by the `query_selector` methods if the given selectors are not a
+/// valid comma separated list of CSS selectors.
+///
+/// ```
+/// use lewp_html::{api::*, NodeExt};
+///
+/// let list = ul(vec![li(vec![text("One")])]);
+/// let error = list.query_selector_all("li:unknown").unwrap_err();
+/// assert_eq!(error.selectors(), "li:unknown");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelectorError {
+    selectors: String,
+    reason: String,
+}
+
+impl SelectorError {
+    pub(crate) fn new(selectors: &str, reason: String) -> Self {
+        Self {
+            selectors: selectors.to_string(),
+            reason,
+        }
+    }
+
+    /// The selectors that could not be parsed.
+    pub fn selectors(&self) -> &str {
+        &self.selectors
+    }
+}
+
+impl Display for SelectorError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Invalid selector \"{}\": {}",
+            self.selectors, self.reason
+        )
+    }
+}
+
+impl std::error::Error for SelectorError {}
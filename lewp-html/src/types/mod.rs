@@ -1,6 +1,11 @@
 //! Contains types required by the API.
 
 mod browsing_context;
+mod responsive_image;
 mod script;
 
-pub use {browsing_context::BrowsingContext, script::Script};
+pub use {
+    browsing_context::BrowsingContext,
+    responsive_image::{ImageCandidate, ImageSource, ResponsiveImage},
+    script::Script,
+};
@@ -0,0 +1,54 @@
+use std::path::PathBuf;
+
+/// An image candidate of a `srcset` attribute.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageCandidate {
+    /// The URL of the image.
+    pub url: PathBuf,
+    /// The intrinsic width of the image in pixels.
+    pub width: u32,
+}
+
+impl ImageCandidate {
+    /// Creates the value of a `srcset` attribute from the given candidates,
+    /// for example `/img/logo.320w.png 320w, /img/logo.png 512w`.
+    pub fn srcset(candidates: &[ImageCandidate]) -> String {
+        candidates
+            .iter()
+            .map(|c| format!("{} {}w", c.url.display(), c.width))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// A set of image candidates that share the same MIME type. Rendered as
+/// `<source>` element of a `<picture>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageSource {
+    /// The MIME type of the candidates, for example `image/webp`.
+    pub mime_type: String,
+    /// The available candidates.
+    pub candidates: Vec<ImageCandidate>,
+}
+
+/// An image that is available in different sizes and formats. Use
+/// [responsive_image](crate::api::responsive_image) to render it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResponsiveImage {
+    /// The URL of the image that is used by clients that do not support
+    /// `srcset`.
+    pub src: PathBuf,
+    /// The intrinsic width of the image in pixels.
+    pub width: u32,
+    /// The intrinsic height of the image in pixels.
+    pub height: u32,
+    /// The alternative text.
+    pub alt: String,
+    /// The value of the `sizes` attribute, for example
+    /// `(max-width: 600px) 100vw, 50vw`.
+    pub sizes: String,
+    /// The sources in other formats, in order of preference.
+    pub sources: Vec<ImageSource>,
+    /// The candidates in the format of [src](Self::src).
+    pub candidates: Vec<ImageCandidate>,
+}
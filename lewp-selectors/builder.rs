@@ -329,6 +329,7 @@ where
             | Component::Root
             | Component::Empty
             | Component::Scope
+            | Component::Nesting
             | Component::NthChild(..)
             | Component::NthLastChild(..)
             | Component::NthOfType(..)
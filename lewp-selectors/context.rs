@@ -136,6 +136,9 @@ where
     /// Whether we're inside a negation or not.
     in_negation: bool,
 
+    /// Whether we're inside an odd number of negations.
+    negated: bool,
+
     /// An optional hook function for checking whether a pseudo-element
     /// should match when matching_mode is ForStatelessPseudoElement.
     pub pseudo_element_matching_fn:
@@ -189,6 +192,7 @@ where
             current_host: None,
             nesting_level: 0,
             in_negation: false,
+            negated: false,
             pseudo_element_matching_fn: None,
             extra_data: Default::default(),
             _impl: ::std::marker::PhantomData,
@@ -207,6 +211,13 @@ where
         self.in_negation
     }
 
+    /// Whether the result of the selector we're matching is inverted, that is
+    /// we're inside an odd number of :not(..) selectors.
+    #[inline]
+    pub fn is_negated(&self) -> bool {
+        self.negated
+    }
+
     /// The quirks mode of the document.
     #[inline]
     pub fn quirks_mode(&self) -> QuirksMode {
@@ -246,7 +257,9 @@ where
     {
         let old_in_negation = self.in_negation;
         self.in_negation = true;
+        self.negated = !self.negated;
         let result = self.nest(f);
+        self.negated = !self.negated;
         self.in_negation = old_in_negation;
         result
     }
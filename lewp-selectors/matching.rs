@@ -816,7 +816,7 @@ where
                     })
                 })
         },
-        Component::Scope => match context.shared.scope_element {
+        Component::Scope | Component::Nesting => match context.shared.scope_element {
             Some(ref scope_element) => element.opaque() == *scope_element,
             None => element.is_root(),
         },
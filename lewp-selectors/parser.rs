@@ -132,6 +132,9 @@ bitflags! {
 
         /// Whether we explicitly disallow pseudo-element-like things.
         const DISALLOW_PSEUDOS = 1 << 6;
+        /// Whether the selector is relative to the selector of a parent rule,
+        /// see <https://drafts.csswg.org/css-nesting/#syntax>.
+        const RELATIVE = 1 << 7;
     }
 }
 
@@ -276,6 +279,11 @@ pub trait Parser<'i> {
         ParseErrorRecovery::IgnoreInvalidSelector
     }
 
+    /// Whether to parse the nesting selector `&`.
+    fn parse_nesting_selector(&self) -> bool {
+        false
+    }
+
     /// Whether the given function name is an alias for the `:is()` function.
     fn is_is_alias(&self, _name: &str) -> bool {
         false
@@ -391,6 +399,26 @@ impl<Impl: SelectorImpl> SelectorList<Impl> {
         )
     }
 
+    /// Parse a comma-separated list of Selectors that are nested in the rule of
+    /// another selector. Selectors that start with a combinator or do not
+    /// contain the nesting selector `&` are relative to the parent, so
+    /// `> .title` is parsed as `& > .title` and `.title` as `& .title`.
+    /// <https://drafts.csswg.org/css-nesting/#syntax>
+    pub fn parse_relative<'i, 't, P>(
+        parser: &P,
+        input: &mut CssParser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, P::Error>>
+    where
+        P: Parser<'i, Impl = Impl>,
+    {
+        Self::parse_with_state(
+            parser,
+            input,
+            SelectorParsingState::RELATIVE,
+            ParseErrorRecovery::DiscardList,
+        )
+    }
+
     #[inline]
     fn parse_with_state<'i, 't, P>(
         parser: &P,
@@ -795,6 +823,168 @@ impl<Impl: SelectorImpl> Selector<Impl> {
         Selector(builder.build_with_specificity_and_flags(spec))
     }
 
+    /// Creates the selector `&` that only consists of the nesting selector.
+    pub fn nesting() -> Self {
+        let mut builder = SelectorBuilder::default();
+        builder.push_simple_selector(Component::Nesting);
+        Selector(builder.build(false, false, false))
+    }
+
+    /// Whether this selector contains the nesting selector `&`, including
+    /// the selectors of pseudo-classes like `:is()` or `:not()`.
+    pub fn has_nesting(&self) -> bool {
+        self.iter_raw_match_order()
+            .any(|component| match *component {
+                Component::Nesting => true,
+                Component::Negation(ref list)
+                | Component::Is(ref list)
+                | Component::Where(ref list) => {
+                    list.iter().any(Self::has_nesting)
+                }
+                Component::Slotted(ref selector)
+                | Component::Host(Some(ref selector)) => selector.has_nesting(),
+                _ => false,
+            })
+    }
+
+    /// Creates a new selector where every nesting selector `&` is replaced by
+    /// the given parent selectors, see
+    /// <https://drafts.csswg.org/css-nesting/#nest-selector>.
+    ///
+    /// A single parent selector without pseudo-elements is merged into the
+    /// compound selector containing `&`, so `& > .title` nested in `.card`
+    /// becomes `.card > .title`. A parent with combinators is only merged into
+    /// the first compound selector, `div &` nested in `.card > p` becomes
+    /// `div :is(.card > p)`. In all other cases `&` is replaced by `:is()`
+    /// containing the parent selectors. Without any parent selector, `&` is
+    /// replaced by `:scope`.
+    ///
+    /// `&` can not represent pseudo-elements, so parent selectors containing
+    /// one are left out. Returns `None` if this selector contains `&` and all
+    /// parent selectors contain a pseudo-element, the selector can not match
+    /// anything then.
+    pub fn replace_nesting(&self, parent: &[Selector<Impl>]) -> Option<Self> {
+        if parent.is_empty() || !self.has_nesting() {
+            return Some(self.replace_nesting_with(parent));
+        }
+        let represented = parent
+            .iter()
+            .filter(|s| !s.has_pseudo_element())
+            .cloned()
+            .collect::<Vec<_>>();
+        match represented.is_empty() {
+            true => None,
+            false => Some(self.replace_nesting_with(&represented)),
+        }
+    }
+
+    /// Replaces `&` by the given parent selectors, none of them contains a
+    /// pseudo-element.
+    fn replace_nesting_with(&self, parent: &[Selector<Impl>]) -> Self {
+        let merge = match parent {
+            [single]
+                if !single.has_pseudo_element()
+                    && !single.is_slotted()
+                    && !single.is_part() =>
+            {
+                Some(single)
+            }
+            _ => None,
+        };
+        let replace_all = |list: &[Selector<Impl>]| {
+            list.iter()
+                .map(|s| s.replace_nesting_with(parent))
+                .collect::<Vec<_>>()
+                .into_boxed_slice()
+        };
+        let is_compound = |s: &Selector<Impl>| {
+            !s.iter_raw_match_order().any(Component::is_combinator)
+        };
+        let mut is_first = true;
+        let mut builder = SelectorBuilder::default();
+        self.for_each_compound(&mut builder, |builder, compound| {
+            let has_type = compound.iter().any(|c| {
+                matches!(
+                    *c,
+                    Component::LocalName(..)
+                        | Component::ExplicitUniversalType
+                        | Component::ExplicitAnyNamespace
+                        | Component::ExplicitNoNamespace
+                        | Component::DefaultNamespace(..)
+                        | Component::Namespace(..)
+                )
+            });
+            let has_nesting =
+                compound.iter().any(|c| matches!(*c, Component::Nesting));
+            let merged = match merge {
+                Some(single)
+                    if !has_type
+                        && has_nesting
+                        && (is_first || is_compound(single)) =>
+                {
+                    single.for_each_compound(builder, |builder, c| {
+                        for component in c {
+                            builder.push_simple_selector(component.clone());
+                        }
+                    });
+                    true
+                }
+                _ => false,
+            };
+            for component in compound {
+                let component = match *component {
+                    Component::Nesting if merged => continue,
+                    Component::Nesting if parent.is_empty() => Component::Scope,
+                    Component::Nesting => {
+                        Component::Is(parent.to_vec().into_boxed_slice())
+                    }
+                    Component::Negation(ref list) => {
+                        Component::Negation(replace_all(list))
+                    }
+                    Component::Is(ref list) => Component::Is(replace_all(list)),
+                    Component::Where(ref list) => {
+                        Component::Where(replace_all(list))
+                    }
+                    Component::Slotted(ref selector) => Component::Slotted(
+                        selector.replace_nesting_with(parent),
+                    ),
+                    Component::Host(Some(ref selector)) => Component::Host(
+                        Some(selector.replace_nesting_with(parent)),
+                    ),
+                    ref other => other.clone(),
+                };
+                builder.push_simple_selector(component);
+            }
+            is_first = false;
+        });
+        Selector(builder.build(
+            self.has_pseudo_element(),
+            self.is_slotted(),
+            self.is_part(),
+        ))
+    }
+
+    /// Calls `f` for every compound selector in parse order and pushes the
+    /// combinators in between to the builder.
+    fn for_each_compound<F>(
+        &self,
+        builder: &mut SelectorBuilder<Impl>,
+        mut f: F,
+    ) where
+        F: FnMut(&mut SelectorBuilder<Impl>, &[Component<Impl>]),
+    {
+        let mut combinators = self
+            .iter_raw_match_order()
+            .rev()
+            .filter_map(Component::as_combinator);
+        for compound in self.0.slice.rsplit(Component::is_combinator) {
+            f(builder, compound);
+            if let Some(combinator) = combinators.next() {
+                builder.push_combinator(combinator);
+            }
+        }
+    }
+
     /// Returns count of simple selectors and combinators in the Selector.
     #[inline]
     pub fn len(&self) -> usize {
@@ -1078,6 +1268,11 @@ pub enum Component<Impl: SelectorImpl> {
     Root,
     Empty,
     Scope,
+    /// The nesting selector `&`, representing the elements matched by the
+    /// parent rule.
+    ///
+    /// <https://drafts.csswg.org/css-nesting/#nest-selector>
+    Nesting,
     NthChild(i32, i32),
     NthLastChild(i32, i32),
     NthOfType(i32, i32),
@@ -1575,6 +1770,7 @@ impl<Impl: SelectorImpl> ToCss for Component<Impl> {
             Root => dest.write_str(":root"),
             Empty => dest.write_str(":empty"),
             Scope => dest.write_str(":scope"),
+            Nesting => dest.write_char('&'),
             Host(ref selector) => {
                 dest.write_str(":host")?;
                 if let Some(ref selector) = *selector {
@@ -1680,6 +1876,21 @@ where
 {
     let mut builder = SelectorBuilder::default();
 
+    if state.intersects(SelectorParsingState::RELATIVE) {
+        state.remove(SelectorParsingState::RELATIVE);
+        let start = input.state();
+        let combinator = parse_leading_combinator(input);
+        if combinator.is_none() {
+            let selector = parse_selector(parser, input, state)?;
+            if selector.has_nesting() {
+                return Ok(selector);
+            }
+            input.reset(&start);
+        }
+        builder.push_simple_selector(Component::Nesting);
+        builder.push_combinator(combinator.unwrap_or(Combinator::Descendant));
+    }
+
     let mut has_pseudo_element = false;
     let mut slotted = false;
     let mut part = false;
@@ -1748,6 +1959,21 @@ where
     Ok(Selector(builder.build(has_pseudo_element, slotted, part)))
 }
 
+/// Consumes the combinator a relative selector starts with, if any.
+fn parse_leading_combinator(input: &mut CssParser) -> Option<Combinator> {
+    input.skip_whitespace();
+    let start = input.state();
+    match input.next() {
+        Ok(&Token::Delim('>')) => Some(Combinator::Child),
+        Ok(&Token::Delim('+')) => Some(Combinator::NextSibling),
+        Ok(&Token::Delim('~')) => Some(Combinator::LaterSibling),
+        _ => {
+            input.reset(&start);
+            None
+        }
+    }
+}
+
 impl<Impl: SelectorImpl> Selector<Impl> {
     /// Parse a selector, without any pseudo-element.
     #[inline]
@@ -2474,6 +2700,13 @@ where
             let class = Component::Class(class.as_ref().into());
             SimpleSelectorParseResult::SimpleSelector(class)
         }
+        Token::Delim('&') if parser.parse_nesting_selector() => {
+            if state.intersects(SelectorParsingState::AFTER_PSEUDO) {
+                return Err(input
+                    .new_custom_error(SelectorParseErrorKind::InvalidState));
+            }
+            SimpleSelectorParseResult::SimpleSelector(Component::Nesting)
+        }
         Token::SquareBracketBlock => {
             if state.intersects(SelectorParsingState::AFTER_PSEUDO) {
                 return Err(input
@@ -2804,6 +3037,10 @@ pub mod tests {
             true
         }
 
+        fn parse_nesting_selector(&self) -> bool {
+            true
+        }
+
         fn parse_non_ts_pseudo_class(
             &self,
             location: SourceLocation,
@@ -3419,6 +3656,85 @@ pub mod tests {
         assert!(parse("foo:where(::before)").is_err());
     }
 
+    #[test]
+    fn test_nesting() {
+        let list = |input, relative| {
+            let mut parser_input = ParserInput::new(input);
+            let input = &mut CssParser::new(&mut parser_input);
+            let parser = &DummyParser::default();
+            match relative {
+                true => SelectorList::parse_relative(parser, input),
+                false => SelectorList::parse(parser, input),
+            }
+            .unwrap()
+        };
+        let relative = |input| list(input, true);
+        let parent = list(".card > p, #main", false);
+        let nested =
+            relative("&.active, > .title, .icon, :not(&) + b, div &, a&");
+        assert_eq!(
+            nested.to_css_string(),
+            "&.active, & > .title, & .icon, :not(&) + b, div &, a&"
+        );
+        assert!(nested.0.iter().all(Selector::has_nesting));
+        let flattened = nested
+            .0
+            .iter()
+            .map(|s| s.replace_nesting(&parent.0[..1]).unwrap().to_css_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            flattened,
+            [
+                ".card > p.active",
+                ".card > p > .title",
+                ".card > p .icon",
+                ":not(.card > p) + b",
+                "div :is(.card > p)",
+                "a:is(.card > p)"
+            ]
+        );
+        assert_eq!(
+            nested.0[4]
+                .replace_nesting(&parent.0[1..])
+                .unwrap()
+                .to_css_string(),
+            "div #main"
+        );
+        assert_eq!(
+            nested.0[1]
+                .replace_nesting(&parent.0)
+                .unwrap()
+                .to_css_string(),
+            ":is(.card > p, #main) > .title"
+        );
+        assert_eq!(
+            nested.0[0].replace_nesting(&[]).unwrap().to_css_string(),
+            ":scope.active"
+        );
+
+        // & can not represent pseudo-elements
+        let pseudo = list(".a::before", false);
+        assert!(nested.0[0].replace_nesting(&pseudo.0).is_none());
+        assert!(nested.0[3].replace_nesting(&pseudo.0).is_none());
+        let mixed = list(".a::before, .b", false);
+        assert_eq!(
+            nested.0[0]
+                .replace_nesting(&mixed.0)
+                .unwrap()
+                .to_css_string(),
+            ".b.active"
+        );
+        let unnested = list("b", false);
+        assert_eq!(
+            unnested.0[0]
+                .replace_nesting(&pseudo.0)
+                .unwrap()
+                .to_css_string(),
+            "b"
+        );
+        assert!(parse("&.active").is_ok());
+    }
+
     #[test]
     fn test_pseudo_iter() {
         let selector = &parse("q::before").unwrap().0[0];
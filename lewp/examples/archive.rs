@@ -35,14 +35,15 @@ impl ComponentModel for HelloWorld {
         "hello-world".into()
     }
 
-    // There is no reason your page should fail. It should always render
-    // at least something. Errors during processing should already be
-    // handled before you call your page to be rendered.
-    fn main(&mut self) {}
+    // If your component fails, return an error. The component is then
+    // replaced by its fallback view, the rest of the page is rendered as usual.
+    fn main(&mut self) -> anyhow::Result<()> {
+        Ok(())
+    }
 
     // This is the view of your component.
-    fn view(&self) -> Option<Node> {
-        Some(h1(vec![text(&self.data)]))
+    fn view(&self) -> anyhow::Result<Option<Node>> {
+        Ok(Some(h1(vec![text(&self.data)])))
     }
 }
 
@@ -59,12 +60,13 @@ impl PageModel for HelloWorldPage {
 
     // The main method of the page. In here you can add your components to the
     // page and do whatever processing is required for your page to be rendered.
-    fn main(&self, view: &mut PageView) {
+    fn main(&self, view: &mut PageView) -> anyhow::Result<()> {
         let mut comp = Component::from(HelloWorld::new());
         // the component is only borrowed, to enable the possibility of adding
         // it twice to your page. You can use the state of your component to
         // define the behavior when adding it multiple times.
         view.push(&mut comp);
+        Ok(())
     }
 }
 
@@ -72,7 +74,7 @@ impl PageModel for HelloWorldPage {
 lewp_archive!(TestArchive, "testfiles");
 impl WebInterface for TestArchive {}
 
-fn main() {
+fn main() -> Result<(), lewp::Error> {
     simple_logger::init().unwrap();
     let archive_cache = ArchiveCache::default()
         .load_css::<TestArchive>()
@@ -80,5 +82,6 @@ fn main() {
         .seal();
     let hello_world = HelloWorldPage {};
     let page = Page::from(hello_world).with_archive_cache(archive_cache);
-    println!("{}", page.main().render());
+    println!("{}", page.main()?.render()?);
+    Ok(())
 }
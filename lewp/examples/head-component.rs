@@ -27,14 +27,15 @@ impl ComponentModel for HeadComponent {
         "head-component".into()
     }
 
-    // There is no reason your page should fail. It should always render
-    // at least something. Errors during processing should already be
-    // handled before you call your page to be rendered.
-    fn main(&mut self) {}
+    // If your component fails, return an error. The component is then
+    // replaced by its fallback view, the rest of the page is rendered as usual.
+    fn main(&mut self) -> anyhow::Result<()> {
+        Ok(())
+    }
 
     // This is the view of your component.
-    fn view(&self) -> Option<Node> {
-        None
+    fn view(&self) -> anyhow::Result<Option<Node>> {
+        Ok(None)
     }
 
     fn head(&self) -> NodeList {
@@ -58,7 +59,7 @@ impl PageModel for HeadOnlyPage {
 
     // The main method of the page. In here you can add your components to the
     // page and do whatever processing is required for your page to be rendered.
-    fn main(&self, view: &mut PageView) {
+    fn main(&self, view: &mut PageView) -> anyhow::Result<()> {
         let mut comp = Component::from(HeadComponent::new());
         // the component is only borrowed, to enable the possibility of adding
         // it twice to your page. You can use the state of your component to
@@ -66,12 +67,14 @@ impl PageModel for HeadOnlyPage {
         view.push(&mut comp);
         // head nodes are only added !ONCE! for every component intentionally
         view.push(&mut comp);
+        Ok(())
     }
 }
 
-fn main() {
+fn main() -> Result<(), lewp::Error> {
     simple_logger::init().unwrap();
     let hello_world = HeadOnlyPage {};
     let page = Page::from(hello_world);
-    println!("{}", page.main().render());
+    println!("{}", page.main()?.render()?);
+    Ok(())
 }
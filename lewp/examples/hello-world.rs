@@ -35,13 +35,15 @@ impl ComponentModel for HelloWorld {
         "hello-world".into()
     }
 
-    // There is no reason for your page to fail. Errors during processing should
-    // result in a different view that you define below.
-    fn main(&mut self) {}
+    // If your component fails, return an error. The component is then
+    // replaced by its fallback view, the rest of the page is rendered as usual.
+    fn main(&mut self) -> anyhow::Result<()> {
+        Ok(())
+    }
 
     // This is the view of your component.
-    fn view(&self) -> Option<Node> {
-        Some(h1(vec![text(&self.data)]))
+    fn view(&self) -> anyhow::Result<Option<Node>> {
+        Ok(Some(h1(vec![text(&self.data)])))
     }
 }
 
@@ -58,7 +60,7 @@ impl PageModel for HelloWorldPage {
 
     // The main method of the page. In here you can add your components to the
     // page and do whatever processing is required for your page to be rendered.
-    fn main(&self, view: &mut PageView) {
+    fn main(&self, view: &mut PageView) -> anyhow::Result<()> {
         let mut comp = Component::from(HelloWorld::new());
         // The component is only borrowed, to enable the possibility of adding
         // it twice to your page. You can use the state of your component to
@@ -67,10 +69,11 @@ impl PageModel for HelloWorldPage {
         // only once, so you can be sure that there is no overhead when adding
         // the component multiple times.
         view.push(&mut comp);
+        Ok(())
     }
 }
 
-fn main() {
+fn main() -> Result<(), lewp::Error> {
     simple_logger::init().unwrap();
 
     // Create an instance of your page
@@ -80,7 +83,8 @@ fn main() {
     // Because the internal state of the page changes when running the main
     // method, you need to get the result in order to be able to render the
     // resulting page.
-    let executed_page = page.main();
+    let executed_page = page.main()?;
 
-    println!("{}", executed_page.render());
+    println!("{}", executed_page.render()?);
+    Ok(())
 }
@@ -34,22 +34,24 @@ impl ComponentModel for Parent {
         "parent".into()
     }
 
-    // There is no reason your page should fail. It should always render
-    // at least something. Errors during processing should already be
-    // handled before you call your page to be rendered.
-    fn main(&mut self) {
-        // you can decide when to execute the nested component
-        self.nested.main();
+    // If your component fails, return an error. The component is then
+    // replaced by its fallback view, the rest of the page is rendered as usual.
+    fn main(&mut self) -> anyhow::Result<()> {
+        // you can decide when to execute the nested component, its errors
+        // are handled by the fallback view of this component
+        self.nested.main()
     }
 
     // This is the view of your component.
-    fn view(&self) -> Option<Node> {
+    fn view(&self) -> anyhow::Result<Option<Node>> {
         // Make sure that you use `nested_view()` instead of the standard `view()`
         // method. If not, your JavaScript on the client side will not be working.
-        let nested_view = self.nested.nested_view().unwrap();
+        let nested_view = self.nested.nested_view()?;
 
         // You have full control where you insert the nested component's view.
-        Some(div(vec![h1(vec![text(&self.data)]), nested_view]))
+        let mut children = vec![h1(vec![text(&self.data)])];
+        children.extend(nested_view);
+        Ok(Some(div(children)))
     }
 
     fn dependency_list(&self) -> DependencyList {
@@ -73,16 +75,17 @@ impl ComponentModel for NestedComponent {
         "nested-component-id".into()
     }
 
-    // There is no reason your page should fail. It should always render
-    // at least something. Errors during processing should already be
-    // handled before you call your page to be rendered.
-    fn main(&mut self) {}
+    // If your component fails, return an error. The component is then
+    // replaced by its fallback view, the rest of the page is rendered as usual.
+    fn main(&mut self) -> anyhow::Result<()> {
+        Ok(())
+    }
 
     // This is the view of your component.
-    fn view(&self) -> Option<Node> {
-        Some(h2(vec![text(
+    fn view(&self) -> anyhow::Result<Option<Node>> {
+        Ok(Some(h2(vec![text(
             "This text is rendered by a nested component! :-)",
-        )]))
+        )])))
     }
 }
 
@@ -99,18 +102,20 @@ impl PageModel for HelloWorldPage {
 
     // The main method of the page. In here you can add your components to the
     // page and do whatever processing is required for your page to be rendered.
-    fn main(&self, view: &mut PageView) {
+    fn main(&self, view: &mut PageView) -> anyhow::Result<()> {
         let mut comp = Component::from(Parent::new());
         // the component is only borrowed, to enable the possibility of adding
         // it twice to your page. You can use the state of your component to
         // define the behavior when adding it multiple times.
         view.push(&mut comp);
+        Ok(())
     }
 }
 
-fn main() {
+fn main() -> Result<(), lewp::Error> {
     simple_logger::init().unwrap();
     let hello_world = HelloWorldPage {};
     let page = Page::from(hello_world);
-    println!("{}", page.main().render());
+    println!("{}", page.main()?.render()?);
+    Ok(())
 }
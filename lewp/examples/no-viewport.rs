@@ -15,16 +15,19 @@ impl PageModel for NoViewportPage {
     }
 
     // The view does not change.
-    fn main(&self, _view: &mut PageView) {}
+    fn main(&self, _view: &mut PageView) -> anyhow::Result<()> {
+        Ok(())
+    }
 
     fn viewport(&self) -> Option<Node> {
         None
     }
 }
 
-fn main() {
+fn main() -> Result<(), lewp::Error> {
     simple_logger::init().unwrap();
     let no_viewport = NoViewportPage {};
     let page = Page::from(no_viewport);
-    println!("{}", page.main().render());
+    println!("{}", page.main()?.render()?);
+    Ok(())
 }
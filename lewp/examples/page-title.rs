@@ -14,7 +14,9 @@ impl PageModel for DescriptivePage {
     }
 
     // The view does not change.
-    fn main(&self, _view: &mut PageView) {}
+    fn main(&self, _view: &mut PageView) -> anyhow::Result<()> {
+        Ok(())
+    }
 
     fn title(&self) -> String {
         "A new, custom titled web page.".into()
@@ -25,9 +27,10 @@ impl PageModel for DescriptivePage {
     }
 }
 
-fn main() {
+fn main() -> Result<(), lewp::Error> {
     simple_logger::init().unwrap();
     let descriptive_page = DescriptivePage {};
     let page = Page::from(descriptive_page);
-    println!("{}", page.main().render());
+    println!("{}", page.main()?.render()?);
+    Ok(())
 }
@@ -0,0 +1,88 @@
+use lewp::{
+    archive::ArchiveCache,
+    component::{Component, ComponentId, ComponentModel},
+    export::StaticSite,
+    html::{
+        api::{h1, text},
+        Node,
+    },
+    lewp_archive,
+    page::{PageId, PageModel},
+    resources::{ImageFormat, WebInterface},
+    view::PageView,
+};
+
+// A component that shows the given headline.
+struct Headline(String);
+
+impl ComponentModel for Headline {
+    type Message = ();
+
+    fn id(&self) -> ComponentId {
+        "hello-world".into()
+    }
+
+    fn view(&self) -> anyhow::Result<Option<Node>> {
+        Ok(Some(h1(vec![text(&self.0)])))
+    }
+}
+
+// A page that only contains a headline.
+struct HeadlinePage {
+    id: PageId,
+    headline: String,
+}
+
+impl PageModel for HeadlinePage {
+    fn id(&self) -> PageId {
+        self.id.clone()
+    }
+
+    fn main(&self, view: &mut PageView) -> anyhow::Result<()> {
+        let mut headline = Component::from(Headline(self.headline.clone()));
+        view.push(&mut headline);
+        Ok(())
+    }
+}
+
+// The pages and resources are taken from this archive.
+lewp_archive!(TestArchive, "testfiles");
+impl WebInterface for TestArchive {}
+
+// Exports the site to the directory given as first argument, for example:
+// cargo run --example static-site -- target/static-site
+fn main() -> anyhow::Result<()> {
+    simple_logger::init_with_level(log::Level::Info).unwrap();
+    let output = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| String::from("target/static-site"));
+
+    let archive_cache = ArchiveCache::default()
+        .load_css::<TestArchive>()?
+        .load_javascript::<TestArchive>()?
+        .load_images::<TestArchive>(&[256], &[ImageFormat::WebP])?
+        .seal();
+
+    let written = StaticSite::new(archive_cache)
+        .with_page(
+            "/",
+            HeadlinePage {
+                id: "home".into(),
+                headline: "Hello World!".into(),
+            },
+        )
+        .with_page(
+            "/sitemap",
+            HeadlinePage {
+                id: "sitemap".into(),
+                headline: "Sitemap".into(),
+            },
+        )
+        .export(&output)?;
+
+    for file in &written {
+        log::info!("Written {}", file.display());
+    }
+    log::info!("Exported {} files to {output}", written.len());
+    Ok(())
+}
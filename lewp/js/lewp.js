@@ -1,7 +1,50 @@
 class Lewp {
 	constructor() {}
 
+	loadStylesheets() {
+		let stylesheets = document.querySelectorAll('link[data-lewp-type="css"][rel="preload"]');
+		stylesheets.forEach((stylesheet) => {
+			// The preload may have finished before this script runs, in which
+			// case the load event has already been dispatched.
+			if (this.isLoaded(stylesheet)) {
+				stylesheet.rel = 'stylesheet';
+				return;
+			}
+			stylesheet.addEventListener('load', () => {
+				stylesheet.rel = 'stylesheet';
+			}, { once: true });
+		});
+	}
+
+	isLoaded(link) {
+		if (link.sheet !== null) {
+			return true;
+		}
+		if (typeof performance === 'undefined' || !performance.getEntriesByName) {
+			return false;
+		}
+		return performance.getEntriesByName(link.href, 'resource')
+			.some((entry) => entry.responseEnd > 0);
+	}
+
+	clientStates() {
+		let state_script = document.querySelector('script[type="application/json"][data-lewp-type="state"]');
+		if (state_script === null) {
+			return {};
+		}
+		try {
+			return JSON.parse(state_script.textContent);
+		} catch (e) {
+			console.error({
+				'message': 'Could not parse the client state of the components',
+				'error': e,
+			})
+			return {};
+		}
+	}
+
 	init() {
+		let client_states = this.clientStates();
 		let all_module_scripts = document.querySelectorAll('script[data-lewp-type="component"]');
 		all_module_scripts.forEach((module_script) => {
 			let module_id = module_script.dataset.lewpId;
@@ -13,7 +56,7 @@ class Lewp {
 				return;
 			}
 			let module_dom_nodes = document.querySelectorAll(
-				'body [data-lewp-type="component"][class="' + module_id + '"]'
+				'body [data-lewp-type="component"][data-lewp-id="' + CSS.escape(module_id) + '"]'
 			);
 			let module_url = new URL(module_script.src);
 			import(module_url.pathname).then((module) => {
@@ -25,7 +68,7 @@ class Lewp {
 					return;
 				}
 				module_dom_nodes.forEach((dom) => {
-					module.init(dom);
+					module.init(dom, client_states[dom.dataset.lewpInstance]);
 				})
 			})
 		});
@@ -33,6 +76,7 @@ class Lewp {
 }
 
 const lewp = new Lewp();
+lewp.loadStylesheets();
 
 document.addEventListener('DOMContentLoaded', () => {
 	lewp.init();
@@ -4,7 +4,13 @@
 use {
     crate::{
         component::{ComponentDetails, ComponentId},
-        resources::{ResourceId, ResourceLevel, ResourceType, WebInterface},
+        resources::{
+            strip_content_hash,
+            ResourceId,
+            ResourceLevel,
+            ResourceType,
+            WebInterface,
+        },
     },
     anyhow::Context,
     rust_embed::RustEmbed,
@@ -16,9 +22,19 @@ use {
 
 mod cache;
 mod component;
+#[doc(hidden)]
+pub mod filesystem;
+mod reloadable;
 mod root;
-
-pub use {cache::ArchiveCache, component::ArchiveComponent, root::ArchiveRoot};
+mod server;
+
+pub use {
+    cache::ArchiveCache,
+    component::ArchiveComponent,
+    reloadable::{ArchiveWatcher, ReloadableArchiveCache},
+    root::ArchiveRoot,
+    server::{ResourceResponse, ResourceServer, ServedResource},
+};
 
 /// Defines an archive at the given filesystem location. Uses [rust-embed](rust_embed)
 /// under the hood to compile the archive files into the release binary.
@@ -49,6 +65,54 @@ macro_rules! lewp_archive {
     };
 }
 
+/// Defines an archive at the given filesystem location that is read from disk
+/// at runtime instead of being compiled into the binary.
+///
+/// Use this during development together with a [ReloadableArchiveCache], so
+/// changes to your resources show up without recompiling. Relative folders are
+/// resolved against the directory of your `Cargo.toml`.
+///
+/// For example:
+/// ```rust
+/// # use lewp::lewp_filesystem_archive;
+/// #[cfg(debug_assertions)]
+/// lewp_filesystem_archive!(AssetsArchive, "testfiles");
+/// #[cfg(not(debug_assertions))]
+/// lewp::lewp_archive!(AssetsArchive, "testfiles");
+/// ```
+#[macro_export]
+macro_rules! lewp_filesystem_archive {
+    ($name: ident, $folder: literal) => {
+        /// User defined archive that is read from the given folder at runtime.
+        pub struct $name;
+        impl $crate::archive::ArchiveRoot for $name {
+            fn root() -> ::std::path::PathBuf {
+                $crate::archive::filesystem::resolve_root(
+                    env!("CARGO_MANIFEST_DIR"),
+                    $folder,
+                )
+            }
+        }
+        impl $crate::rust_embed::RustEmbed for $name {
+            fn get(
+                file_path: &str,
+            ) -> ::std::option::Option<$crate::rust_embed::EmbeddedFile> {
+                $crate::archive::filesystem::get(
+                    &<Self as $crate::archive::ArchiveRoot>::root(),
+                    file_path,
+                )
+            }
+            fn iter() -> impl ::std::iter::Iterator<
+                Item = ::std::borrow::Cow<'static, str>,
+            > + 'static {
+                $crate::archive::filesystem::iter(
+                    &<Self as $crate::archive::ArchiveRoot>::root(),
+                )
+            }
+        }
+    };
+}
+
 /// Definition of the archive on the file system.
 pub trait Archive: ArchiveRoot + WebInterface
 where
@@ -74,8 +138,20 @@ where
     /// root      |         |           resource_type        |
     /// (unused)  level     component_id                 resource_id
     /// ```
+    ///
+    /// URLs containing a content hash, as created by
+    /// [Resource::url](crate::resources::Resource::url), are accepted as well.
+    /// The hash is ignored, use [parse_hashed](Self::parse_hashed) to get it.
+    /// ```text
+    /// resources/components/hello-world/js.3f2a1c09.js
+    /// resources/components/hello-world/css/non-render-critical.3f2a1c09.css
+    /// ```
     /// ## Examples
     fn parse(value: PathBuf) -> anyhow::Result<ComponentDetails> {
+        let value = match Self::split_content_hash(&value) {
+            Some((v, _)) => v,
+            None => value,
+        };
         // create default values
         let mut resource_id = None;
 
@@ -125,6 +201,43 @@ where
             resource_id,
         })
     }
+
+    /// Like [parse](Self::parse), but also returns the content hash if the
+    /// given path contains one.
+    fn parse_hashed(
+        value: PathBuf,
+    ) -> anyhow::Result<(ComponentDetails, Option<String>)> {
+        let hash = Self::split_content_hash(&value).map(|(_, h)| h);
+        Ok((Self::parse(value)?, hash))
+    }
+
+    /// Removes the content hash from the file name of the given path. Returns
+    /// the path without the hash and the hash itself, or `None` if the path
+    /// does not contain a hash.
+    ///
+    /// Only the `CSS` and `JavaScript` URLs created by
+    /// [Resource::url](crate::resources::Resource::url) contain a hash, so
+    /// other files like `images/photo.cafebabe.png` are left untouched.
+    fn split_content_hash(value: &Path) -> Option<(PathBuf, String)> {
+        let file_name = value.file_name()?.to_str()?;
+        let (file_name, hash) = strip_content_hash(file_name)?;
+        let value = value.with_file_name(file_name);
+        // component wide resources are named after their resource type
+        let resource_type = match value.extension() {
+            Some(_) => value.parent()?.file_name()?,
+            None => value.file_name()?,
+        };
+        let resource_type = ResourceType::try_from(resource_type).ok()?;
+        let is_hashed = match resource_type {
+            ResourceType::Css | ResourceType::JavaScript => {
+                value.extension().is_none_or(|e| {
+                    e.to_str() == resource_type.extension().as_deref()
+                })
+            }
+            _ => false,
+        };
+        is_hashed.then_some((value, hash))
+    }
 }
 
 impl<A: RustEmbed + ArchiveRoot + WebInterface> Archive for A {
@@ -317,4 +430,82 @@ mod tests {
         let js_queried = cache.query::<Js>(&js_component_details).unwrap();
         assert_eq!(*js_queried.some_combined_script, JS_SCRIPT_COMBINED);
     }
+
+    #[test]
+    fn parse_hashed_urls() {
+        let (details, hash) = ResourceArchive::parse_hashed(PathBuf::from(
+            "components/hello-world/js.3f2a1c09.js",
+        ))
+        .unwrap();
+        assert_eq!(details.component_id, "hello-world");
+        assert_eq!(details.resource_type, ResourceType::JavaScript);
+        assert_eq!(details.resource_id, None);
+        assert_eq!(hash.as_deref(), Some("3f2a1c09"));
+
+        let details = ResourceArchive::parse(PathBuf::from(
+            "components/hello-world/css/non-render-critical.3f2a1c09.css",
+        ))
+        .unwrap();
+        assert_eq!(details.resource_type, ResourceType::Css);
+        assert_eq!(
+            details.resource_id,
+            Some(PathBuf::from("non-render-critical.css"))
+        );
+
+        let (details, hash) = ResourceArchive::parse_hashed(PathBuf::from(
+            "components/hello-world/images/logo.png",
+        ))
+        .unwrap();
+        assert_eq!(details.resource_id, Some(PathBuf::from("logo.png")));
+        assert_eq!(hash, None);
+
+        let (details, hash) = ResourceArchive::parse_hashed(PathBuf::from(
+            "components/hello-world/images/photo.cafebabe.png",
+        ))
+        .unwrap();
+        assert_eq!(
+            details.resource_id,
+            Some(PathBuf::from("photo.cafebabe.png"))
+        );
+        assert_eq!(hash, None);
+
+        let (details, hash) = ResourceArchive::parse_hashed(PathBuf::from(
+            "components/hello-world/text/notes.cafebabe.txt",
+        ))
+        .unwrap();
+        assert_eq!(
+            details.resource_id,
+            Some(PathBuf::from("notes.cafebabe.txt"))
+        );
+        assert_eq!(hash, None);
+    }
+
+    #[test]
+    fn load_text() {
+        use crate::resources::{Messages, Text};
+        let cache = ArchiveCache::default()
+            .load_text::<ResourceArchive>()
+            .unwrap();
+        let details = ComponentDetails::new(
+            "hello-world".into(),
+            ResourceType::Text,
+            ResourceLevel::Component,
+        );
+        let imprint = cache
+            .get::<Text>(
+                &details
+                    .clone()
+                    .with_resource_id(PathBuf::from("imprint.txt")),
+            )
+            .unwrap();
+        assert_eq!(imprint.content.trim(), "Hello World Ltd.");
+        let messages = cache.get::<Messages>(&details).unwrap();
+        assert_eq!(
+            messages.get("de-AT", "greeting"),
+            Some("Servus { $name }!")
+        );
+        assert!(cache
+            .get::<Text>(&details.with_resource_id(PathBuf::from("en.ftl")))
+            .is_none());
+    }
 }
@@ -3,25 +3,52 @@ use {
     crate::{
         component::ComponentDetails,
         resources::{
+            localization::MESSAGES_EXTENSION,
+            BrowserTargets,
             Css,
             CssOptions,
+            Entireness,
+            Image,
+            ImageFormat,
+            ImageOptions,
             Js,
             JsOptions,
+            Messages,
+            MessagesOptions,
             Resource,
             ResourceLevel,
             ResourceType,
+            Text,
+            TextOptions,
         },
     },
-    std::{any::Any, collections::HashMap, sync::Arc},
+    rust_embed::RustEmbed,
+    std::{
+        any::Any,
+        collections::{HashMap, HashSet},
+        path::PathBuf,
+        sync::Arc,
+    },
 };
 
 /// Can hold multiple components identified by [ComponentDetails] in memory.
-#[derive(Default)]
+///
+/// Cloning is cheap, the components are shared between the clones.
+#[derive(Default, Clone)]
 pub struct ArchiveCache {
     cache: HashMap<ComponentDetails, Arc<dyn Any + Send + Sync>>,
+    browser_targets: Option<BrowserTargets>,
 }
 
 impl ArchiveCache {
+    /// Sets the browsers that the [Css] components are prefixed for while
+    /// loading, see [CssOptions::browser_targets]. Has to be called before
+    /// [load_css](Self::load_css).
+    pub fn with_browser_targets(mut self, targets: BrowserTargets) -> Self {
+        self.browser_targets = Some(targets);
+        self
+    }
+
     /// Inserts the given component into the cache.
     pub fn insert<C: ArchiveComponent + Send + Sync + 'static>(
         &mut self,
@@ -52,6 +79,143 @@ impl ArchiveCache {
         Some(Arc::new(c))
     }
 
+    /// Returns a shared reference to the component with the given details, so
+    /// it can be kept beyond the lifetime of the cache reference.
+    pub fn get<C: ArchiveComponent + Send + Sync + 'static>(
+        &self,
+        details: &ComponentDetails,
+    ) -> Option<Arc<Resource<C>>> {
+        let c = Arc::clone(self.cache.get(details)?);
+        c.downcast::<Resource<C>>().ok()
+    }
+
+    /// Returns the URL and content of every file in the cache that is
+    /// available on the webserver, for example the [Js] of the components or
+    /// the variants of an [Image]. The URLs contain the
+    /// [web_root](crate::resources::WebInterface::web_root) and the content
+    /// hash, matching the references in the rendered pages.
+    pub fn web_files(&self) -> Vec<(PathBuf, Vec<u8>)> {
+        let mut files = vec![];
+        for (details, component) in &self.cache {
+            let component = Arc::clone(component);
+            match details.resource_type {
+                ResourceType::Css => {
+                    let Ok(css) = component.downcast::<Resource<Css>>() else {
+                        continue;
+                    };
+                    for e in [
+                        Entireness::Full,
+                        Entireness::RenderCritical,
+                        Entireness::NonRenderCritical,
+                    ] {
+                        files.push((
+                            css.url_of(e.resource_id()),
+                            css.content.get(&e).as_bytes().to_vec(),
+                        ));
+                    }
+                }
+                ResourceType::JavaScript => {
+                    if let Ok(js) = component.downcast::<Resource<Js>>() {
+                        files.push((js.url(), js.content.as_bytes().to_vec()));
+                    }
+                }
+                ResourceType::Image => {
+                    let Ok(image) = component.downcast::<Resource<Image>>()
+                    else {
+                        continue;
+                    };
+                    // variants share the instance of the original image
+                    let variant = details
+                        .resource_id
+                        .as_ref()
+                        .and_then(|r| image.variant(r));
+                    match variant {
+                        Some(v) => files.push((
+                            image.url().with_file_name(&v.resource_id),
+                            v.content.clone(),
+                        )),
+                        None => {
+                            files.push((image.url(), image.content.clone()))
+                        }
+                    }
+                }
+                ResourceType::Text => {
+                    if let Ok(text) = component.downcast::<Resource<Text>>() {
+                        files.push((
+                            text.url(),
+                            text.content.as_bytes().to_vec(),
+                        ));
+                    }
+                }
+                ResourceType::Custom(_) => (),
+            }
+        }
+        files.sort_by(|a, b| a.0.cmp(&b.0));
+        files
+    }
+
+    /// Removes the component with the given details from the cache.
+    pub fn remove(&mut self, details: &ComponentDetails) {
+        self.cache.remove(details);
+    }
+
+    /// Loads the [Css], [Js] or [Text] component with the given details from
+    /// the archive again and replaces the cached version. The component is
+    /// removed from the cache if the archive does not contain any of its files
+    /// anymore. Other resource types are not touched.
+    pub fn reload<A: Archive>(
+        &mut self,
+        details: &ComponentDetails,
+    ) -> anyhow::Result<()> {
+        let details = ComponentDetails {
+            resource_id: None,
+            ..details.clone()
+        };
+        match details.resource_type {
+            ResourceType::Css | ResourceType::JavaScript => (),
+            ResourceType::Text => return self.reload_text::<A>(&details),
+            _ => return Ok(()),
+        }
+        if A::get_file_list(&details).is_empty() {
+            log::debug!("Removing {details:?} from the cache");
+            self.remove(&details);
+            return Ok(());
+        }
+        log::debug!("Reloading {details:?}");
+        match details.resource_type {
+            ResourceType::Css => {
+                let options = CssOptions {
+                    id: details.component_id,
+                    level: details.level,
+                    browser_targets: self.browser_targets.clone(),
+                };
+                self.insert(Arc::new(Resource::<Css>::load::<A>(options)?));
+            }
+            _ => {
+                let options = JsOptions {
+                    id: details.component_id,
+                    level: details.level,
+                };
+                self.insert(Arc::new(Resource::<Js>::load::<A>(options)?));
+            }
+        }
+        Ok(())
+    }
+
+    /// Replaces all [Text] files and the [Messages] of the given component.
+    fn reload_text<A: Archive>(
+        &mut self,
+        details: &ComponentDetails,
+    ) -> anyhow::Result<()> {
+        log::debug!("Reloading {details:?}");
+        self.cache.retain(|d, _| {
+            d.component_id != details.component_id
+                || d.level != details.level
+                || d.resource_type != ResourceType::Text
+        });
+        self.load_text_component::<A>(details)
+    }
+
     /// Prepares the instance for further use in your program. After calling this
     /// method, the instance is not adjustable anymore.
     pub fn seal(self) -> Arc<Self> {
@@ -75,6 +239,7 @@ impl ArchiveCache {
             let options = CssOptions {
                 id,
                 level: ResourceLevel::Component,
+                browser_targets: self.browser_targets.clone(),
             };
             let css = Resource::<Css>::load::<A>(options)?;
             self.insert(Arc::new(css));
@@ -89,6 +254,7 @@ impl ArchiveCache {
             let options = CssOptions {
                 id,
                 level: ResourceLevel::Page,
+                browser_targets: self.browser_targets.clone(),
             };
             let css = Resource::<Css>::load::<A>(options)?;
             self.insert(Arc::new(css));
@@ -96,6 +262,104 @@ impl ArchiveCache {
         Ok(())
     }
 
+    /// Loads all [Image]s of the pages and components from the archive and
+    /// inserts them into the cache. For every image, resized variants with
+    /// the given widths are created in the format of the image and in the
+    /// given additional formats, see [ImageOptions].
+    ///
+    /// The variants are served by the
+    /// [ResourceServer](super::ResourceServer) next to the original image,
+    /// use [Resource::picture] to reference them in your views.
+    pub fn load_images<A: Archive>(
+        mut self,
+        widths: &[u32],
+        formats: &[ImageFormat],
+    ) -> anyhow::Result<Self> {
+        for file in <A as RustEmbed>::iter() {
+            let details = match A::parse(PathBuf::from(file.as_ref())) {
+                Ok(d) if d.resource_type == ResourceType::Image => d,
+                _ => continue,
+            };
+            let filename = match details.resource_id {
+                Some(f) => f,
+                None => continue,
+            };
+            let options = ImageOptions::new(
+                details.component_id,
+                details.level,
+                filename,
+            )
+            .with_widths(widths.to_vec())
+            .with_formats(formats.to_vec());
+            let image = Arc::new(Resource::<Image>::load::<A>(options)?);
+            // the variants are available using their own resource id
+            for variant in &image.variants {
+                let details = image
+                    .details()
+                    .clone()
+                    .with_resource_id(variant.resource_id.clone());
+                self.cache.insert(details, Arc::clone(&image) as _);
+            }
+            self.insert(image);
+        }
+        Ok(self)
+    }
+
+    /// Loads all [Text] files of the pages and components from the archive and
+    /// inserts them into the cache. The message files of a component, for
+    /// example `components/hello-world/text/en.ftl`, are combined into its
+    /// [Messages] that are used for [Localization](crate::resources::Localization).
+    pub fn load_text<A: Archive>(mut self) -> anyhow::Result<Self> {
+        let components = <A as RustEmbed>::iter()
+            .filter_map(|file| match A::parse(PathBuf::from(file.as_ref())) {
+                Ok(d) if d.resource_type == ResourceType::Text => {
+                    Some(ComponentDetails {
+                        resource_id: None,
+                        ..d
+                    })
+                }
+                _ => None,
+            })
+            .collect::<HashSet<_>>();
+        for details in components {
+            self.load_text_component::<A>(&details)?;
+        }
+        Ok(self)
+    }
+
+    fn load_text_component<A: Archive>(
+        &mut self,
+        details: &ComponentDetails,
+    ) -> anyhow::Result<()> {
+        let mut has_messages = false;
+        for file in A::get_file_list(details) {
+            let filename = match file.strip_prefix(A::path(details)) {
+                Ok(f) => f.to_path_buf(),
+                Err(_) => continue,
+            };
+            if filename.extension().and_then(|e| e.to_str())
+                == Some(MESSAGES_EXTENSION)
+            {
+                has_messages = true;
+                continue;
+            }
+            let options = TextOptions {
+                id: details.component_id.clone(),
+                level: details.level,
+                filename,
+            };
+            self.insert(Arc::new(Resource::<Text>::load::<A>(options)?));
+        }
+        if has_messages {
+            let options = MessagesOptions {
+                id: details.component_id.clone(),
+                level: details.level,
+            };
+            self.insert(Arc::new(Resource::<Messages>::load::<A>(options)?));
+        }
+        Ok(())
+    }
+
     /// Loads all [Js] components from the archive and inserts them into the
     /// cache.
     pub fn load_javascript<A: Archive>(mut self) -> anyhow::Result<Self> {
@@ -16,4 +16,13 @@ pub trait ArchiveComponent {
     /// When implementing this trait, these details should be generated by your
     /// [load](Self::load) method and stored in the component.
     fn details(&self) -> &ComponentDetails;
+    /// A short hash that changes whenever the content of the component
+    /// changes. It is computed once when the component is loaded and used to
+    /// create cache busting URLs, see [Resource::url](crate::resources::Resource::url).
+    ///
+    /// Returns `None` by default, the component is then served from a URL
+    /// without hash.
+    fn content_hash(&self) -> Option<String> {
+        None
+    }
 }
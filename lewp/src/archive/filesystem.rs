@@ -0,0 +1,62 @@
+//! File access used by archives that are defined with [lewp_filesystem_archive](crate::lewp_filesystem_archive).
+//!
+//! These functions are called by the code generated by the macro and are not
+//! meant to be used directly.
+
+use {
+    rust_embed::{utils::read_file_from_fs, EmbeddedFile},
+    std::{
+        borrow::Cow,
+        path::{Path, PathBuf},
+    },
+    walkdir::WalkDir,
+};
+
+/// Resolves the folder given to the macro. Relative folders are resolved
+/// against the manifest directory of the crate that defines the archive, the
+/// same way `rust-embed` does.
+pub fn resolve_root(manifest_dir: &str, folder: &str) -> PathBuf {
+    let folder = Path::new(folder);
+    match folder.is_absolute() {
+        true => folder.to_path_buf(),
+        false => Path::new(manifest_dir).join(folder),
+    }
+}
+
+/// Reads the file at the given path relative to `root` from disk. Returns
+/// `None` if the file does not exist or is located outside of `root`.
+pub fn get(root: &Path, file_path: &str) -> Option<EmbeddedFile> {
+    let root = root.canonicalize().ok()?;
+    let path = root.join(file_path).canonicalize().ok()?;
+    if !path.starts_with(&root) {
+        log::warn!(
+            "Refusing to read {file_path}, it is not located in the archive."
+        );
+        return None;
+    }
+    match read_file_from_fs(&path) {
+        Ok(f) => Some(f),
+        Err(e) => {
+            log::error!("Could not read {}: {e}", path.display());
+            None
+        }
+    }
+}
+
+/// Lists all files below `root`, sorted and relative to `root`.
+pub fn iter(root: &Path) -> impl Iterator<Item = Cow<'static, str>> + 'static {
+    let mut files = WalkDir::new(root)
+        .follow_links(true)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| {
+            e.path()
+                .strip_prefix(root)
+                .ok()
+                .map(|p| p.to_string_lossy().replace('\\', "/"))
+        })
+        .collect::<Vec<_>>();
+    files.sort();
+    files.into_iter().map(Cow::Owned)
+}
@@ -0,0 +1,390 @@
+use {
+    super::{Archive, ArchiveCache},
+    crate::component::ComponentDetails,
+    std::{
+        collections::{HashMap, HashSet},
+        marker::PhantomData,
+        path::{Path, PathBuf},
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+            Mutex,
+            RwLock,
+        },
+        thread::JoinHandle,
+        time::{Duration, SystemTime},
+    },
+    walkdir::WalkDir,
+};
+
+/// An [ArchiveCache] whose [Css](crate::resources::Css),
+/// [Js](crate::resources::Js) and [Text](crate::resources::Text) components
+/// can be rebuilt while your server is running.
+///
+/// Intended for development. Combined with an archive defined by
+/// [lewp_filesystem_archive](crate::lewp_filesystem_archive) and an
+/// [ArchiveWatcher], changes to the files in your archive show up on the next
+/// request without recompiling the binary.
+///
+/// Every call to [current](Self::current) returns the latest version of the
+/// cache. Request it once per request and pass it to your
+/// [Page](crate::page::Page), a reload does not change cache instances that
+/// have already been handed out.
+pub struct ReloadableArchiveCache<A: Archive> {
+    cache: RwLock<Arc<ArchiveCache>>,
+    /// Serializes reloads, so concurrent calls do not overwrite each others
+    /// changes.
+    reload: Mutex<()>,
+    archive: PhantomData<fn() -> A>,
+}
+
+impl<A: Archive> ReloadableArchiveCache<A> {
+    /// Creates a new instance that starts with the given cache.
+    pub fn new(archive_cache: ArchiveCache) -> Self {
+        Self {
+            cache: RwLock::new(Arc::new(archive_cache)),
+            reload: Mutex::new(()),
+            archive: PhantomData,
+        }
+    }
+
+    /// Loads all [Css](crate::resources::Css), [Js](crate::resources::Js) and
+    /// [Text](crate::resources::Text) components of the archive.
+    pub fn load() -> anyhow::Result<Self> {
+        let archive_cache = ArchiveCache::default()
+            .load_css::<A>()?
+            .load_javascript::<A>()?
+            .load_text::<A>()?;
+        Ok(Self::new(archive_cache))
+    }
+
+    /// Returns the current version of the cache.
+    pub fn current(&self) -> Arc<ArchiveCache> {
+        let cache = match self.cache.read() {
+            Ok(c) => c,
+            Err(e) => e.into_inner(),
+        };
+        Arc::clone(&cache)
+    }
+
+    /// Rebuilds all components that are affected by the given files. The
+    /// paths are relative to [root](super::ArchiveRoot::root), for example
+    /// `components/navigation/css/nav.css`.
+    ///
+    /// A component that fails to load, for example because of a syntax error
+    /// in its `CSS`, keeps its previous version and the error is logged.
+    pub fn reload(&self, changed_files: &[PathBuf]) {
+        let affected = changed_files
+            .iter()
+            .filter_map(|f| match A::parse(f.clone()) {
+                Ok(d) => Some(ComponentDetails {
+                    resource_id: None,
+                    ..d
+                }),
+                Err(e) => {
+                    log::debug!("Ignoring change of {}: {e}", f.display());
+                    None
+                }
+            })
+            .collect::<HashSet<_>>();
+        if affected.is_empty() {
+            return;
+        }
+        let _reload = match self.reload.lock() {
+            Ok(r) => r,
+            Err(e) => e.into_inner(),
+        };
+        let mut archive_cache = ArchiveCache::clone(&self.current());
+        for details in affected {
+            if let Err(e) = archive_cache.reload::<A>(&details) {
+                log::error!("Could not reload {details:?}: {e}");
+            }
+        }
+        match self.cache.write() {
+            Ok(mut c) => *c = Arc::new(archive_cache),
+            Err(e) => *e.into_inner() = Arc::new(archive_cache),
+        }
+    }
+}
+
+impl<A: Archive + 'static> ReloadableArchiveCache<A> {
+    /// Starts an [ArchiveWatcher] that checks the files in
+    /// [root](super::ArchiveRoot::root) for changes in the given interval and
+    /// reloads the affected components.
+    pub fn watch(self: &Arc<Self>, interval: Duration) -> ArchiveWatcher {
+        ArchiveWatcher::new::<A>(Arc::clone(self), interval)
+    }
+}
+
+/// Watches the files of an archive on a background thread and reloads a
+/// [ReloadableArchiveCache] when they change.
+///
+/// The files are polled by comparing their modification time, so no platform
+/// specific file system notifications are required. The watcher stops when it
+/// is dropped.
+pub struct ArchiveWatcher {
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl ArchiveWatcher {
+    fn new<A: Archive + 'static>(
+        archive_cache: Arc<ReloadableArchiveCache<A>>,
+        interval: Duration,
+    ) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = Arc::clone(&stop);
+        let handle = std::thread::spawn(move || {
+            let root = A::root();
+            log::info!("Watching {} for changes", root.display());
+            let mut files = Self::scan(&root);
+            while !thread_stop.load(Ordering::Relaxed) {
+                std::thread::park_timeout(interval);
+                if thread_stop.load(Ordering::Relaxed) {
+                    break;
+                }
+                let current = Self::scan(&root);
+                let changed = Self::changed_files(&files, &current);
+                if !changed.is_empty() {
+                    log::info!("Reloading changed files: {changed:?}");
+                    archive_cache.reload(&changed);
+                }
+                files = current;
+            }
+        });
+        Self {
+            stop,
+            handle: Some(handle),
+        }
+    }
+
+    /// Collects the modification time of all files below `root`.
+    fn scan(root: &Path) -> HashMap<PathBuf, SystemTime> {
+        WalkDir::new(root)
+            .follow_links(true)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+            .filter_map(|e| {
+                let modified = e.metadata().ok()?.modified().ok()?;
+                let path = e.path().strip_prefix(root).ok()?.to_path_buf();
+                Some((path, modified))
+            })
+            .collect()
+    }
+
+    /// Returns all files that have been added, modified or removed.
+    fn changed_files(
+        before: &HashMap<PathBuf, SystemTime>,
+        after: &HashMap<PathBuf, SystemTime>,
+    ) -> Vec<PathBuf> {
+        let mut changed = after
+            .iter()
+            .filter(|(path, modified)| before.get(*path) != Some(modified))
+            .map(|(path, _)| path.clone())
+            .chain(
+                before
+                    .keys()
+                    .filter(|path| !after.contains_key(*path))
+                    .cloned(),
+            )
+            .collect::<Vec<_>>();
+        changed.sort();
+        changed
+    }
+}
+
+impl Drop for ArchiveWatcher {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            handle.thread().unpark();
+            if handle.join().is_err() {
+                log::error!("The archive watcher panicked");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            archive::{filesystem, ArchiveRoot},
+            resources::{
+                BrowserTargets,
+                Css,
+                ResourceLevel,
+                ResourceType,
+                WebInterface,
+            },
+        },
+        rust_embed::{EmbeddedFile, RustEmbed},
+        std::{borrow::Cow, sync::MutexGuard},
+        tempfile::TempDir,
+    };
+
+    static ROOT: Mutex<Option<TempDir>> = Mutex::new(None);
+    static TEST: Mutex<()> = Mutex::new(());
+
+    /// Provides a fresh copy of the test files as root of [TempArchive]. The
+    /// copy is removed when it is dropped, tests using it run one after
+    /// another.
+    struct TempRoot {
+        _test: MutexGuard<'static, ()>,
+    }
+
+    impl TempRoot {
+        fn new() -> Self {
+            let test = TEST.lock().unwrap_or_else(|e| e.into_inner());
+            let dir = tempfile::tempdir().unwrap();
+            fs_extra::dir::copy(
+                "testfiles",
+                dir.path(),
+                &fs_extra::dir::CopyOptions::new().content_only(true),
+            )
+            .unwrap();
+            *ROOT.lock().unwrap() = Some(dir);
+            Self { _test: test }
+        }
+    }
+
+    impl Drop for TempRoot {
+        fn drop(&mut self) {
+            if let Ok(mut root) = ROOT.lock() {
+                *root = None;
+            }
+        }
+    }
+
+    struct TempArchive;
+
+    impl ArchiveRoot for TempArchive {
+        fn root() -> PathBuf {
+            ROOT.lock()
+                .unwrap()
+                .as_ref()
+                .expect("TempRoot has not been created")
+                .path()
+                .to_path_buf()
+        }
+    }
+
+    impl RustEmbed for TempArchive {
+        fn get(file_path: &str) -> Option<EmbeddedFile> {
+            filesystem::get(&Self::root(), file_path)
+        }
+        fn iter() -> impl Iterator<Item = Cow<'static, str>> + 'static {
+            filesystem::iter(&Self::root())
+        }
+    }
+
+    impl WebInterface for TempArchive {}
+
+    fn component_css(archive_cache: &ArchiveCache, id: &str) -> String {
+        let details = ComponentDetails::new(
+            id.into(),
+            ResourceType::Css,
+            ResourceLevel::Component,
+        );
+        archive_cache
+            .query::<Css>(&details)
+            .map(|c| c.content.get(&Default::default()).to_string())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn reload_changed_components() {
+        let _root = TempRoot::new();
+        let cache = ReloadableArchiveCache::<TempArchive>::load().unwrap();
+        let before = cache.current();
+        assert!(!component_css(&before, "footer").contains("rebeccapurple"));
+
+        let file = PathBuf::from("components/footer/css/footer.css");
+        std::fs::write(
+            TempArchive::root().join(&file),
+            "footer { color: rebeccapurple; }",
+        )
+        .unwrap();
+        cache.reload(std::slice::from_ref(&file));
+        assert!(
+            component_css(&cache.current(), "footer").contains("rebeccapurple")
+        );
+        assert!(!component_css(&before, "footer").contains("rebeccapurple"));
+
+        std::fs::remove_file(TempArchive::root().join(&file)).unwrap();
+        cache.reload(&[file]);
+        assert_eq!(component_css(&cache.current(), "footer"), "");
+    }
+
+    #[test]
+    fn reload_concurrently() {
+        let _root = TempRoot::new();
+        let cache = ReloadableArchiveCache::<TempArchive>::load().unwrap();
+        let files = ["footer", "toolbar"].map(|id| {
+            let file = PathBuf::from(format!("components/{id}/css/{id}.css"));
+            std::fs::write(
+                TempArchive::root().join(&file),
+                format!("{id} {{ color: rebeccapurple; }}"),
+            )
+            .unwrap();
+            file
+        });
+        std::thread::scope(|s| {
+            for file in &files {
+                let cache = &cache;
+                s.spawn(move || cache.reload(std::slice::from_ref(file)));
+            }
+        });
+        for id in ["footer", "toolbar"] {
+            assert!(
+                component_css(&cache.current(), id).contains("rebeccapurple")
+            );
+        }
+    }
+
+    #[test]
+    fn reload_with_browser_targets() {
+        let _root = TempRoot::new();
+        let archive_cache = ArchiveCache::default()
+            .with_browser_targets(
+                BrowserTargets::parse("safari >= 14, firefox >= 100").unwrap(),
+            )
+            .load_css::<TempArchive>()
+            .unwrap();
+        let cache = ReloadableArchiveCache::<TempArchive>::new(archive_cache);
+
+        let file = PathBuf::from("components/toolbar/css/toolbar.css");
+        std::fs::write(
+            TempArchive::root().join(&file),
+            "button { user-select: none; -moz-user-select: none; }",
+        )
+        .unwrap();
+        cache.reload(&[file]);
+        assert_eq!(
+            component_css(&cache.current(), "toolbar"),
+            "[data-lewp-id=\"toolbar\"] button{-webkit-user-select: none;user-select: none}"
+        );
+    }
+
+    #[test]
+    fn detect_changed_files() {
+        let now = SystemTime::now();
+        let later = now + Duration::from_secs(1);
+        let before = HashMap::from([
+            (PathBuf::from("a"), now),
+            (PathBuf::from("b"), now),
+            (PathBuf::from("c"), now),
+        ]);
+        let after = HashMap::from([
+            (PathBuf::from("a"), now),
+            (PathBuf::from("b"), later),
+            (PathBuf::from("d"), now),
+        ]);
+        assert_eq!(
+            ArchiveWatcher::changed_files(&before, &after),
+            vec![PathBuf::from("b"), PathBuf::from("c"), PathBuf::from("d")]
+        );
+    }
+}
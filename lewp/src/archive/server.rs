@@ -0,0 +1,505 @@
+//! A framework agnostic server for the resources of an [ArchiveCache].
+
+use {
+    super::{Archive, ArchiveCache, ArchiveComponent, ReloadableArchiveCache},
+    crate::{
+        component::ComponentDetails,
+        resources::{
+            content_hash as content_hash_of,
+            Css,
+            Entireness,
+            Image,
+            Js,
+            ResourceType,
+        },
+    },
+    mime::Mime,
+    rust_embed::RustEmbed,
+    std::{
+        fmt::Write,
+        marker::PhantomData,
+        path::Path,
+        sync::Arc,
+        time::Duration,
+    },
+};
+
+#[cfg(feature = "axum")]
+mod axum;
+
+/// The `Cache-Control` header value of resources that are requested using a
+/// URL containing their current content hash. The content behind such a URL
+/// never changes.
+const IMMUTABLE_CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+
+/// A resource that is ready to be sent to the client.
+#[derive(Debug, Clone)]
+pub struct ServedResource {
+    /// The content of the resource.
+    pub body: Vec<u8>,
+    /// The [Mime] type of the resource.
+    pub mime_type: Mime,
+    /// The quoted entity tag of the resource. It is created from the hash of
+    /// the content that is computed once when the resource is loaded.
+    pub etag: String,
+    /// The value of the `Cache-Control` header.
+    pub cache_control: String,
+}
+
+impl ServedResource {
+    /// Returns the headers that should be attached to the response as tuples
+    /// of name and value.
+    pub fn headers(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("content-type", self.mime_type.to_string()),
+            ("etag", self.etag.clone()),
+            ("cache-control", self.cache_control.clone()),
+        ]
+    }
+}
+
+/// The answer of a [ResourceServer] to a request.
+#[derive(Debug, Clone)]
+pub enum ResourceResponse {
+    /// The resource has been found and needs to be sent to the client.
+    Found(ServedResource),
+    /// The resource has been found, but the client already has the current
+    /// version (the given `If-None-Match` header matched). The body must not
+    /// be sent.
+    NotModified(ServedResource),
+    /// The requested resource does not exist.
+    NotFound,
+}
+
+impl ResourceResponse {
+    /// Returns the HTTP status code of the response.
+    pub fn status_code(&self) -> u16 {
+        match self {
+            Self::Found(_) => 200,
+            Self::NotModified(_) => 304,
+            Self::NotFound => 404,
+        }
+    }
+}
+
+/// Serves the resources of an [ArchiveCache] that are available at
+/// [WebInterface::web_root](crate::resources::WebInterface::web_root).
+///
+/// The request path is parsed using [Archive::parse] into [ComponentDetails].
+/// Processed [Css] and [Js] components are served from the [ArchiveCache],
+/// including the parts of the [Css] that are addressed by [Entireness].
+/// [Image]s and their variants are served from the [ArchiveCache] if they have
+/// been loaded using [ArchiveCache::load_images]. Any other file is served
+/// directly from the [Archive].
+///
+/// If the request path contains the current content hash of the component, as
+/// created by [Resource::url](crate::resources::Resource::url), the resource
+/// is served with an immutable `Cache-Control` header that allows clients and
+/// CDNs to cache it for one year. Requests with an outdated hash receive the
+/// current content with the regular header.
+///
+/// This server does not depend on a specific web framework. Pass the requested
+/// path and the value of the `If-None-Match` header to [serve](Self::serve) and
+/// convert the resulting [ResourceResponse] to your framework's response type.
+/// If you are using `axum`, enable the `axum` feature to get a ready-made
+/// router.
+pub struct ResourceServer<A: Archive> {
+    archive_cache: CacheSource<A>,
+    max_age: Option<Duration>,
+    archive: PhantomData<fn() -> A>,
+}
+
+/// The cache a [ResourceServer] takes its components from.
+enum CacheSource<A: Archive> {
+    Sealed(Arc<ArchiveCache>),
+    Reloadable(Arc<ReloadableArchiveCache<A>>),
+}
+
+impl<A: Archive> ResourceServer<A> {
+    /// Creates a new server for the given [ArchiveCache].
+    pub fn new(archive_cache: Arc<ArchiveCache>) -> Self {
+        Self {
+            archive_cache: CacheSource::Sealed(archive_cache),
+            max_age: None,
+            archive: PhantomData,
+        }
+    }
+
+    /// Creates a new server that always serves the current version of the
+    /// given [ReloadableArchiveCache].
+    pub fn new_reloadable(
+        archive_cache: Arc<ReloadableArchiveCache<A>>,
+    ) -> Self {
+        Self {
+            archive_cache: CacheSource::Reloadable(archive_cache),
+            max_age: None,
+            archive: PhantomData,
+        }
+    }
+
+    fn archive_cache(&self) -> Arc<ArchiveCache> {
+        match &self.archive_cache {
+            CacheSource::Sealed(c) => Arc::clone(c),
+            CacheSource::Reloadable(c) => c.current(),
+        }
+    }
+
+    /// Sets the `max-age` of the `Cache-Control` header. If not set, the
+    /// clients are required to revalidate the resource on every request using
+    /// its entity tag.
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Serves the resource at the given request path, for example
+    /// `/resources/components/hello-world/js`.
+    ///
+    /// `if_none_match` is the value of the `If-None-Match` request header.
+    pub fn serve(
+        &self,
+        request_path: &str,
+        if_none_match: Option<&str>,
+    ) -> ResourceResponse {
+        let (body, mime_type, etag, immutable) = match self.lookup(request_path)
+        {
+            Ok(r) => r,
+            Err(e) => {
+                log::debug!("Requested resource could not be served: {e}");
+                return ResourceResponse::NotFound;
+            }
+        };
+        let resource = ServedResource {
+            etag,
+            cache_control: match immutable {
+                true => String::from(IMMUTABLE_CACHE_CONTROL),
+                false => self.cache_control(),
+            },
+            body,
+            mime_type,
+        };
+        match if_none_match {
+            Some(v) if Self::etag_matches(v, &resource.etag) => {
+                ResourceResponse::NotModified(resource)
+            }
+            _ => ResourceResponse::Found(resource),
+        }
+    }
+
+    /// Returns the content, [Mime] type and entity tag of the requested
+    /// resource. The returned flag is true if the request path contains the
+    /// current content hash of the resource.
+    fn lookup(
+        &self,
+        request_path: &str,
+    ) -> anyhow::Result<(Vec<u8>, Mime, String, bool)> {
+        let path = match Path::new(request_path).strip_prefix(A::web_root()) {
+            Ok(p) => p.to_path_buf(),
+            Err(_) => {
+                return Err(anyhow::anyhow!(
+                    "{request_path} is not located in {}",
+                    A::web_root().display()
+                ))
+            }
+        };
+        let (details, hash) = A::parse_hashed(path)?;
+        let archive_cache = self.archive_cache();
+
+        if details.resource_type == ResourceType::Image {
+            if let Some(image) = archive_cache.query::<Image>(&details) {
+                let variant =
+                    details.resource_id.as_ref().and_then(|r| image.variant(r));
+                // variants are created from the original content, so its hash
+                // identifies them together with their id
+                let hash = hex(&image.sha256);
+                return Ok(match variant {
+                    Some(v) => (
+                        v.content.clone(),
+                        v.mime_type.clone(),
+                        Self::etag(&hash, Some(&v.resource_id)),
+                        false,
+                    ),
+                    None => (
+                        image.content.clone(),
+                        image.mime.clone(),
+                        Self::etag(&hash, None),
+                        false,
+                    ),
+                });
+            }
+        }
+
+        match (&details.resource_type, &details.resource_id) {
+            (ResourceType::Css, resource_id)
+                if Entireness::from_resource_id(resource_id).is_some() =>
+            {
+                let entireness = Entireness::from_resource_id(resource_id)
+                    .unwrap_or_default();
+                let details = ComponentDetails {
+                    resource_id: None,
+                    ..details
+                };
+                match archive_cache.query::<Css>(&details) {
+                    Some(c) => Ok((
+                        c.content.get(&entireness).as_bytes().to_vec(),
+                        Css::mime_type(),
+                        Self::component_etag(
+                            &c.content_hash,
+                            c.content.get(&entireness).as_bytes(),
+                            entireness.resource_id().as_deref(),
+                        ),
+                        hash.is_some() && hash == c.content_hash,
+                    )),
+                    None => Err(anyhow::anyhow!("{details:?} not found")),
+                }
+            }
+            (ResourceType::JavaScript, None) => {
+                match archive_cache.query::<Js>(&details) {
+                    Some(j) => Ok((
+                        j.content.as_bytes().to_vec(),
+                        Js::mime_type(),
+                        Self::component_etag(
+                            &j.content_hash,
+                            j.content.as_bytes(),
+                            None,
+                        ),
+                        hash.is_some() && hash == j.content_hash,
+                    )),
+                    None => Err(anyhow::anyhow!("{details:?} not found")),
+                }
+            }
+            (_, Some(_)) => {
+                let file_path = A::path(&details);
+                let file = file_path
+                    .to_str()
+                    .and_then(<A as RustEmbed>::get)
+                    .ok_or_else(|| {
+                    anyhow::anyhow!("{} not found", file_path.display())
+                })?;
+                Ok((
+                    file.data.to_vec(),
+                    Self::mime_from_extension(&file_path),
+                    Self::etag(&hex(&file.metadata.sha256_hash()), None),
+                    false,
+                ))
+            }
+            _ => Err(anyhow::anyhow!("{details:?} can not be served")),
+        }
+    }
+
+    fn cache_control(&self) -> String {
+        match self.max_age {
+            Some(d) => format!("public, max-age={}", d.as_secs()),
+            None => String::from("public, no-cache"),
+        }
+    }
+
+    /// Creates the entity tag of a [Css] or [Js] component from its content
+    /// hash. The given content is only hashed if the component has none.
+    fn component_etag(
+        content_hash: &Option<String>,
+        content: &[u8],
+        resource_id: Option<&Path>,
+    ) -> String {
+        match content_hash {
+            Some(h) => Self::etag(h, resource_id),
+            None => Self::etag(&content_hash_of(content), None),
+        }
+    }
+
+    /// Creates an entity tag from the given hash. The [ResourceId](crate::resources::ResourceId)
+    /// distinguishes the parts of a resource that share the hash, for example
+    /// the variants of an [Image].
+    fn etag(hash: &str, resource_id: Option<&Path>) -> String {
+        match resource_id {
+            Some(r) => format!("\"{hash}-{}\"", r.display()),
+            None => format!("\"{hash}\""),
+        }
+    }
+
+    fn etag_matches(if_none_match: &str, etag: &str) -> bool {
+        if_none_match
+            .split(',')
+            .map(|e| e.trim().trim_start_matches("W/"))
+            .any(|e| e == "*" || e == etag)
+    }
+
+    fn mime_from_extension(path: &Path) -> Mime {
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_lowercase());
+        match extension.as_deref() {
+            Some("css") => mime::TEXT_CSS,
+            Some("js") => mime::APPLICATION_JAVASCRIPT,
+            Some("txt") => mime::TEXT_PLAIN,
+            Some("png") => mime::IMAGE_PNG,
+            Some("jpg") | Some("jpeg") => mime::IMAGE_JPEG,
+            Some("gif") => mime::IMAGE_GIF,
+            Some("bmp") => mime::IMAGE_BMP,
+            Some("svg") => mime::IMAGE_SVG,
+            Some("webp") => "image/webp".parse().unwrap_or(mime::IMAGE_STAR),
+            Some("avif") => "image/avif".parse().unwrap_or(mime::IMAGE_STAR),
+            Some("woff") => mime::FONT_WOFF,
+            Some("woff2") => mime::FONT_WOFF2,
+            _ => mime::APPLICATION_OCTET_STREAM,
+        }
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    let mut hex = String::with_capacity(bytes.len() * 2);
+    for b in bytes {
+        let _ = write!(hex, "{b:02x}");
+    }
+    hex
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{lewp_archive, resources::WebInterface},
+    };
+
+    lewp_archive!(ServerArchive, "testfiles");
+    impl WebInterface for ServerArchive {}
+
+    fn server() -> ResourceServer<ServerArchive> {
+        let archive_cache = ArchiveCache::default()
+            .load_css::<ServerArchive>()
+            .unwrap()
+            .load_javascript::<ServerArchive>()
+            .unwrap()
+            .load_images::<ServerArchive>(&[64], &[])
+            .unwrap()
+            .seal();
+        ResourceServer::new(archive_cache)
+    }
+
+    #[test]
+    fn serve_archive_cache_resources() {
+        let server = server();
+
+        let js =
+            match server.serve("/resources/components/hello-world/js", None) {
+                ResourceResponse::Found(r) => r,
+                r => panic!("Expected the JavaScript to be found, got {r:?}"),
+            };
+        assert_eq!(js.mime_type, mime::APPLICATION_JAVASCRIPT);
+        assert!(!js.body.is_empty());
+
+        let css =
+            match server.serve("/resources/components/hello-world/css", None) {
+                ResourceResponse::Found(r) => r,
+                r => panic!("Expected the CSS to be found, got {r:?}"),
+            };
+        assert_eq!(css.mime_type, mime::TEXT_CSS);
+        assert_eq!(css.cache_control, "public, no-cache");
+
+        let response = server.serve(
+            "/resources/components/hello-world/css/non-render-critical.css",
+            None,
+        );
+        assert_eq!(response.status_code(), 200);
+
+        let response = server.serve(
+            "/resources/components/hello-world/images/rust-logo-512x512-blk.png",
+            None,
+        );
+        match response {
+            ResourceResponse::Found(r) => {
+                assert_eq!(r.mime_type, mime::IMAGE_PNG)
+            }
+            r => panic!("Expected the image to be found, got {r:?}"),
+        }
+
+        let response = server.serve(
+            "/resources/components/hello-world/images/rust-logo-512x512-blk.64w.png",
+            None,
+        );
+        match response {
+            ResourceResponse::Found(r) => {
+                assert_eq!(r.mime_type, mime::IMAGE_PNG)
+            }
+            r => panic!("Expected the image variant to be found, got {r:?}"),
+        }
+
+        let response = server
+            .serve("/resources/components/hello-world/js", Some(&js.etag));
+        assert_eq!(response.status_code(), 304);
+
+        let url = server
+            .archive_cache()
+            .query::<Js>(&ComponentDetails::new(
+                "hello-world".into(),
+                ResourceType::JavaScript,
+                crate::resources::ResourceLevel::Component,
+            ))
+            .unwrap()
+            .url();
+        match server.serve(&url.display().to_string(), None) {
+            ResourceResponse::Found(r) => {
+                assert_eq!(r.cache_control, IMMUTABLE_CACHE_CONTROL);
+                assert_eq!(r.body, js.body);
+            }
+            r => {
+                panic!("Expected the hashed JavaScript to be found, got {r:?}")
+            }
+        }
+        let response = server
+            .serve("/resources/components/hello-world/js.00000000.js", None);
+        match response {
+            ResourceResponse::Found(r) => {
+                assert_eq!(r.cache_control, "public, no-cache")
+            }
+            r => panic!("Expected the JavaScript to be found, got {r:?}"),
+        }
+
+        assert_eq!(
+            server
+                .serve("/resources/components/not-existing/js", None)
+                .status_code(),
+            404
+        );
+        assert_eq!(
+            server
+                .serve("/somewhere/components/hello-world/js", None)
+                .status_code(),
+            404
+        );
+    }
+    #[test]
+    fn reuse_content_hashes_as_etags() {
+        let server = server();
+        let details = ComponentDetails::new(
+            "hello-world".into(),
+            ResourceType::Css,
+            crate::resources::ResourceLevel::Component,
+        );
+        let css = server.archive_cache().get::<Css>(&details).unwrap();
+        let hash = css.content_hash.clone().unwrap();
+
+        let etag = |path: &str| match server.serve(path, None) {
+            ResourceResponse::Found(r) => r.etag,
+            r => panic!("Expected {path} to be found, got {r:?}"),
+        };
+        assert_eq!(
+            etag("/resources/components/hello-world/css"),
+            format!("\"{hash}\"")
+        );
+        assert_eq!(
+            etag("/resources/components/hello-world/css/render-critical.css"),
+            format!("\"{hash}-render-critical.css\"")
+        );
+
+        let image = etag(
+            "/resources/components/hello-world/images/rust-logo-512x512-blk.png",
+        );
+        let variant = etag(
+            "/resources/components/hello-world/images/rust-logo-512x512-blk.64w.png",
+        );
+        assert_ne!(image, variant);
+        assert!(variant.starts_with(image.trim_end_matches('"')));
+    }
+}
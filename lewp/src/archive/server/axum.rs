@@ -0,0 +1,69 @@
+//! Adapter to use a [ResourceServer] with [axum](::axum).
+
+use {
+    super::{ResourceResponse, ResourceServer},
+    crate::archive::Archive,
+    ::axum::{
+        extract::State,
+        http::{header, HeaderMap, HeaderName, HeaderValue, StatusCode, Uri},
+        response::{IntoResponse, Response},
+        routing::get,
+        Router,
+    },
+    std::sync::Arc,
+};
+
+impl IntoResponse for ResourceResponse {
+    fn into_response(self) -> Response {
+        let status = StatusCode::from_u16(self.status_code())
+            .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        let (resource, send_body) = match self {
+            Self::Found(r) => (r, true),
+            Self::NotModified(r) => (r, false),
+            Self::NotFound => return status.into_response(),
+        };
+        let mut headers = HeaderMap::new();
+        for (name, value) in resource.headers() {
+            let value = match HeaderValue::from_str(&value) {
+                Ok(v) => v,
+                Err(e) => {
+                    log::error!("Invalid value for header \"{name}\": {e}");
+                    continue;
+                }
+            };
+            headers.insert(HeaderName::from_static(name), value);
+        }
+        if send_body {
+            (status, headers, resource.body).into_response()
+        } else {
+            (status, headers).into_response()
+        }
+    }
+}
+
+impl<A: Archive + 'static> ResourceServer<A> {
+    /// Creates an [axum](::axum) [Router] that serves all resources below
+    /// [WebInterface::web_root](crate::resources::WebInterface::web_root).
+    ///
+    /// The resulting router can be merged into the router of your application.
+    pub fn into_router(self) -> Router {
+        let route = format!(
+            "{}/*path",
+            A::web_root().display().to_string().trim_end_matches('/')
+        );
+        Router::new()
+            .route(&route, get(Self::handle))
+            .with_state(Arc::new(self))
+    }
+
+    async fn handle(
+        State(server): State<Arc<Self>>,
+        uri: Uri,
+        headers: HeaderMap,
+    ) -> ResourceResponse {
+        let if_none_match = headers
+            .get(header::IF_NONE_MATCH)
+            .and_then(|v| v.to_str().ok());
+        server.serve(uri.path(), if_none_match)
+    }
+}
@@ -8,12 +8,14 @@ pub use {dependency_list::DependencyList, details::ComponentDetails};
 
 use {
     crate::{
-        html::{Node, NodeList},
+        html::{Node, NodeList, OwnedNode},
         view::ComponentView,
+        Error,
     },
+    futures_util::lock::{Mutex as AsyncMutex, MutexGuard},
     std::{
-        cell::{Ref, RefCell},
-        rc::Rc,
+        future::Future,
+        sync::{Arc, Mutex, PoisonError},
     },
 };
 
@@ -32,22 +34,64 @@ where
     /// is not executed automatically, it needs to be called by the user's
     /// implementation. Mostly, it is called from another component's
     /// [main](Component::main) method.
-    fn update(&mut self, _message: Self::Message) {}
+    ///
+    /// If an error is returned, the [fallback_view](Self::fallback_view) is
+    /// rendered.
+    fn update(&mut self, _message: Self::Message) -> anyhow::Result<()> {
+        Ok(())
+    }
     /// The main method designing the behavior of the component. Does nothing by
     /// default.
-    fn main(&mut self) {}
+    ///
+    /// If an error is returned, the [fallback_view](Self::fallback_view) is
+    /// rendered.
+    fn main(&mut self) -> anyhow::Result<()> {
+        Ok(())
+    }
+    /// The asynchronous variant of [main](Self::main). Use it to load the
+    /// data of your component, for example from a database, without blocking
+    /// the thread. It is called instead of [main](Self::main) when the
+    /// component is added using
+    /// [PageView::push_async](crate::view::PageView::push_async). The
+    /// components of a page are loaded concurrently.
+    ///
+    /// `lewp` does not depend on a specific runtime, so you can await any
+    /// future in here. The returned future needs to be [Send], so the page can
+    /// be loaded on a multi-threaded runtime.
+    ///
+    /// Calls [main](Self::main) by default.
+    fn main_async(&mut self) -> impl Future<Output = anyhow::Result<()>> + Send
+    where
+        Self: Send,
+    {
+        async move { self.main() }
+    }
     /// Defines the view of the component.
-    fn view(&self) -> Option<Node>;
+    ///
+    /// If an error is returned, the [fallback_view](Self::fallback_view) is
+    /// rendered.
+    fn view(&self) -> anyhow::Result<Option<Node>>;
     /// Use this when rendering a nested component. This ensures that all
     /// required attributes are set to make your `JavaScript` and `CSS` work on client side.
-    fn nested_view(&self) -> Option<Node> {
-        match self.view() {
-            Some(v) => {
-                v.to_component_view(self.id());
-                Some(v)
+    fn nested_view(&self) -> anyhow::Result<Option<Node>> {
+        let view = self.view()?;
+        if let Some(v) = &view {
+            v.to_component_view(self.id());
+            if let Some(state) = self.client_state() {
+                v.attach_client_state(&state);
             }
-            None => None,
         }
+        Ok(view)
+    }
+    /// The view that is rendered instead of [view](Self::view) if one of the
+    /// lifecycle methods of the component failed. This makes the component an
+    /// error boundary, the remaining page is rendered as usual. Errors of
+    /// nested components are handled by the boundary of their parent if they
+    /// are propagated.
+    ///
+    /// Defaults to `None`, so the component is left out of the page.
+    fn fallback_view(&self, _error: &Error) -> Option<Node> {
+        None
     }
     /// Defines the additional head nodes this component requires.
     ///
@@ -63,24 +107,52 @@ where
     fn dependency_list(&self) -> DependencyList {
         DependencyList::default()
     }
+    /// Returns the state that is handed to the client. It is passed as
+    /// second parameter to the `init` function exported by the `JavaScript`
+    /// of your component.
+    ///
+    /// Defaults to `None`.
+    fn client_state(&self) -> Option<serde_json::Value> {
+        None
+    }
+    /// Receives the [InstanceId] of the component's view before
+    /// [main](Self::main) or [main_async](Self::main_async) is called. Store it if you need to reference the
+    /// rendered view, for example to create unique `id` attributes.
+    ///
+    /// Does nothing by default.
+    fn set_instance_id(&mut self, _instance_id: InstanceId) {}
 }
 
 /// A unique component ID.
 pub type ComponentId = String;
 
+/// An ID that is unique for every component view on a page. It is composed of
+/// the [ComponentId] and a counter, for example `hello-world-2`, and is
+/// attached to the view using the `data-lewp-instance` attribute.
+pub type InstanceId = String;
+
 /// A component that is used to create web pages. This struct can be created from
 /// a [ComponentModel].
+///
+/// The component is [Send] if the model is [Send], so it can be used in
+/// multi-threaded async runtimes.
 pub struct Component<C>
 where
     C: ComponentModel,
 {
     ///// Contains head nodes required by the component.
-    head: Rc<RefCell<NodeList>>,
+    head: Arc<Mutex<Vec<OwnedNode>>>,
     /// An instance of the model that is implemented by the user.
-    model: Rc<RefCell<C>>,
+    model: Arc<AsyncMutex<C>>,
     /// Contains the rendered view. This view gets initially created when the
     /// [main](Self::main) or [update](Self::update) method is called.
-    view: Rc<RefCell<Option<Node>>>,
+    view: Arc<Mutex<Option<OwnedNode>>>,
+    /// The [InstanceId] of the current view.
+    instance_id: Option<InstanceId>,
+    /// The error of the last run, if the component failed. It is shared
+    /// with the handle that runs the instance, see
+    /// [share_instance](Self::share_instance).
+    error: Arc<Mutex<Option<Arc<Error>>>>,
 }
 
 impl<C> Component<C>
@@ -89,70 +161,178 @@ where
 {
     /// Executes and renders the component by calling its
     /// [main](Component::main) method and updates its view.
+    ///
+    /// If the component fails, its [fallback view](ComponentModel::fallback_view)
+    /// is used and the error is available using [error](Self::error).
     pub fn main(&mut self) {
-        log::debug!("Running component \"{}\"", self.model.borrow().id());
-        self.model.borrow_mut().main();
-        self.update_content();
+        log::debug!("Running component \"{}\"", self.lock_model().id());
+        self.attach_instance_id_to_model();
+        let result = self.lock_model().main();
+        self.update_content(result);
+    }
+
+    /// The asynchronous variant of [main](Self::main). Awaits the
+    /// [main_async](ComponentModel::main_async) method of the model and updates
+    /// its view.
+    ///
+    /// The model is locked until the future is completed, so the component
+    /// must not be accessed in the meantime.
+    pub async fn main_async(&mut self)
+    where
+        C: Send,
+    {
+        log::debug!(
+            "Running component \"{}\" asynchronously",
+            self.lock_model().id()
+        );
+        self.attach_instance_id_to_model();
+        let result = self.model.lock().await.main_async().await;
+        self.update_content(result);
+    }
+
+    /// Hands the [InstanceId] of the current view to the model.
+    fn attach_instance_id_to_model(&mut self) {
+        if let Some(instance_id) = &self.instance_id {
+            self.lock_model().set_instance_id(instance_id.clone());
+        }
     }
 
     /// Updates the model by calling the models [update](Component::update)
     /// method using the given message.
     pub fn update(&mut self, message: <C as ComponentModel>::Message) {
-        log::debug!(
-            "Updating component state \"{}\"",
-            self.model.borrow().id()
-        );
-        self.model.borrow_mut().update(message);
-        self.update_content();
+        log::debug!("Updating component state \"{}\"", self.lock_model().id());
+        let result = self.lock_model().update(message);
+        self.update_content(result);
     }
 
     /// Updates the content of the component. This is called in [Self::main],
     /// as well as in [Self::update] (required for nested components).
-    fn update_content(&mut self) {
-        self.update_view();
+    fn update_content(&mut self, result: anyhow::Result<()>) {
+        self.update_view(result);
         self.update_head();
     }
 
     /// Updates the view by calling model's [view](Component::view) method.
     /// This is especially required for nested components as the reference to
-    /// the view gets stored in the [PageView] instance.
-    fn update_view(&mut self) {
-        log::debug!("Updating view for \"{}\"", self.model.borrow().id());
-        let mut view = self.view.borrow_mut();
-        *view = self.model.borrow().view();
+    /// the view gets stored in the [PageView](crate::view::PageView) instance.
+    ///
+    /// The fallback view is used if the given result of the previous
+    /// lifecycle method or the view itself is an error. The view is prepared
+    /// to be a component view and stored as [OwnedNode].
+    fn update_view(&mut self, result: anyhow::Result<()>) {
+        let model = self.lock_model();
+        log::debug!("Updating view for \"{}\"", model.id());
+        let (view, error) = match result.and_then(|_| model.view()) {
+            Ok(v) => (v, None),
+            Err(source) => {
+                let error = Error::Component {
+                    id: model.id(),
+                    source,
+                };
+                log::error!("{error}, rendering fallback view");
+                (model.fallback_view(&error), Some(Arc::new(error)))
+            }
+        };
+        let view = view.map(|v| {
+            v.to_component_view(Self::filter_id(model.id()));
+            if let Some(instance_id) = &self.instance_id {
+                v.attach_instance_id(instance_id);
+            }
+            if let Some(state) = model.client_state() {
+                v.attach_client_state(&state);
+            }
+            OwnedNode::from(v)
+        });
+        drop(model);
+        *lock(&self.view) = view;
+        *lock(&self.error) = error;
     }
 
     /// Updates the head nodes by calling model's [head](Component::head) method.
     /// This is especially required for nested components as the reference to
-    /// the head gets stored in the [PageView] instance.
-    fn update_head(&mut self) {
-        log::debug!("Updating head nodes for \"{}\"", self.model.borrow().id());
-        let mut head = self.head.borrow_mut();
-        *head = self.model.borrow().head();
+    /// the head gets stored in the [PageView](crate::view::PageView) instance.
+    pub(crate) fn update_head(&mut self) {
+        let model = self.lock_model();
+        log::debug!("Updating head nodes for \"{}\"", model.id());
+        *lock(&self.head) = model.head().iter().map(OwnedNode::from).collect();
     }
 
     /// Returns a clone of the given component view. This is for internal use
-    /// only because it reveals a [RefCell] to the user.
-    /// This method is called by the [HtmlPage] for further processing.
-    pub(crate) fn view(&self) -> Rc<RefCell<Option<Node>>> {
-        let view = self.view.borrow_mut();
-        match *view {
-            Some(ref v) => v.to_component_view(self.id()),
-            None => (),
-        };
-        Rc::clone(&self.view)
+    /// only because it reveals a [Mutex] to the user.
+    /// This method is called by the [PageView](crate::view::PageView) for
+    /// further processing.
+    pub(crate) fn view(&self) -> Arc<Mutex<Option<OwnedNode>>> {
+        Arc::clone(&self.view)
+    }
+
+    /// Starts a new instance of the component with the given [InstanceId].
+    /// The view and the error of the previous instance stay untouched, so the
+    /// same component can be added to a page multiple times.
+    pub(crate) fn new_instance(&mut self, instance_id: InstanceId) {
+        self.view = Arc::new(Mutex::new(None));
+        self.error = Arc::new(Mutex::new(None));
+        self.instance_id = Some(instance_id);
+    }
+
+    /// Creates a handle to the current instance that shares the model, the
+    /// view, the head and the error with this component. This is used to run the
+    /// instance after it has been added to the
+    /// [PageView](crate::view::PageView).
+    pub(crate) fn share_instance(&self) -> Self {
+        Self {
+            head: Arc::clone(&self.head),
+            model: Arc::clone(&self.model),
+            view: Arc::clone(&self.view),
+            instance_id: self.instance_id.clone(),
+            error: Arc::clone(&self.error),
+        }
+    }
+
+    /// Returns the address of the model. Instances with the same address
+    /// share their model.
+    pub(crate) fn model_address(&self) -> usize {
+        Arc::as_ptr(&self.model) as *const () as usize
+    }
+
+    /// Returns the [InstanceId] of the current view. Is `None` until the
+    /// component has been added to a [PageView](crate::view::PageView).
+    pub fn instance_id(&self) -> Option<&InstanceId> {
+        self.instance_id.as_ref()
     }
 
     /// Returns a clone of the given component head. This is for internal use
-    /// only because it reveals a [RefCell] to the user.
-    /// This method is called by the [HtmlPage] for further processing.
-    pub(crate) fn head(&self) -> Rc<RefCell<NodeList>> {
-        Rc::clone(&self.head)
+    /// only because it reveals a [Mutex] to the user.
+    /// This method is called by the [PageView](crate::view::PageView) for
+    /// further processing.
+    pub(crate) fn head(&self) -> Arc<Mutex<Vec<OwnedNode>>> {
+        Arc::clone(&self.head)
+    }
+
+    /// Returns the error of the last run if the component failed. This
+    /// includes instances that have been added using
+    /// [PageView::push_async](crate::view::PageView::push_async), as soon as
+    /// the page has been loaded.
+    pub fn error(&self) -> Option<Arc<Error>> {
+        lock(&self.error).clone()
+    }
+
+    /// Returns a locked reference of the model, or `None` if the model is
+    /// currently locked by [main_async](Self::main_async).
+    pub fn model(&self) -> Option<MutexGuard<'_, C>> {
+        self.model.try_lock()
     }
 
-    /// Returns a borrowed reference of the model.
-    pub fn model(&self) -> Ref<C> {
-        self.model.borrow()
+    /// Locks the model for the lifecycle methods. They take `&mut self`, so
+    /// [main_async](Self::main_async) can not run on this handle meanwhile.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the model is locked by [main_async](Self::main_async) on a
+    /// different handle of the same model.
+    pub(crate) fn lock_model(&self) -> MutexGuard<'_, C> {
+        self.model
+            .try_lock()
+            .expect("The model is locked by a running main_async method")
     }
 
     /// Returns the [ComponentId] filtered by allowed characters.
@@ -160,20 +340,32 @@ where
     /// filtered.
     ///
     /// Allowed characters are: `[a-z]`, `[0-9]` and `-`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the model is currently locked by
+    /// [main_async](Self::main_async).
     pub fn id(&self) -> ComponentId {
-        self.model
-            .borrow()
-            .id()
-            .to_lowercase()
+        Self::filter_id(self.lock_model().id())
+    }
+
+    fn filter_id(id: ComponentId) -> ComponentId {
+        id.to_lowercase()
             .chars()
             .filter(|x| x.is_alphanumeric() || x == &'-')
             .collect()
     }
 
     /// Returns the dependency list of the component.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the model is currently locked by
+    /// [main_async](Self::main_async).
     pub fn dependency_list(&self) -> DependencyList {
-        let mut list = DependencyList::from(self.model().id());
-        list.append(self.model().dependency_list());
+        let model = self.lock_model();
+        let mut list = DependencyList::from(model.id());
+        list.append(model.dependency_list());
         list
     }
 }
@@ -181,9 +373,22 @@ where
 impl<C: ComponentModel> From<C> for Component<C> {
     fn from(model: C) -> Self {
         log::debug!("Creating new component for model: \"{}\"", model.id());
-        let model = Rc::new(RefCell::new(model));
-        let view = Rc::new(RefCell::new(None));
-        let head = Rc::new(RefCell::new(NodeList::new()));
-        Self { model, view, head }
+        let model = Arc::new(AsyncMutex::new(model));
+        let view = Arc::new(Mutex::new(None));
+        let head = Arc::new(Mutex::new(vec![]));
+        Self {
+            model,
+            view,
+            head,
+            instance_id: None,
+            error: Arc::new(Mutex::new(None)),
+        }
     }
 }
+
+/// Locks the given [Mutex]. A poisoned lock is recovered, because the
+/// contained nodes are replaced as a whole and can not be left in an
+/// inconsistent state.
+pub(crate) fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
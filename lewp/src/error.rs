@@ -0,0 +1,79 @@
+//! The error type of `lewp`.
+
+use {
+    crate::{component::ComponentId, page::PageId},
+    std::fmt,
+};
+
+/// Errors that occur while running or rendering a page.
+#[derive(Debug)]
+pub enum Error {
+    /// A component failed while running its [main](crate::component::ComponentModel::main),
+    /// [update](crate::component::ComponentModel::update) or
+    /// [view](crate::component::ComponentModel::view) method.
+    Component {
+        /// The id of the failed component.
+        id: ComponentId,
+        /// The error returned by the component.
+        source: anyhow::Error,
+    },
+    /// The [main](crate::page::PageModel::main) method of a page failed.
+    Page {
+        /// The id of the failed page.
+        id: PageId,
+        /// The error returned by the page.
+        source: anyhow::Error,
+    },
+    /// The constructor of the matched [Route](crate::router::Route) failed,
+    /// so the page could not be created.
+    Route {
+        /// The pattern of the matched route.
+        pattern: String,
+        /// The error returned by the constructor.
+        source: anyhow::Error,
+    },
+    /// The page could not be serialized or written.
+    Render(std::io::Error),
+    /// No [Route](crate::router::Route) of the
+    /// [Router](crate::router::Router) matches the requested path.
+    NotFound {
+        /// The requested path.
+        path: String,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Component { id, source } => {
+                write!(f, "Component \"{id}\" failed: {source}")
+            }
+            Self::Page { id, source } => {
+                write!(f, "Page \"{id}\" failed: {source}")
+            }
+            Self::Route { pattern, source } => {
+                write!(f, "Route \"{pattern}\" failed: {source}")
+            }
+            Self::Render(e) => write!(f, "Could not render the page: {e}"),
+            Self::NotFound { path } => write!(f, "No page found at \"{path}\""),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Component { source, .. }
+            | Self::Page { source, .. }
+            | Self::Route { source, .. } => Some(source.as_ref()),
+            Self::Render(e) => Some(e),
+            Self::NotFound { .. } => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Self::Render(e)
+    }
+}
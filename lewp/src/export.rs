@@ -0,0 +1,319 @@
+//! Exports your pages and their resources as a static site.
+//!
+//! A [StaticSite] renders every added page and writes it to an output
+//! directory, together with all files of the
+//! [ArchiveCache](crate::archive::ArchiveCache) that are served by the
+//! [ResourceServer](crate::archive::ResourceServer). The resources are located
+//! at their URL, so the output directory can be uploaded to any static file
+//! host without changing the rendered pages:
+//! ```text
+//! OUTPUT/index.html
+//! OUTPUT/about/index.html
+//! OUTPUT/resources/components/hello-world/js.3f2a1c09.js
+//! OUTPUT/resources/components/hello-world/css.5be2a1d7.css
+//! OUTPUT/resources/components/hello-world/images/rust-logo-512x512-blk.png
+//! ```
+//!
+//! ```rust
+//! # use lewp::{
+//! #     lewp_archive,
+//! #     archive::ArchiveCache,
+//! #     export::StaticSite,
+//! #     page::{PageId, PageModel},
+//! #     resources::WebInterface,
+//! # };
+//! # lewp_archive!(ResourceArchive, "testfiles");
+//! # impl WebInterface for ResourceArchive {}
+//! # struct HomePage;
+//! # impl PageModel for HomePage {
+//! #     fn id(&self) -> PageId {
+//! #         "home".into()
+//! #     }
+//! # }
+//! # struct AboutPage;
+//! # impl PageModel for AboutPage {
+//! #     fn id(&self) -> PageId {
+//! #         "about".into()
+//! #     }
+//! # }
+//! # let output = tempfile::tempdir().unwrap();
+//! let archive_cache = ArchiveCache::default()
+//!     .load_css::<ResourceArchive>()
+//!     .unwrap()
+//!     .load_javascript::<ResourceArchive>()
+//!     .unwrap()
+//!     .seal();
+//! let written = StaticSite::new(archive_cache)
+//!     .with_page("/", HomePage)
+//!     .with_page("/about", AboutPage)
+//!     .export(output.path())
+//!     .unwrap();
+//! assert!(output.path().join("index.html").is_file());
+//! assert!(output.path().join("about/index.html").is_file());
+//! assert!(written.iter().any(|f| f.starts_with(output.path().join("resources/components"))));
+//! ```
+//!
+//! See the `static-site` example for a binary that exports a site into the
+//! directory given on the command line.
+
+use {
+    crate::{
+        archive::ArchiveCache,
+        page::{LocalizedPageModel, Page, PageModel},
+        router::Router,
+        Error,
+        LanguageTag,
+    },
+    std::{
+        fs,
+        path::{Component, Path, PathBuf},
+        sync::Arc,
+    },
+};
+
+type Render = Box<dyn FnOnce(Arc<ArchiveCache>) -> Result<String, Error>>;
+
+/// A page of a [StaticSite] together with its route.
+struct StaticPage {
+    route: String,
+    render: Render,
+}
+
+/// A set of pages that are rendered and written to disk together with the
+/// resources of an [ArchiveCache]. See the [module documentation](self) for
+/// an example.
+pub struct StaticSite {
+    archive_cache: Arc<ArchiveCache>,
+    pages: Vec<StaticPage>,
+    files: Vec<(PathBuf, Vec<u8>)>,
+}
+
+impl StaticSite {
+    /// Creates a new instance. The [ArchiveCache] is attached to every page
+    /// and all of its [web files](ArchiveCache::web_files) are exported.
+    pub fn new(archive_cache: Arc<ArchiveCache>) -> Self {
+        Self {
+            archive_cache,
+            pages: vec![],
+            files: vec![],
+        }
+    }
+
+    /// Adds the given page at the given route. Routes without an extension
+    /// are written as `index.html` of the corresponding directory, so `/`
+    /// becomes `index.html` and `/about` becomes `about/index.html`. Routes
+    /// with an extension, like `/404.html`, are written as they are.
+    pub fn with_page<P: PageModel + 'static>(
+        mut self,
+        route: impl Into<String>,
+        model: P,
+    ) -> Self {
+        self.pages.push(StaticPage {
+            route: route.into(),
+            render: Box::new(move |archive_cache| {
+                Page::from(model)
+                    .with_archive_cache(archive_cache)
+                    .main()?
+                    .render()
+            }),
+        });
+        self
+    }
+
+    /// Adds the page in all of its [languages](PageModel::languages), see
+    /// [Page::localized]. The route of every language is created by the given
+    /// function.
+    pub fn with_localized_page<P: LocalizedPageModel + 'static>(
+        mut self,
+        model: &P,
+        route: impl Fn(&LanguageTag) -> String,
+    ) -> Self {
+        for language in model.languages() {
+            self = self.with_page(route(&language), model.localize(&language));
+        }
+        self
+    }
+
+    /// Adds the pages of the given [Router] at the given paths. Use
+    /// [Router::static_paths] to export all routes without parameters, and
+    /// [Route::path](crate::router::Route::path) to create the paths of the
+    /// routes with parameters.
+    ///
+    /// The pages are created immediately and use the
+    /// [ArchiveCache] of the router.
+    pub fn with_router(
+        mut self,
+        router: &Router,
+        paths: impl IntoIterator<Item = String>,
+    ) -> Self {
+        for path in paths {
+            let page = router.page(&path);
+            self.pages.push(StaticPage {
+                route: path,
+                render: Box::new(move |_| page?.render()),
+            });
+        }
+        self
+    }
+
+    /// Adds a file with the given content at the given URL, for example the
+    /// `/sitemap.xml` created by a [Sitemap](crate::seo::Sitemap) or the
+    /// `/robots.txt` created by a [RobotsTxt](crate::seo::RobotsTxt).
+    pub fn with_file(
+        mut self,
+        url: impl Into<PathBuf>,
+        content: impl Into<Vec<u8>>,
+    ) -> Self {
+        self.files.push((url.into(), content.into()));
+        self
+    }
+
+    /// Renders all pages and writes them together with the resources to the
+    /// given directory. Existing files are overwritten. Returns the paths of
+    /// all written files.
+    ///
+    /// Fails on the first page that cannot be rendered, or if a file cannot
+    /// be written.
+    pub fn export(
+        self,
+        output: impl AsRef<Path>,
+    ) -> anyhow::Result<Vec<PathBuf>> {
+        let output = output.as_ref();
+        let mut written = vec![];
+        for page in self.pages {
+            let file = output.join(Self::page_path(&page.route)?);
+            log::debug!(
+                "Rendering route \"{}\" to {}",
+                page.route,
+                file.display()
+            );
+            let html = (page.render)(Arc::clone(&self.archive_cache))?;
+            Self::write(&file, html.as_bytes())?;
+            written.push(file);
+        }
+        let files =
+            self.archive_cache.web_files().into_iter().chain(self.files);
+        for (url, content) in files {
+            let file = output.join(Self::relative(&url)?);
+            log::debug!("Writing {} to {}", url.display(), file.display());
+            Self::write(&file, &content)?;
+            written.push(file);
+        }
+        Ok(written)
+    }
+
+    /// Creates the path of the HTML file of the given route, relative to the
+    /// output directory.
+    fn page_path(route: &str) -> anyhow::Result<PathBuf> {
+        let path = Self::relative(Path::new(route))?;
+        match path.extension() {
+            Some(_) => Ok(path),
+            None => Ok(path.join("index.html")),
+        }
+    }
+
+    /// Removes the root from the given URL. URLs that point outside of the
+    /// output directory are rejected.
+    fn relative(url: &Path) -> anyhow::Result<PathBuf> {
+        let mut path = PathBuf::new();
+        for component in url.components() {
+            match component {
+                Component::Normal(c) => path.push(c),
+                Component::RootDir | Component::CurDir => (),
+                _ => {
+                    return Err(anyhow::anyhow!(
+                        "{} can not be exported, it points outside of the output directory",
+                        url.display()
+                    ))
+                }
+            }
+        }
+        Ok(path)
+    }
+
+    fn write(file: &Path, content: &[u8]) -> anyhow::Result<()> {
+        if let Some(parent) = file.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(file, content)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            lewp_archive,
+            page::PageId,
+            resources::{ImageFormat, WebInterface},
+            seo::RobotsTxt,
+        },
+    };
+
+    lewp_archive!(ResourceArchive, "testfiles");
+    impl WebInterface for ResourceArchive {}
+
+    struct SitemapPage;
+
+    impl PageModel for SitemapPage {
+        fn id(&self) -> PageId {
+            "sitemap".into()
+        }
+    }
+
+    #[test]
+    fn create_page_paths() {
+        assert_eq!(
+            StaticSite::page_path("/").unwrap(),
+            PathBuf::from("index.html")
+        );
+        assert_eq!(
+            StaticSite::page_path("/blog/first/").unwrap(),
+            PathBuf::from("blog/first/index.html")
+        );
+        assert_eq!(
+            StaticSite::page_path("/404.html").unwrap(),
+            PathBuf::from("404.html")
+        );
+        assert!(StaticSite::page_path("/../etc").is_err());
+    }
+
+    #[test]
+    fn export_pages_and_resources() {
+        let archive_cache = ArchiveCache::default()
+            .load_css::<ResourceArchive>()
+            .unwrap()
+            .load_javascript::<ResourceArchive>()
+            .unwrap()
+            .load_images::<ResourceArchive>(&[128], &[ImageFormat::WebP])
+            .unwrap()
+            .seal();
+        let router = Router::default()
+            .with_archive_cache(Arc::clone(&archive_cache))
+            .with_route("/", |_| Ok(SitemapPage));
+        let output = tempfile::tempdir().unwrap();
+        let written = StaticSite::new(Arc::clone(&archive_cache))
+            .with_page("/sitemap", SitemapPage)
+            .with_router(&router, router.static_paths())
+            .with_file("/robots.txt", RobotsTxt::default().to_string())
+            .export(output.path())
+            .unwrap();
+
+        let html = fs::read_to_string(output.path().join("sitemap/index.html"))
+            .unwrap();
+        assert!(html.contains("<title>"));
+        for (url, content) in archive_cache.web_files() {
+            let file = output.path().join(StaticSite::relative(&url).unwrap());
+            assert!(written.contains(&file));
+            assert_eq!(fs::read(file).unwrap(), content);
+        }
+        assert!(output.path().join("robots.txt").is_file());
+        let images = output
+            .path()
+            .join("resources/components/hello-world/images");
+        assert!(images.join("rust-logo-512x512-blk.png").is_file());
+        assert!(images.join("rust-logo-512x512-blk.128w.webp").is_file());
+    }
+}
@@ -1,7 +1,14 @@
 #![deny(missing_docs)]
 #![doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/README.md"))]
 
-pub use {charsets::Charset, langtag::LanguageTag, rust_embed};
+pub use {
+    anyhow,
+    charsets::Charset,
+    error::Error,
+    langtag::LanguageTag,
+    rust_embed,
+    serde_json,
+};
 
 /// Re-export of the [lewp_html] crate.
 pub mod html {
@@ -10,7 +17,11 @@ pub mod html {
 
 pub mod archive;
 pub mod component;
+mod error;
+pub mod export;
 pub mod page;
 pub mod resources;
+pub mod router;
+pub mod seo;
 //pub mod storage;
 pub mod view;
@@ -7,37 +7,75 @@ use {
         component::ComponentDetails,
         html::{
             api::{
+                alternate,
                 body,
                 charset,
+                custom,
                 description,
                 document,
                 head,
+                link,
                 script,
                 style,
                 text,
                 title,
                 viewport,
             },
+            Document,
+            DocumentExt,
             Node,
             NodeExt,
             NodeList,
             Script,
         },
-        resources::{Css, Js, Resource, ResourceLevel, ResourceType},
-        view::PageView,
+        resources::{
+            css::{remove_unused_rules, ProcessedComponent},
+            Css,
+            Entireness,
+            Js,
+            Localization,
+            Resource,
+            ResourceLevel,
+            ResourceType,
+        },
+        seo::{remove_duplicates, Metadata},
+        view::{
+            ComponentView,
+            PageView,
+            CLIENT_STATE_ATTRIBUTE,
+            COMPONENT_ID_ATTRIBUTE,
+            INSTANCE_ID_ATTRIBUTE,
+        },
         Charset,
+        Error,
         LanguageTag,
     },
-    html5ever::serialize,
-    markup5ever_rcdom::SerializableHandle,
+    futures_util::{stream, Stream},
     state::*,
-    std::sync::Arc,
+    std::{
+        collections::{HashSet, VecDeque},
+        future::Future,
+        io::Write,
+        rc::Rc,
+        sync::Arc,
+    },
 };
 
 #[cfg(not(debug_assertions))]
 use minify_js::{minify, TopLevelMode};
 
-mod state;
+mod css_delivery;
+mod render_chunks;
+pub(crate) mod state;
+
+pub use {css_delivery::CssDelivery, render_chunks::RenderChunks};
+
+/// The state of the stream returned by [Page::render_stream].
+enum Streaming<P: PageModel> {
+    Running(Page<P, PagePreparing>),
+    Loading(Page<P, PageFinished>),
+    Done(VecDeque<Result<String, Error>>),
+}
 
 /// JavaScript scripts required to run `lewp`.
 #[derive(rust_embed::RustEmbed)]
@@ -56,7 +94,35 @@ where
     /// Implements the main behavior of the page. This method is mainly used for
     /// creating necessary components and adding them to the [PageView].
     /// The added components are automatically executed when added to the [PageView].
-    fn main(&self, view: &mut PageView);
+    ///
+    /// Return an error if the page cannot be rendered at all, for example
+    /// because the requested content does not exist. Failing components do not
+    /// need to be handled here, they are replaced by their
+    /// [fallback view](crate::component::ComponentModel::fallback_view).
+    ///
+    /// Either this method or [main_async](Self::main_async) needs to be
+    /// implemented. Does nothing by default.
+    fn main(&self, _view: &mut PageView) -> anyhow::Result<()> {
+        Ok(())
+    }
+    /// The asynchronous variant of [main](Self::main) that is called by
+    /// [Page::main_async]. Components that are added using
+    /// [PageView::push_async] are loaded concurrently after this method has
+    /// finished.
+    ///
+    /// The returned future needs to be [Send], so the page can be run on a
+    /// multi-threaded runtime.
+    ///
+    /// Calls [main](Self::main) by default.
+    fn main_async(
+        &self,
+        view: &mut PageView,
+    ) -> impl Future<Output = anyhow::Result<()>> + Send
+    where
+        Self: Sync,
+    {
+        async move { self.main(view) }
+    }
     /// Returns a reference to the page ID.
     fn id(&self) -> PageId;
     /// Title of the page. Will land in the `title` tag.
@@ -71,6 +137,51 @@ where
     fn language(&self) -> LanguageTag {
         LanguageTag::parse("en-US").unwrap()
     }
+    /// The languages the page is available in. If not empty, a
+    /// `<link rel="alternate" hreflang>` element is added to the `<head>` for
+    /// every language that has a [language_url](Self::language_url),
+    /// including the [language](Self::language) of the page itself. The first
+    /// language is additionally referenced as `x-default`.
+    ///
+    /// Use [Page::localized] to create the page for each of these languages.
+    ///
+    /// Empty by default.
+    fn languages(&self) -> Vec<LanguageTag<'_>> {
+        vec![]
+    }
+    /// The absolute URL of the page in the given language, for example
+    /// `https://example.com/de-at/`. Used for the alternate links of
+    /// [languages](Self::languages).
+    ///
+    /// Returns `None` by default.
+    fn language_url(&self, _language: &LanguageTag) -> Option<String> {
+        None
+    }
+    /// The absolute canonical URL of the page, for example
+    /// `https://example.com/about`. Pages without a canonical URL are not
+    /// added to the [Sitemap](crate::seo::Sitemap).
+    ///
+    /// Returns `None` by default.
+    fn canonical_url(&self) -> Option<String> {
+        None
+    }
+    /// The date of the last modification of the page in
+    /// [W3C Datetime](https://www.w3.org/TR/NOTE-datetime) format, for example
+    /// `2023-04-01` or `2023-04-01T12:30:00+00:00`. Added as `<lastmod>` to
+    /// the [Sitemap](crate::seo::Sitemap).
+    ///
+    /// Returns `None` by default.
+    fn last_modified(&self) -> Option<String> {
+        None
+    }
+    /// Additional languages that are used to look up localised messages if a
+    /// message is not available in the [language](Self::language) of the
+    /// page, see [Localization].
+    ///
+    /// Empty by default.
+    fn fallback_languages(&self) -> Vec<LanguageTag<'_>> {
+        vec![]
+    }
     /// Contains the charset of the HTML page. Will be added to the `head` of the page.
     fn charset(&self) -> Charset {
         Charset::Utf8
@@ -81,9 +192,72 @@ where
         Some(viewport())
     }
     /// Prepends the returned [NodeList] to the `<head>` of the page.
+    ///
+    /// Nodes that provide the same metadata as the generated tags, for
+    /// example a `<title>`, `<link rel="canonical">` or
+    /// `<meta property="og:title">`, replace the generated ones. `<script>`,
+    /// `<style>` and stylesheet `<link>` elements get the nonce of
+    /// [Page::with_csp_nonce].
     fn head(&self) -> NodeList {
         vec![]
     }
+    /// The structured [Metadata] of the page, like robots directives, Open
+    /// Graph and Twitter card properties or JSON-LD structured data. It is
+    /// rendered into the `<head>` together with the `<link rel="canonical">`
+    /// of the [canonical_url](Self::canonical_url).
+    ///
+    /// Empty by default.
+    fn metadata(&self) -> Metadata {
+        Metadata::default()
+    }
+    /// Defines how the `CSS` of the page and its components is delivered.
+    ///
+    /// Defaults to [CssDelivery::Inline].
+    fn css_delivery(&self) -> CssDelivery {
+        CssDelivery::default()
+    }
+    /// If enabled, style rules that do not apply to any element of the
+    /// rendered page are removed from the inlined `CSS`. `@media`, `@supports`
+    /// and `@keyframes` rules are kept as long as they are still used.
+    ///
+    /// The page is matched against the selectors on every call to
+    /// [Page::render], so this adds processing time in favor of a smaller
+    /// document. The non render critical stylesheets that are linked when
+    /// using [CssDelivery::RenderCriticalInline] are not affected.
+    ///
+    /// Defaults to `false`.
+    fn remove_unused_css(&self) -> bool {
+        false
+    }
+}
+
+/// Returns the `hreflang` and URL of all [languages](PageModel::languages) of
+/// the given page, followed by `x-default`.
+pub(crate) fn alternate_urls<P: PageModel>(model: &P) -> Vec<(String, String)> {
+    let languages = model.languages();
+    let mut urls = vec![];
+    for language in &languages {
+        match model.language_url(language) {
+            Some(url) => urls.push((language.to_string(), url)),
+            None => log::warn!(
+                "Page \"{}\" does not provide a URL for language \"{language}\"",
+                model.id()
+            ),
+        }
+    }
+    if let Some(url) = languages.first().and_then(|l| model.language_url(l)) {
+        urls.push((String::from("x-default"), url));
+    }
+    urls
+}
+
+/// A [PageModel] that can be rendered in all of its
+/// [languages](PageModel::languages).
+pub trait LocalizedPageModel: PageModel {
+    /// Creates the model of the page in the given language. The
+    /// [language](PageModel::language) of the returned model is expected to
+    /// be the given one.
+    fn localize(&self, language: &LanguageTag) -> Self;
 }
 
 /// A wrapper around the implemented [PageModel] trait. Contains all necessary code
@@ -93,6 +267,7 @@ pub struct Page<P: PageModel, E: ExecutionState> {
     model: P,
     view: PageView,
     archive_cache: Option<Arc<ArchiveCache>>,
+    csp_nonce: Option<String>,
     execution_state: std::marker::PhantomData<E>,
 }
 
@@ -106,56 +281,327 @@ impl<P: PageModel> Page<P, PagePreparing> {
             model: self.model,
             view: self.view,
             archive_cache: Some(archive_cache),
+            csp_nonce: self.csp_nonce,
             execution_state: std::marker::PhantomData,
         }
     }
+
+    /// Creates a page for every [language](PageModel::languages) of the given
+    /// model, using [LocalizedPageModel::localize]. The pages are returned in
+    /// the order of the languages.
+    pub fn localized(model: &P) -> Vec<Page<P, PagePreparing>>
+    where
+        P: LocalizedPageModel,
+    {
+        model
+            .languages()
+            .iter()
+            .map(|l| Page::from(model.localize(l)))
+            .collect()
+    }
+
+    /// Attaches the given nonce to the `<style>`, `<script>` and stylesheet
+    /// `<link>` elements that are generated by `lewp` and to the ones in the
+    /// [head](PageModel::head) of the page and the
+    /// [head](crate::component::ComponentModel::head) of the components, so
+    /// the page can be delivered with a strict `Content-Security-Policy`.
+    /// Elements in the views of the components do not get the nonce and are
+    /// blocked by the policy.
+    /// Send the header value returned by
+    /// [content_security_policy](Page::content_security_policy) with the
+    /// response.
+    ///
+    /// The nonce needs to be a fresh, unguessable and base64 encoded random
+    /// value for every response. A nonce containing other characters is
+    /// ignored and an error is logged.
+    pub fn with_csp_nonce(
+        self,
+        nonce: impl Into<String>,
+    ) -> Page<P, PagePreparing> {
+        let nonce = nonce.into();
+        let is_valid = !nonce.is_empty()
+            && nonce.chars().all(|c| {
+                c.is_ascii_alphanumeric()
+                    || matches!(c, '+' | '/' | '-' | '_' | '=')
+            });
+        if !is_valid {
+            log::error!("Ignoring invalid CSP nonce \"{nonce}\"");
+        }
+        Page {
+            csp_nonce: is_valid.then_some(nonce),
+            ..self
+        }
+    }
+}
+
+impl<P: PageModel, E: ExecutionState> Page<P, E> {
+    /// Returns the model of the page.
+    pub(crate) fn model(&self) -> &P {
+        &self.model
+    }
+
+    /// Returns the value of the `Content-Security-Policy` header that matches
+    /// the page, if a nonce has been set using
+    /// [with_csp_nonce](Page::with_csp_nonce).
+    ///
+    /// Scripts and styles are only allowed if they carry the nonce. Scripts
+    /// that are loaded by these scripts, like the component modules that are
+    /// imported by `lewp`, are allowed using `'strict-dynamic'`. Inline
+    /// `style` attributes are not allowed.
+    pub fn content_security_policy(&self) -> Option<String> {
+        self.csp_nonce.as_ref().map(|nonce| {
+            format!(
+                "script-src 'nonce-{nonce}' 'strict-dynamic'; style-src 'nonce-{nonce}'; object-src 'none'; base-uri 'none'"
+            )
+        })
+    }
 }
 
 impl<P: PageModel> Page<P, PagePreparing> {
     /// This is your main entry point to processing your implemented page.
-    pub fn main(mut self) -> Page<P, PageFinished> {
-        self.model.main(&mut self.view);
+    ///
+    /// Returns [Error::Page] if the [PageModel::main] method failed.
+    pub fn main(mut self) -> Result<Page<P, PageFinished>, Error> {
+        self.prepare_view();
+        let result = self.model.main(&mut self.view);
+        if self.view.has_pending() {
+            log::warn!(
+                "Components of page \"{}\" have been added using PageView::push_async, use Page::main_async to load them",
+                self.model.id()
+            );
+        }
+        self.finish(result)
+    }
 
-        Page {
+    /// The asynchronous variant of [Page::main]. Awaits
+    /// [PageModel::main_async] and loads all components that have been added
+    /// using [PageView::push_async] concurrently.
+    ///
+    /// The returned future can be run on any runtime. It is [Send] if the
+    /// [PageModel] is [Send] and [Sync], so it can be awaited in the handlers
+    /// of multi-threaded web servers.
+    pub async fn main_async(self) -> Result<Page<P, PageFinished>, Error>
+    where
+        P: Sync,
+    {
+        let mut page = self.run_async().await?;
+        page.view.load().await;
+        Ok(page)
+    }
+
+    /// Runs the page using [main_async](Self::main_async) and renders it as a
+    /// [Stream] of chunks, see [RenderChunks].
+    ///
+    /// The first chunk with the `<head>` is created as soon as
+    /// [PageModel::main_async] has finished. The components that have been
+    /// added using [PageView::push_async] are loaded afterwards, so the
+    /// browser can fetch the resources of the page in the meantime. The
+    /// [head](crate::component::ComponentModel::head) of these components is
+    /// taken before they are loaded.
+    ///
+    /// If [PageModel::remove_unused_css] is enabled, the whole page is loaded
+    /// before the first chunk is created.
+    ///
+    /// The stream is [Send] if the [PageModel] is [Send] and [Sync], so it
+    /// can be used as the response body of multi-threaded web servers.
+    pub fn render_stream(
+        self,
+    ) -> impl Stream<Item = Result<String, Error>> + Send
+    where
+        P: Send + Sync,
+    {
+        stream::unfold(Streaming::Running(self), |state| async move {
+            let mut chunks = match state {
+                Streaming::Running(page) => match page.run_async().await {
+                    Ok(page) if !page.model.remove_unused_css() => {
+                        let head = page.render_head();
+                        return Some((head, Streaming::Loading(page)));
+                    }
+                    // the whole document is required to remove unused CSS
+                    Ok(mut page) => {
+                        page.view.load().await;
+                        page.render_chunks().collect::<VecDeque<_>>()
+                    }
+                    Err(e) => VecDeque::from([Err(e)]),
+                },
+                Streaming::Loading(mut page) => {
+                    page.view.load().await;
+                    page.render_body().collect::<VecDeque<_>>()
+                }
+                Streaming::Done(chunks) => chunks,
+            };
+            let chunk = chunks.pop_front()?;
+            Some((chunk, Streaming::Done(chunks)))
+        })
+    }
+
+    /// Awaits [PageModel::main_async] without loading the components that
+    /// have been added using [PageView::push_async].
+    async fn run_async(mut self) -> Result<Page<P, PageFinished>, Error>
+    where
+        P: Sync,
+    {
+        self.prepare_view();
+        let result = self.model.main_async(&mut self.view).await;
+        self.finish(result)
+    }
+
+    /// Passes the languages and the archive cache to the view, so
+    /// [PageView::localization] is available in [PageModel::main].
+    fn prepare_view(&mut self) {
+        let localization = self.model.fallback_languages().iter().fold(
+            Localization::new(None, &self.model.language()),
+            |l, fallback| l.with_fallback(fallback),
+        );
+        self.view
+            .set_localization(self.archive_cache.clone(), localization);
+    }
+
+    fn finish(
+        self,
+        result: anyhow::Result<()>,
+    ) -> Result<Page<P, PageFinished>, Error> {
+        if let Err(source) = result {
+            return Err(Error::Page {
+                id: self.model.id(),
+                source,
+            });
+        }
+
+        Ok(Page {
             model: self.model,
             view: self.view,
             archive_cache: self.archive_cache,
+            csp_nonce: self.csp_nonce,
             execution_state: std::marker::PhantomData,
-        }
+        })
     }
 }
 
 impl<P: PageModel> Page<P, PageFinished> {
+    /// Returns the errors of all components that failed and have been
+    /// replaced by their
+    /// [fallback view](crate::component::ComponentModel::fallback_view).
+    pub fn errors(&self) -> impl Iterator<Item = &Error> {
+        self.view.errors()
+    }
+
     /// Renders the page to valid `HTML5` code.
-    pub fn render(self) -> String {
-        log::debug!(
-            "Full dependency list on rendering:\n{}",
-            self.view.dependency_list()
+    pub fn render(self) -> Result<String, Error> {
+        self.render_chunks().collect()
+    }
+
+    /// Renders the page to the given writer. The writer is flushed after the
+    /// `<head>` and after every child of the `<body>` has been written, see
+    /// [RenderChunks] for details.
+    ///
+    /// The `<head>` is written before the `<body>` is assembled, unless
+    /// [PageModel::remove_unused_css] is enabled.
+    pub fn render_to<W: Write>(self, mut writer: W) -> Result<(), Error> {
+        let mut write = |chunk: Result<String, Error>| -> Result<(), Error> {
+            writer.write_all(chunk?.as_bytes())?;
+            writer.flush()?;
+            Ok(())
+        };
+        if self.model.remove_unused_css() {
+            return self.render_chunks().try_for_each(write);
+        }
+        write(self.render_head())?;
+        self.render_body().try_for_each(write)
+    }
+
+    /// Renders the page in chunks, starting with the complete `<head>`.
+    ///
+    /// The whole document is assembled before the first chunk is returned.
+    /// Use [render_to](Self::render_to) or [Page::render_stream] to send the
+    /// `<head>` earlier.
+    pub fn render_chunks(self) -> RenderChunks {
+        RenderChunks::new(self.assemble_document())
+    }
+
+    /// Renders the first chunk of the page, containing everything up to the
+    /// opening `<body>` tag.
+    fn render_head(&self) -> Result<String, Error> {
+        let css = self.get_css();
+        let document = document(
+            self.model.language(),
+            head(self.assemble_head(&css, self.inline_style(&css))),
+            body(vec![]),
         );
+        RenderChunks::new(document)
+            .next()
+            .unwrap_or_else(|| Ok(String::new()))
+    }
+
+    /// Renders the chunks following the [head](Self::render_head).
+    fn render_body(&self) -> impl Iterator<Item = Result<String, Error>> {
+        let document =
+            document(self.model.language(), head(vec![]), self.assemble_body());
+        RenderChunks::new(document).skip(1)
+    }
 
+    /// Assembles the final [Document] of the page.
+    fn assemble_document(self) -> Document {
+        let css = self.get_css();
+        let inline_style = self.inline_style(&css);
         let document = document(
             self.model.language(),
-            head(self.assemble_head()),
-            body(self.view.body()),
+            head(self.assemble_head(&css, inline_style.clone())),
+            self.assemble_body(),
         );
 
-        let mut bytes = vec![];
-        let document: SerializableHandle = document.document.into();
-        serialize(&mut bytes, &document, Default::default()).unwrap();
-        String::from_utf8(bytes).unwrap()
+        if self.model.remove_unused_css() {
+            if let Some(s) = inline_style {
+                self.remove_unused_inline_css(&document, &css, s);
+            }
+        }
+
+        document
+    }
+
+    /// Creates the inline `<style>` element of the given [Css] resources, if
+    /// there is any `CSS` to inline.
+    fn inline_style(&self, css: &[&Resource<Css>]) -> Option<Node> {
+        let inline_css = css.iter().fold(String::new(), |mut acc, c| {
+            acc += &c.content.get(&self.entireness());
+            acc
+        });
+        match inline_css.is_empty() {
+            true => None,
+            false => Some(style(text(&inline_css))),
+        }
+    }
+
+    /// Creates the `<body>` containing the views of all components.
+    fn assemble_body(&self) -> Node {
+        let body = body(self.view.body());
+        Self::assign_instance_ids(&body);
+        self.embed_client_state(&body);
+        body
     }
 
     /// This method collects all nodes that belong to the head node.
-    fn assemble_head(&self) -> NodeList {
+    fn assemble_head(
+        &self,
+        css: &[&Resource<Css>],
+        inline_style: Option<Node>,
+    ) -> NodeList {
+        log::debug!(
+            "Full dependency list on rendering:\n{}",
+            self.view.dependency_list()
+        );
         let mut head = NodeList::new();
 
-        let mut prelude = vec![
-            charset(&self.model.charset()),
-            title(&self.model.title()),
-            description(&self.model.description()),
-        ];
-        let mut page_head = self.model.head();
-        prelude.append(&mut page_head);
+        let page_head = self.model.head();
+        let mut prelude = remove_duplicates(
+            &page_head,
+            vec![
+                charset(&self.model.charset()),
+                title(&self.model.title()),
+                description(&self.model.description()),
+            ],
+        );
+        prelude.append(&mut page_head.clone());
 
         // add viewport if available
         if let Some(v) = self.model.viewport() {
@@ -163,24 +609,23 @@ impl<P: PageModel> Page<P, PageFinished> {
         }
 
         head.append(&mut prelude);
+        let mut metadata = self.alternate_links();
+        metadata.append(&mut self.model.metadata().to_nodes(&self.model));
+        head.append(&mut remove_duplicates(&page_head, metadata));
 
-        let mut inline_css = match self.get_page_css() {
-            Some(css) => format!("{css}"),
-            None => {
-                log::debug!("No page CSS has been found!");
-                "".into()
-            }
-        };
-        if let Some(css) = self.get_component_css() {
-            inline_css += &css;
-        };
-        if !inline_css.is_empty() {
+        // everything below is generated by lewp or the components
+        let generated = head.len();
+
+        if let Some(s) = inline_style {
             log::debug!("Adding inline <style> element with page and all components to <head>");
-            head.push(style(text(&inline_css)));
+            head.push(s);
+        }
+        if self.model.css_delivery() == CssDelivery::RenderCriticalInline {
+            head.append(&mut Self::non_render_critical_css_links(css));
         }
 
         for c in self.get_component_js() {
-            let web_path = c.web_root.join(&c.path);
+            let web_path = c.url();
             let web_path = match web_path.to_str() {
                 Some(r) => r,
                 None => {
@@ -194,6 +639,7 @@ impl<P: PageModel> Page<P, PageFinished> {
             let script = script(Script::Src(web_path)).attrs(vec![
                 ("type", "module"),
                 ("async", "async"),
+                ("integrity", &c.integrity),
                 ("data-lewp-id", &(*c).details().component_id),
                 ("data-lewp-type", "component"),
             ]);
@@ -228,9 +674,183 @@ impl<P: PageModel> Page<P, PageFinished> {
 
         head.append(&mut self.view.head());
 
+        self.attach_csp_nonce(&page_head);
+        self.attach_csp_nonce(&head[generated..]);
+
         head
     }
 
+    /// Creates the `<link rel="alternate" hreflang>` elements of all
+    /// [languages](PageModel::languages) of the page.
+    fn alternate_links(&self) -> NodeList {
+        alternate_urls(&self.model)
+            .into_iter()
+            .map(|(hreflang, url)| alternate(hreflang, url))
+            .collect()
+    }
+
+    /// Attaches the CSP nonce, if set, to the given nodes and their
+    /// descendants that are restricted by the `Content-Security-Policy`.
+    fn attach_csp_nonce(&self, nodes: &[Node]) {
+        let Some(nonce) = &self.csp_nonce else {
+            return;
+        };
+        for node in nodes {
+            let is_restricted = match node.tag_name().as_deref() {
+                Some("style" | "script") => true,
+                Some("link") => {
+                    node.attribute_eq("rel", "stylesheet")
+                        || (node.attribute_eq("rel", "preload")
+                            && node.attribute_eq("as", "style"))
+                }
+                _ => false,
+            };
+            if is_restricted {
+                node.remove_attribute("nonce");
+                node.borrow_attr("nonce", nonce);
+            }
+            self.attach_csp_nonce(&node.children().borrow());
+        }
+    }
+
+    /// Attaches an [InstanceId](crate::component::InstanceId) to all
+    /// component views in the given `<body>` that do not have one yet, for
+    /// example nested components.
+    fn assign_instance_ids(body: &Node) {
+        let views = body
+            .query_selector_all(&format!(
+                "[data-lewp-type=\"component\"][{COMPONENT_ID_ATTRIBUTE}]"
+            ))
+            .unwrap_or_default();
+        let mut used = views
+            .iter()
+            .filter_map(|v| v.attribute_value(INSTANCE_ID_ATTRIBUTE))
+            .collect::<HashSet<_>>();
+        for view in views {
+            if view.attribute_value(INSTANCE_ID_ATTRIBUTE).is_some() {
+                continue;
+            }
+            let component_id = view
+                .attribute_value(COMPONENT_ID_ATTRIBUTE)
+                .unwrap_or_default();
+            let instance_id = (1..)
+                .map(|n| format!("{component_id}-{n}"))
+                .find(|id| !used.contains(id))
+                .unwrap_or_default();
+            view.attach_instance_id(&instance_id);
+            used.insert(instance_id);
+        }
+    }
+
+    /// Moves the client state of all component views to a single
+    /// `<script type="application/json">` element at the end of the given
+    /// `<body>`. The state is keyed by the `data-lewp-instance` attribute of
+    /// the component view.
+    fn embed_client_state(&self, body: &Node) {
+        let mut states = serde_json::Map::new();
+        for view in body
+            .query_selector_all(&format!("[{CLIENT_STATE_ATTRIBUTE}]"))
+            .unwrap_or_default()
+        {
+            let state = view
+                .attribute_value(CLIENT_STATE_ATTRIBUTE)
+                .map(|s| serde_json::from_str(&s));
+            view.remove_attribute(CLIENT_STATE_ATTRIBUTE);
+            let instance_id = match view.attribute_value(INSTANCE_ID_ATTRIBUTE)
+            {
+                Some(i) => i,
+                None => {
+                    log::error!(
+                        "Component view with client state has no instance id"
+                    );
+                    continue;
+                }
+            };
+            match state {
+                Some(Ok(state)) => {
+                    states.insert(instance_id, state);
+                }
+                Some(Err(e)) => {
+                    log::error!("Could not parse client state of view: {e}")
+                }
+                None => (),
+            }
+        }
+        if states.is_empty() {
+            return;
+        }
+        // prevents closing the <script> element from within the JSON
+        let states = serde_json::Value::Object(states)
+            .to_string()
+            .replace('<', "\\u003c")
+            .replace('>', "\\u003e")
+            .replace('&', "\\u0026");
+        let script = script(Script::Inline(&states)).attrs(vec![
+            ("type", "application/json"),
+            ("data-lewp-type", "state"),
+        ]);
+        self.attach_csp_nonce(std::slice::from_ref(&script));
+        body.append_child(script);
+    }
+
+    /// The part of the [Css] resources that is inlined into the `<head>`.
+    fn entireness(&self) -> Entireness {
+        match self.model.css_delivery() {
+            CssDelivery::Inline => Entireness::Full,
+            CssDelivery::RenderCriticalInline => Entireness::RenderCritical,
+        }
+    }
+
+    /// Replaces the content of the given inline `<style>` element by the rules
+    /// of the [Css] resources that are used in the document. The element is
+    /// removed if no rule is left.
+    fn remove_unused_inline_css(
+        &self,
+        document: &Document,
+        css: &[&Resource<Css>],
+        inline_style: Node,
+    ) {
+        let stylesheets = css
+            .iter()
+            .map(|c| c.content.stylesheet())
+            .collect::<anyhow::Result<Vec<_>>>();
+        let mut stylesheets = match stylesheets {
+            Ok(s) => s,
+            Err(e) => {
+                log::error!("Could not remove unused CSS: {e}");
+                return;
+            }
+        };
+        remove_unused_rules(&mut stylesheets, document);
+
+        let entireness = self.entireness();
+        let mut inline_css = String::new();
+        for stylesheet in stylesheets {
+            match ProcessedComponent::extract_stylesheet(
+                stylesheet,
+                &entireness,
+            ) {
+                Ok(s) => inline_css += &s.to_css_string(false),
+                Err(e) => {
+                    log::error!("Could not remove unused CSS: {e}");
+                    return;
+                }
+            }
+        }
+
+        if !inline_css.is_empty() {
+            inline_style.children().borrow_mut().clear();
+            inline_style.append_child(text(&inline_css));
+            return;
+        }
+        log::debug!("No CSS is used, removing inline <style> element");
+        if let Ok(Some(head)) = document.query_selector("head") {
+            head.children()
+                .borrow_mut()
+                .retain(|n| !Rc::ptr_eq(n, &inline_style));
+        }
+    }
+
     #[cfg(not(debug_assertions))]
     fn minify_javascript(js_utf8: Vec<u8>) -> anyhow::Result<Vec<u8>> {
         let mut result = vec![];
@@ -241,42 +861,74 @@ impl<P: PageModel> Page<P, PageFinished> {
         }
     }
 
-    fn get_page_css(&self) -> Option<Arc<String>> {
-        match self.archive_cache.as_ref() {
-            Some(a) => {
-                let details = ComponentDetails::new(
-                    self.model.id(),
-                    ResourceType::Css,
-                    ResourceLevel::Page,
-                );
-                a.query(&details)
-                    .map(|c: Arc<&Resource<Css>>| Arc::clone(&c.content.full))
-            }
-            None => None,
-        }
-    }
+    /// Collects the [Css] resources of the page and all components in the
+    /// dependency list. The page [Css] is always the first entry if available.
+    fn get_css(&self) -> Vec<&Resource<Css>> {
+        let archive_cache = match self.archive_cache.as_ref() {
+            Some(a) => a,
+            None => return vec![],
+        };
+        let mut collected_css = vec![];
 
-    fn get_component_css(&self) -> Option<String> {
-        let mut collected_css = String::new();
+        let details = ComponentDetails::new(
+            self.model.id(),
+            ResourceType::Css,
+            ResourceLevel::Page,
+        );
+        match archive_cache.query::<Css>(&details) {
+            Some(c) => collected_css.push(*c),
+            None => log::debug!("No page CSS has been found!"),
+        }
 
         for component_id in self.view.dependency_list().list() {
-            if let Some(a) = self.archive_cache.as_ref() {
-                let details = ComponentDetails::new(
-                    component_id.into(),
-                    ResourceType::Css,
-                    ResourceLevel::Component,
-                );
-                a.query(&details).map(|c: Arc<&Resource<Css>>| {
-                    log::debug!("Adding CSS for {:?}", details);
-                    collected_css += &(*c).content.full;
-                });
-            };
+            let details = ComponentDetails::new(
+                component_id.into(),
+                ResourceType::Css,
+                ResourceLevel::Component,
+            );
+            if let Some(c) = archive_cache.query::<Css>(&details) {
+                log::debug!("Adding CSS for {:?}", details);
+                collected_css.push(*c);
+            }
         }
 
-        if collected_css.is_empty() {
-            return None;
+        collected_css
+    }
+
+    /// Creates the `<link>` elements that asynchronously load the non render
+    /// critical parts of the given [Css] resources. `lewp.js` switches the
+    /// preloaded stylesheets on as soon as they have been loaded. A `<noscript>`
+    /// fallback is added for clients without JavaScript.
+    fn non_render_critical_css_links(css: &[&Resource<Css>]) -> NodeList {
+        let mut links = NodeList::new();
+        for c in css {
+            if c.content.non_render_critical.is_empty() {
+                continue;
+            }
+            let web_path =
+                c.url_of(Entireness::NonRenderCritical.resource_id());
+            let web_path = match web_path.to_str() {
+                Some(r) => r,
+                None => {
+                    log::error!(
+                        "Could not convert {} to str",
+                        web_path.display()
+                    );
+                    continue;
+                }
+            };
+            links.push(link("text/css", web_path).attrs(vec![
+                ("rel", "preload"),
+                ("as", "style"),
+                ("data-lewp-id", &c.details().component_id),
+                ("data-lewp-type", "css"),
+            ]));
+            links.push(custom(
+                "noscript",
+                vec![link("text/css", web_path).attr("rel", "stylesheet")],
+            ));
         }
-        Some(collected_css)
+        links
     }
 
     fn get_component_js(&self) -> Vec<Arc<&Resource<Js>>> {
@@ -326,7 +978,163 @@ impl<P: PageModel> From<P> for Page<P, PagePreparing> {
             model,
             view: PageView::default(),
             archive_cache: None,
+            csp_nonce: None,
             execution_state: std::marker::PhantomData,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            component::{Component, ComponentId, ComponentModel},
+            html::api::p,
+        },
+        futures_executor::block_on,
+        futures_util::StreamExt,
+        std::{
+            pin::pin,
+            sync::atomic::{AtomicBool, Ordering},
+        },
+    };
+
+    struct TestPage;
+
+    impl PageModel for TestPage {
+        fn id(&self) -> PageId {
+            "test".into()
+        }
+        fn head(&self) -> NodeList {
+            vec![
+                title("custom"),
+                charset(&Charset::Utf8),
+                script(Script::Src("/page.js")),
+            ]
+        }
+        fn main(&self, view: &mut PageView) -> anyhow::Result<()> {
+            view.push(&mut Component::from(State));
+            Ok(())
+        }
+    }
+
+    struct State;
+
+    impl ComponentModel for State {
+        type Message = ();
+        fn id(&self) -> ComponentId {
+            "state".into()
+        }
+        fn view(&self) -> anyhow::Result<Option<Node>> {
+            Ok(Some(p(vec![script(Script::Inline("user content"))])))
+        }
+        fn head(&self) -> NodeList {
+            vec![style(text("p{color:red}"))]
+        }
+        fn client_state(&self) -> Option<serde_json::Value> {
+            Some(serde_json::json!({ "text": "</script><!-- a & b >" }))
+        }
+    }
+
+    struct StreamedPage(Arc<AtomicBool>);
+
+    impl PageModel for StreamedPage {
+        fn id(&self) -> PageId {
+            "streamed".into()
+        }
+        async fn main_async(&self, view: &mut PageView) -> anyhow::Result<()> {
+            view.push(&mut Component::from(State));
+            view.push_async(&mut Component::from(Loading(Arc::clone(&self.0))));
+            Ok(())
+        }
+    }
+
+    struct Loading(Arc<AtomicBool>);
+
+    impl ComponentModel for Loading {
+        type Message = ();
+        fn id(&self) -> ComponentId {
+            "loading".into()
+        }
+        async fn main_async(&mut self) -> anyhow::Result<()> {
+            self.0.store(true, Ordering::SeqCst);
+            Ok(())
+        }
+        fn view(&self) -> anyhow::Result<Option<Node>> {
+            Ok(Some(p(vec![text("loaded")])))
+        }
+        fn head(&self) -> NodeList {
+            vec![style(text("p{color:red}"))]
+        }
+    }
+
+    #[test]
+    fn stream_head_before_loading() {
+        let loaded = Arc::new(AtomicBool::new(false));
+        let mut stream =
+            pin!(Page::from(StreamedPage(Arc::clone(&loaded))).render_stream());
+        let head = block_on(stream.next()).unwrap().unwrap();
+        assert!(head.ends_with("</head><body>"));
+        assert!(head.contains("<style>p{color:red}</style>"));
+        assert!(!loaded.load(Ordering::SeqCst));
+        let body = block_on(stream.collect::<Vec<_>>())
+            .into_iter()
+            .collect::<Result<String, _>>()
+            .unwrap();
+        assert!(loaded.load(Ordering::SeqCst));
+
+        let page = block_on(
+            Page::from(StreamedPage(Arc::new(AtomicBool::new(false))))
+                .main_async(),
+        )
+        .unwrap();
+        assert_eq!(head + &body, page.render().unwrap());
+    }
+
+    #[test]
+    fn render_to_writer() {
+        let mut html = vec![];
+        Page::from(TestPage)
+            .main()
+            .unwrap()
+            .render_to(&mut html)
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(html).unwrap(),
+            Page::from(TestPage).main().unwrap().render().unwrap()
+        );
+    }
+
+    #[test]
+    fn escape_client_state() {
+        let html = Page::from(TestPage).main().unwrap().render().unwrap();
+        assert!(html.contains(
+            r#"{"state-1":{"text":"\u003c/script\u003e\u003c!-- a \u0026 b \u003e"}}"#
+        ));
+    }
+
+    #[test]
+    fn replace_generated_title_and_charset() {
+        let html = Page::from(TestPage).main().unwrap().render().unwrap();
+        assert_eq!(html.matches("<title>").count(), 1);
+        assert!(html.contains("<title>custom</title>"));
+        assert_eq!(html.matches("charset=").count(), 1);
+    }
+
+    #[test]
+    fn attach_csp_nonce_to_generated_nodes() {
+        let html = Page::from(TestPage)
+            .with_csp_nonce("bm9uY2U=")
+            .main()
+            .unwrap()
+            .render()
+            .unwrap();
+        // the page head, lewp.js, the component head and the client state
+        assert_eq!(html.matches("nonce=\"bm9uY2U=\"").count(), 4);
+        assert!(html
+            .contains("<script src=\"/page.js\" nonce=\"bm9uY2U=\"></script>"));
+        assert!(html.contains("<script>user content</script>"));
+        assert!(html.contains("<style nonce=\"bm9uY2U=\">p{color:red}</style>"));
+    }
+}
@@ -0,0 +1,17 @@
+//! Defines how the CSS of a page is delivered to the client.
+
+/// Defines how the `CSS` of the page and its dependency list is delivered
+/// to the client.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CssDelivery {
+    /// The full `CSS` of the page and all its components is inlined into a
+    /// `<style>` element in the `<head>`.
+    #[default]
+    Inline,
+    /// Only the render critical parts of the `CSS` are inlined into the
+    /// `<head>`. The non render critical parts are added as `<link>` elements
+    /// pointing to the [non render critical](crate::resources::Entireness::NonRenderCritical)
+    /// stylesheets of the page and every component. These stylesheets are
+    /// loaded asynchronously, so they do not block the first render of the page.
+    RenderCriticalInline,
+}
@@ -0,0 +1,185 @@
+//! Chunked rendering of a page document.
+
+use {
+    crate::{
+        html::{Document, DocumentExt, Node, NodeExt},
+        Error,
+    },
+    html5ever::serialize::{
+        serialize,
+        HtmlSerializer,
+        SerializeOpts,
+        Serializer,
+        TraversalScope,
+    },
+    markup5ever_rcdom::{NodeData, SerializableHandle},
+    std::{collections::VecDeque, io::Write},
+};
+
+/// A part of the document that is serialized as one chunk.
+enum Chunk {
+    /// The doctype, the opening `<html>` tag, the complete `<head>` and the
+    /// opening `<body>` tag.
+    Start { html: Node, head: Node, body: Node },
+    /// A child of the `<body>`.
+    Body(Node),
+    /// The closing `</body>` and `</html>` tags.
+    End { html: Node, body: Node },
+}
+
+/// An [Iterator] over the serialized parts of a rendered page.
+///
+/// The first chunk contains everything up to the opening `<body>` tag,
+/// including the inlined `CSS` in the `<head>`. Every following chunk contains
+/// one child of the `<body>`, the last chunk closes the document. This enables
+/// sending the `<head>` to the client before the body has been serialized, so
+/// the browser can start fetching the resources of the page early.
+///
+/// The iterator can be converted into a stream for asynchronous response
+/// bodies, for example by using `futures::stream::iter`.
+pub struct RenderChunks {
+    chunks: VecDeque<Chunk>,
+    /// Dropping the document removes the children of all its nodes, so it
+    /// needs to live as long as the chunks.
+    _document: Document,
+}
+
+impl RenderChunks {
+    /// Creates the chunks of the given [Document]. The document needs to
+    /// contain an `<html>` element with a `<head>` and a `<body>`.
+    pub(crate) fn new(document: Document) -> Self {
+        let mut chunks = VecDeque::new();
+        let html = document.query_selector(":root").ok().flatten();
+        let (head, body) = match &html {
+            Some(h) => (
+                Self::child_element(h, "head"),
+                Self::child_element(h, "body"),
+            ),
+            None => (None, None),
+        };
+        match (html, head, body) {
+            (Some(html), Some(head), Some(body)) => {
+                chunks.push_back(Chunk::Start {
+                    html: html.clone(),
+                    head,
+                    body: body.clone(),
+                });
+                for child in body.children().borrow().iter() {
+                    chunks.push_back(Chunk::Body(child.clone()));
+                }
+                chunks.push_back(Chunk::End { html, body });
+            }
+            _ => log::error!(
+                "The document is missing an <html>, <head> or <body> element!"
+            ),
+        }
+        Self {
+            chunks,
+            _document: document,
+        }
+    }
+
+    fn child_element(node: &Node, tag_name: &str) -> Option<Node> {
+        node.children()
+            .borrow()
+            .iter()
+            .find(|c| c.tag_name().as_deref() == Some(tag_name))
+            .cloned()
+    }
+
+    fn serialize_chunk(chunk: Chunk) -> std::io::Result<Vec<u8>> {
+        let mut bytes = vec![];
+        match chunk {
+            Chunk::Start { html, head, body } => {
+                let mut serializer =
+                    HtmlSerializer::new(&mut bytes, SerializeOpts::default());
+                serializer.write_doctype("html")?;
+                Self::start_elem(&mut serializer, &html)?;
+                let head: SerializableHandle = head.into();
+                serialize(&mut serializer.writer, &head, Self::include_node())?;
+                Self::start_elem(&mut serializer, &body)?;
+            }
+            Chunk::Body(node) => {
+                let node: SerializableHandle = node.into();
+                serialize(&mut bytes, &node, Self::include_node())?;
+            }
+            Chunk::End { html, body } => {
+                for node in [body, html] {
+                    if let NodeData::Element { name, .. } = &node.data {
+                        write!(bytes, "</{}>", name.local)?;
+                    }
+                }
+            }
+        }
+        Ok(bytes)
+    }
+
+    fn start_elem<W: Write>(
+        serializer: &mut HtmlSerializer<W>,
+        node: &Node,
+    ) -> std::io::Result<()> {
+        if let NodeData::Element { name, attrs, .. } = &node.data {
+            let attrs = attrs.borrow();
+            serializer.start_elem(
+                name.clone(),
+                attrs.iter().map(|a| (&a.name, &a.value[..])),
+            )?;
+        }
+        Ok(())
+    }
+
+    fn include_node() -> SerializeOpts {
+        SerializeOpts {
+            traversal_scope: TraversalScope::IncludeNode,
+            ..Default::default()
+        }
+    }
+}
+
+impl Iterator for RenderChunks {
+    type Item = Result<String, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let chunk = self.chunks.pop_front()?;
+        let chunk = Self::serialize_chunk(chunk).and_then(|bytes| {
+            String::from_utf8(bytes).map_err(|e| {
+                std::io::Error::new(std::io::ErrorKind::InvalidData, e)
+            })
+        });
+        Some(chunk.map_err(Error::Render))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::RenderChunks,
+        crate::html::{api::*, DocumentExt, LanguageTag, NodeExt, Script},
+    };
+
+    #[test]
+    fn chunks_equal_serialized_document() {
+        let create_document = || {
+            document(
+                LanguageTag::parse("de-DE").unwrap(),
+                head(vec![
+                    style(text("nav>a{color: red}")),
+                    script(Script::Inline("if (a < b && c) {}")),
+                ]),
+                body(vec![
+                    h1(vec![text("a < b & \"c\"")]).attr("class", "x\"y"),
+                    br(),
+                    custom("noscript", vec![p(vec![text("Hello")])]),
+                ])
+                .attr("data-lewp", "body"),
+            )
+        };
+        let chunks = RenderChunks::new(create_document())
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(chunks.len(), 5);
+        assert!(chunks[0].ends_with("</head><body data-lewp=\"body\">"));
+        assert_eq!(chunks[4], "</body></html>");
+        assert_eq!(chunks.concat(), create_document().into_html());
+    }
+}
@@ -3,11 +3,17 @@ use {
         archive::{Archive, ArchiveComponent},
         component::{ComponentDetails, ComponentId},
         resources::{ResourceLevel, ResourceType},
+        view::COMPONENT_ID_ATTRIBUTE,
     },
     lewp_css::{
         cssparser::ToCss,
         domain::{
-            at_rules::{document::DocumentAtRule, media::MediaAtRule},
+            at_rules::{
+                container::ContainerAtRule,
+                document::DocumentAtRule,
+                layer::LayerAtRule,
+                media::MediaAtRule,
+            },
             selectors::OurSelectorImpl,
             CssRule,
             CssRules,
@@ -26,11 +32,16 @@ mod processed_component;
 mod property_classification;
 #[cfg(test)]
 mod test;
+mod unused_rules;
 
+pub use {
+    entireness::Entireness,
+    lewp_css::autoprefixer::{Browser, BrowserTargets, BrowserVersion},
+};
 pub(crate) use {
-    //entireness::Entireness,
     processed_component::ProcessedComponent,
     property_classification::PropertyClassification,
+    unused_rules::remove_unused_rules,
 };
 
 /// This keyword is intentionally defined with a whitespace at the end.
@@ -43,6 +54,9 @@ pub struct CssOptions {
     pub id: ComponentId,
     /// The resource level of the component.
     pub level: ResourceLevel,
+    /// The browsers the stylesheet is prefixed for, see
+    /// [CssRules::autoprefix]. No prefixes are added or removed if `None`.
+    pub browser_targets: Option<BrowserTargets>,
 }
 
 /// CSS resources available in an [Archive].
@@ -82,14 +96,17 @@ impl ArchiveComponent for Css {
             }
         };
         log::debug!("Successfully parsed combined stylesheet for {details:?}",);
-        match &options.level {
-            ResourceLevel::Page => {
-                let content = ProcessedComponent::new(stylesheet)?;
-                return Ok(Self { details, content });
-            } // there is no reason for pages to be isolated
-            _ => (),
+        let mut stylesheet = match &options.level {
+            // there is no reason for pages to be isolated
+            ResourceLevel::Page => stylesheet,
+            _ => Self::isolate_stylesheet(stylesheet, &options)?,
+        };
+        // nested rules are relative to their parent, so they are isolated
+        // together with it and can be flattened afterwards
+        stylesheet.rules.flatten_nesting();
+        if let Some(targets) = &options.browser_targets {
+            stylesheet.rules.autoprefix(targets);
         }
-        let stylesheet = Self::isolate_stylesheet(stylesheet, &options)?;
         let content = ProcessedComponent::new(stylesheet)?;
         Ok(Self { details, content })
     }
@@ -101,6 +118,10 @@ impl ArchiveComponent for Css {
     fn details(&self) -> &ComponentDetails {
         &self.details
     }
+
+    fn content_hash(&self) -> Option<String> {
+        Some(super::content_hash(self.content.full.as_bytes()))
+    }
 }
 
 impl Css {
@@ -160,7 +181,11 @@ impl Css {
                     }
                 }
                 CssRule::Media(MediaAtRule { rules, .. })
-                | CssRule::Document(DocumentAtRule { rules, .. }) => {
+                | CssRule::Document(DocumentAtRule { rules, .. })
+                | CssRule::Container(ContainerAtRule { rules, .. })
+                | CssRule::Layer(LayerAtRule {
+                    rules: Some(rules), ..
+                }) => {
                     if !recursive {
                         continue;
                     }
@@ -181,7 +206,7 @@ impl Css {
             return Err(anyhow::anyhow!("{e:#?}",));
         };
         let new = match lewp_css::parse_css_selector(&format!(
-            ".{} {}",
+            "[{COMPONENT_ID_ATTRIBUTE}=\"{}\"] {}",
             options.id, old
         )) {
             Err(e) => {
@@ -202,7 +227,7 @@ impl Css {
             return Err(anyhow::anyhow!("{e:#?}",));
         };
         let new = match lewp_css::parse_css_selector(&format!(
-            "{}.{}",
+            "{}[{COMPONENT_ID_ATTRIBUTE}=\"{}\"]",
             old.replace(CSS_COMPONENT_IDENTIFIER, ""),
             options.id
         )) {
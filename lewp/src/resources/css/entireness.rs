@@ -1,5 +1,7 @@
+use crate::resources::ResourceId;
+
 /// Defines the level of completeness.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Entireness {
     /// The entire CSS.
     #[default]
@@ -10,3 +12,31 @@ pub enum Entireness {
     /// Only non-render critical parts.
     NonRenderCritical,
 }
+
+impl Entireness {
+    /// Returns the [ResourceId] that addresses this part of a [Css](super::Css)
+    /// component on the webserver, for example
+    /// `/resources/components/hello-world/css/non-render-critical.css`.
+    ///
+    /// [Full](Entireness::Full) is addressed by the component path itself and
+    /// therefore returns `None`.
+    pub fn resource_id(&self) -> Option<ResourceId> {
+        match self {
+            Self::Full => None,
+            Self::RenderCritical => {
+                Some(ResourceId::from("render-critical.css"))
+            }
+            Self::NonRenderCritical => {
+                Some(ResourceId::from("non-render-critical.css"))
+            }
+        }
+    }
+
+    /// Returns the [Entireness] that is addressed by the given [ResourceId].
+    /// This is the reverse operation of [resource_id](Self::resource_id).
+    pub fn from_resource_id(resource_id: &Option<ResourceId>) -> Option<Self> {
+        [Self::Full, Self::RenderCritical, Self::NonRenderCritical]
+            .into_iter()
+            .find(|e| &e.resource_id() == resource_id)
+    }
+}
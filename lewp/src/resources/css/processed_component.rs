@@ -1,8 +1,14 @@
 use {
-    crate::resources::css::PropertyClassification,
+    crate::resources::css::{Entireness, PropertyClassification},
     lewp_css::{
         domain::{
-            at_rules::{document::DocumentAtRule, media::MediaAtRule},
+            at_rules::{
+                container::ContainerAtRule,
+                document::DocumentAtRule,
+                layer::LayerAtRule,
+                media::MediaAtRule,
+                supports::SupportsAtRule,
+            },
             properties::{Importance, PropertyDeclaration},
             CssRule,
             CssRules,
@@ -37,6 +43,43 @@ impl ProcessedComponent {
         })
     }
 
+    /// Returns the part of the processed component defined by the given
+    /// [Entireness].
+    pub fn get(&self, entireness: &Entireness) -> Arc<String> {
+        match entireness {
+            Entireness::Full => Arc::clone(&self.full),
+            Entireness::RenderCritical => Arc::clone(&self.render_critical),
+            Entireness::NonRenderCritical => {
+                Arc::clone(&self.non_render_critical)
+            }
+        }
+    }
+
+    /// Parses the [full](Self::full) processed component into a [Stylesheet].
+    ///
+    /// The parsed stylesheet is not kept because it can not be shared
+    /// between threads.
+    pub fn stylesheet(&self) -> anyhow::Result<Stylesheet> {
+        Stylesheet::parse(&self.full).map_err(|e| anyhow::anyhow!("{e:#?}"))
+    }
+
+    /// Creates a new stylesheet that contains only the part defined by the
+    /// given [Entireness].
+    pub fn extract_stylesheet(
+        stylesheet: Stylesheet,
+        entireness: &Entireness,
+    ) -> anyhow::Result<Stylesheet> {
+        match entireness {
+            Entireness::Full => Ok(stylesheet),
+            Entireness::RenderCritical => {
+                Self::extract_render_critical_stylesheet(stylesheet)
+            }
+            Entireness::NonRenderCritical => {
+                Self::extract_non_render_critical_stylesheet(stylesheet)
+            }
+        }
+    }
+
     /// Creates a new stylesheet that contains only render critical properties.
     pub fn extract_render_critical_stylesheet(
         stylesheet: Stylesheet,
@@ -44,31 +87,38 @@ impl ProcessedComponent {
         Self::filter_stylesheet_properties(
             stylesheet,
             Rc::new(Box::new(|x| x.is_render_critical())),
+            false,
         )
     }
 
     /// Creates a new stylesheet that contains only NON render critical properties.
+    ///
+    /// At-rules that do not contain style rules, for example `@font-face` or
+    /// `@keyframes`, are kept in this stylesheet.
     pub fn extract_non_render_critical_stylesheet(
         stylesheet: Stylesheet,
     ) -> anyhow::Result<Stylesheet> {
         Self::filter_stylesheet_properties(
             stylesheet,
             Rc::new(Box::new(|x| !x.is_render_critical())),
+            true,
         )
     }
 
     /// Creates a new stylesheet and filters the properties by the given closure.
     ///
-    /// It automatically cleans up empty rules.
+    /// It automatically cleans up empty rules. At-rules that do not contain
+    /// style rules (eg. `@font-face`) are kept if `keep_other_at_rules` is set.
     pub fn filter_stylesheet_properties(
         stylesheet: Stylesheet,
         filter: Rc<Box<dyn Fn(&PropertyDeclaration<Importance>) -> bool>>,
+        keep_other_at_rules: bool,
     ) -> anyhow::Result<Stylesheet> {
         let mut stylesheet = stylesheet;
 
         Self::filter_rules(&mut stylesheet.rules, filter, true)?;
 
-        Self::remove_empty_rules(&mut stylesheet.rules);
+        Self::remove_empty_rules(&mut stylesheet.rules, keep_other_at_rules);
 
         Ok(stylesheet)
     }
@@ -88,7 +138,12 @@ impl ProcessedComponent {
                     property_declarations.0.retain(|x| iteration_filter(x));
                 }
                 CssRule::Media(MediaAtRule { rules, .. })
-                | CssRule::Document(DocumentAtRule { rules, .. }) => {
+                | CssRule::Document(DocumentAtRule { rules, .. })
+                | CssRule::Supports(SupportsAtRule { rules, .. })
+                | CssRule::Container(ContainerAtRule { rules, .. })
+                | CssRule::Layer(LayerAtRule {
+                    rules: Some(rules), ..
+                }) => {
                     if !recursive {
                         continue;
                     }
@@ -101,13 +156,32 @@ impl ProcessedComponent {
         Ok(())
     }
 
-    fn remove_empty_rules(rules: &mut CssRules) {
-        rules.0.retain(|r| match r {
+    /// Removes the rules that are empty after filtering. Empty `@layer`
+    /// blocks with a name are replaced by a statement, so the order of the
+    /// layers stays the same in every part of the stylesheet.
+    fn remove_empty_rules(rules: &mut CssRules, keep_other_at_rules: bool) {
+        rules.0.retain_mut(|r| match r {
             CssRule::Style(StyleRule {
                 property_declarations,
                 ..
             }) => !property_declarations.is_empty(),
-            _ => false,
+            CssRule::Media(MediaAtRule { rules, .. })
+            | CssRule::Document(DocumentAtRule { rules, .. })
+            | CssRule::Supports(SupportsAtRule { rules, .. })
+            | CssRule::Container(ContainerAtRule { rules, .. }) => {
+                Self::remove_empty_rules(rules, keep_other_at_rules);
+                !rules.is_empty()
+            }
+            CssRule::Layer(layer) => {
+                if let Some(rules) = &mut layer.rules {
+                    Self::remove_empty_rules(rules, keep_other_at_rules);
+                    if rules.is_empty() {
+                        layer.rules = None;
+                    }
+                }
+                !layer.is_statement() || !layer.names.is_empty()
+            }
+            _ => keep_other_at_rules,
         });
     }
 }
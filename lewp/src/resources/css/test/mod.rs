@@ -37,6 +37,7 @@ fn css_components_and_archive() {
     let options = CssOptions {
         id: "sitemap".into(),
         level: ResourceLevel::Page,
+        browser_targets: None,
     };
     let c = Arc::new(Resource::<Css>::load::<TestArchive>(options).unwrap());
     println!("Parsed render critical: {:#?}", c.content.render_critical);
@@ -59,10 +60,199 @@ fn isolate_css_module() {
     let options = CssOptions {
         id: "hello-world".into(),
         level: ResourceLevel::Component,
+        browser_targets: None,
     };
     let css = Resource::<Css>::load::<TestArchive>(options).unwrap();
     assert_eq!(
         *css.content.full,
-        String::from("header.hello-world{border: thin solid black}.hello-world h1{font-style: bold}.hello-world h2{font-style: italic}")
+        String::from("header[data-lewp-id=\"hello-world\"]{border: thin solid black}[data-lewp-id=\"hello-world\"] h1{font-style: bold}[data-lewp-id=\"hello-world\"] h2{font-style: italic}")
         );
 }
+
+#[test]
+fn split_render_critical_css() {
+    use {
+        crate::resources::{css::ProcessedComponent, Entireness},
+        lewp_css::Stylesheet,
+    };
+
+    let stylesheet = Stylesheet::parse(
+        "h1{display: block;color: red}@media (max-width: 600px){h1{width: 100%;color: blue}}@font-face{font-family: \"Test\";src: url(test.woff2)}",
+    )
+    .unwrap();
+    let processed = ProcessedComponent::new(stylesheet).unwrap();
+    assert_eq!(
+        *processed.get(&Entireness::RenderCritical),
+        "h1{display: block}@media (max-width:600px){h1{width: 100%}}"
+    );
+    assert_eq!(
+        *processed.get(&Entireness::NonRenderCritical),
+        "h1{color: red}@media (max-width:600px){h1{color: blue}}@font-face{font-family:\"Test\";src:url(test.woff2)}"
+    );
+    assert_eq!(
+        Entireness::from_resource_id(
+            &Entireness::NonRenderCritical.resource_id()
+        ),
+        Some(Entireness::NonRenderCritical)
+    );
+    assert_eq!(Entireness::from_resource_id(&None), Some(Entireness::Full));
+}
+
+#[test]
+fn remove_unused_css_rules() {
+    use {
+        crate::{
+            html::{Document, DocumentExt},
+            resources::css::remove_unused_rules,
+        },
+        lewp_css::Stylesheet,
+    };
+
+    let document = Document::from_string(
+        "<html><body><div class=\"used\"><a href=\"/\">Home</a></div></body></html>".into(),
+    )
+    .unwrap();
+    let mut stylesheets = vec![
+        Stylesheet::parse(
+            ".used{color: red}.unused{color: blue}a:hover{color: green}@media (max-width: 600px){.unused{color: red}}@media print{body > .used{display: none}}",
+        )
+        .unwrap(),
+        Stylesheet::parse(
+            "@keyframes spin{from{opacity: 0}to{opacity: 1}}@keyframes fade{from{opacity: 0}to{opacity: 1}}.used::before{animation: spin 1s}",
+        )
+        .unwrap(),
+    ];
+    remove_unused_rules(&mut stylesheets, &document);
+    assert_eq!(
+        stylesheets[0].to_css_string(false),
+        ".used{color: red}a:hover{color: green}@media print{body > .used{display: none}}"
+    );
+    assert_eq!(
+        stylesheets[1].to_css_string(false),
+        "@keyframes spin{from{opacity: 0}100%{opacity: 1}}.used::before{animation: spin 1s}"
+    );
+}
+
+#[test]
+fn keep_css_rules_with_negated_dynamic_state() {
+    use {
+        crate::{
+            html::{Document, DocumentExt},
+            resources::css::remove_unused_rules,
+        },
+        lewp_css::Stylesheet,
+    };
+
+    let document = Document::from_string(
+        "<html><body><button class=\"b\">Send</button></body></html>".into(),
+    )
+    .unwrap();
+    let mut stylesheets = vec![Stylesheet::parse(
+        "button:not(:disabled){color: red}.b:not(:focus){color: blue}.b:not(:is(:hover, .c)){color: green}.b:not(:not(:hover)){color: gray}.b:not(.b:hover){color: black}.missing:not(:focus){color: white}",
+    )
+    .unwrap()];
+    remove_unused_rules(&mut stylesheets, &document);
+    assert_eq!(
+        stylesheets[0].to_css_string(false),
+        "button:not(:disabled){color: red}.b:not(:focus){color: blue}.b:not(:is(:hover, .c)){color: green}.b:not(:not(:hover)){color: gray}.b:not(.b:hover){color: black}"
+    );
+}
+
+#[test]
+fn match_attribute_states_when_removing_unused_css() {
+    use {
+        crate::{
+            html::{Document, DocumentExt},
+            resources::css::remove_unused_rules,
+        },
+        lewp_css::Stylesheet,
+    };
+
+    let document = Document::from_string(
+        "<html><body><button class=\"b\" disabled>Send</button><input class=\"i\"></body></html>".into(),
+    )
+    .unwrap();
+    let mut stylesheets = vec![Stylesheet::parse(
+        ".b:disabled{color: red}.b:not(:disabled){color: blue}.b:enabled{color: green}.i:disabled{color: gray}.i:not(:disabled){color: black}.i:required{color: white}.i:optional{color: red}.i:checked{color: blue}.i:hover{color: green}",
+    )
+    .unwrap()];
+    remove_unused_rules(&mut stylesheets, &document);
+    assert_eq!(
+        stylesheets[0].to_css_string(false),
+        ".b:disabled{color: red}.i:not(:disabled){color: black}.i:optional{color: red}.i:hover{color: green}"
+    );
+}
+
+#[test]
+fn isolate_and_split_css_layers() {
+    use {
+        crate::resources::{css::ProcessedComponent, Entireness},
+        lewp_css::Stylesheet,
+    };
+
+    let options = CssOptions {
+        id: "hello-world".into(),
+        level: ResourceLevel::Component,
+        browser_targets: None,
+    };
+    let stylesheet = Stylesheet::parse(
+        "@layer base,theme;@layer base{h1{display: block}}@layer theme{h1{color: red}}@media print{@layer{#component p{width: 100%}}}",
+    )
+    .unwrap();
+    let stylesheet = Css::isolate_stylesheet(stylesheet, &options).unwrap();
+    assert_eq!(
+        stylesheet.to_css_string(false),
+        "@layer base,theme;@layer base{[data-lewp-id=\"hello-world\"] h1{display: block}}@layer theme{[data-lewp-id=\"hello-world\"] h1{color: red}}@media print{@layer{p[data-lewp-id=\"hello-world\"]{width: 100%}}}"
+    );
+
+    let processed = ProcessedComponent::new(stylesheet).unwrap();
+    assert_eq!(
+        *processed.get(&Entireness::RenderCritical),
+        "@layer base,theme;@layer base{[data-lewp-id=\"hello-world\"] h1{display: block}}@layer theme;@media print{@layer{p[data-lewp-id=\"hello-world\"]{width: 100%}}}"
+    );
+    assert_eq!(
+        *processed.get(&Entireness::NonRenderCritical),
+        "@layer base,theme;@layer base;@layer theme{[data-lewp-id=\"hello-world\"] h1{color: red}}"
+    );
+}
+
+#[test]
+fn isolate_css_container_queries() {
+    use lewp_css::Stylesheet;
+
+    let options = CssOptions {
+        id: "hello-world".into(),
+        level: ResourceLevel::Component,
+        browser_targets: None,
+    };
+    let stylesheet = Stylesheet::parse(
+        "@container card (width>=400px){h1{display: none}#component p{color: red}}",
+    )
+    .unwrap();
+    let stylesheet = Css::isolate_stylesheet(stylesheet, &options).unwrap();
+    assert_eq!(
+        stylesheet.to_css_string(false),
+        "@container card (width>=400px){[data-lewp-id=\"hello-world\"] h1{display: none}p[data-lewp-id=\"hello-world\"]{color: red}}"
+    );
+}
+
+#[test]
+fn isolate_and_flatten_nested_css() {
+    use lewp_css::Stylesheet;
+
+    let options = CssOptions {
+        id: "hello-world".into(),
+        level: ResourceLevel::Component,
+        browser_targets: None,
+    };
+    let stylesheet = Stylesheet::parse(
+        "#component .card{padding:0;& .title{margin:0}@media print{padding:1px}}h1{&:hover{color:red}}",
+    )
+    .unwrap();
+    let mut stylesheet = Css::isolate_stylesheet(stylesheet, &options).unwrap();
+    stylesheet.rules.flatten_nesting();
+    assert_eq!(
+        stylesheet.to_css_string(false),
+        ".card[data-lewp-id=\"hello-world\"]{padding:0}.card[data-lewp-id=\"hello-world\"] .title{margin:0}@media print{.card[data-lewp-id=\"hello-world\"]{padding:1px}}[data-lewp-id=\"hello-world\"] h1:hover{color:red}"
+    );
+}
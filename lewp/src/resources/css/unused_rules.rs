@@ -0,0 +1,150 @@
+use {
+    crate::html::{Document, DocumentExt, SelectableNode},
+    lewp_css::{
+        cssparser::ToCss,
+        domain::{
+            at_rules::{
+                container::ContainerAtRule,
+                document::DocumentAtRule,
+                keyframes::KeyframesAtRule,
+                layer::LayerAtRule,
+                media::MediaAtRule,
+                supports::SupportsAtRule,
+            },
+            selectors::matches::matches,
+            CssRule,
+            CssRules,
+            StyleRule,
+        },
+        Stylesheet,
+    },
+    std::collections::HashSet,
+};
+
+/// Removes all rules from the given stylesheets that are not used by the
+/// given [Document].
+///
+/// A style rule is removed if none of its selectors matches an element in the
+/// document. States that can change on the client, like `:hover`, and pseudo
+/// elements are assumed to match, or to not match inside of `:not()`. States
+/// that are set by HTML attributes, like `:disabled`, are matched against the
+/// attributes. `@media`, `@supports`, `@container` and
+/// `@document` rules are removed if they are empty afterwards, empty `@layer`
+/// blocks are replaced by a statement to keep the order of the layers.
+/// `@keyframes` are kept only if they are referenced by an `animation` or
+/// `animation-name` property of a remaining style rule in one of the
+/// stylesheets. All other at-rules are kept.
+pub(crate) fn remove_unused_rules(
+    stylesheets: &mut [Stylesheet],
+    document: &Document,
+) {
+    let elements = document
+        .query_selector_all("*")
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(SelectableNode::new)
+        .map(SelectableNode::with_ignored_dynamic_state)
+        .collect::<Vec<_>>();
+
+    for stylesheet in stylesheets.iter_mut() {
+        retain_matching_rules(&mut stylesheet.rules, &elements);
+    }
+
+    let mut animation_names = HashSet::new();
+    for stylesheet in stylesheets.iter() {
+        collect_animation_names(&stylesheet.rules, &mut animation_names);
+    }
+
+    for stylesheet in stylesheets.iter_mut() {
+        retain_referenced_keyframes(&mut stylesheet.rules, &animation_names);
+    }
+}
+
+fn retain_matching_rules(rules: &mut CssRules, elements: &[SelectableNode]) {
+    rules.0.retain_mut(|r| match r {
+        CssRule::Style(StyleRule { selectors, .. }) => selectors
+            .0
+            .iter()
+            .any(|s| elements.iter().any(|e| matches(s, e))),
+        CssRule::Media(MediaAtRule { rules, .. })
+        | CssRule::Document(DocumentAtRule { rules, .. })
+        | CssRule::Supports(SupportsAtRule { rules, .. })
+        | CssRule::Container(ContainerAtRule { rules, .. }) => {
+            retain_matching_rules(rules, elements);
+            !rules.is_empty()
+        }
+        CssRule::Layer(layer) => {
+            if let Some(rules) = &mut layer.rules {
+                retain_matching_rules(rules, elements);
+                if rules.is_empty() {
+                    layer.rules = None;
+                }
+            }
+            !layer.is_statement() || !layer.names.is_empty()
+        }
+        _ => true,
+    });
+}
+
+fn collect_animation_names(rules: &CssRules, names: &mut HashSet<String>) {
+    for rule in &rules.0 {
+        match rule {
+            CssRule::Style(StyleRule {
+                property_declarations,
+                ..
+            }) => {
+                for declaration in &property_declarations.0 {
+                    if !declaration.hasAsciiNameIgnoringCase("animation")
+                        && !declaration
+                            .hasAsciiNameIgnoringCase("animation-name")
+                    {
+                        continue;
+                    }
+                    let value = declaration.value.to_css_string();
+                    names.extend(
+                        value
+                            .split(|c: char| c == ',' || c.is_whitespace())
+                            .filter(|v| !v.is_empty())
+                            .map(|v| v.trim_matches('"').to_string()),
+                    );
+                }
+            }
+            CssRule::Media(MediaAtRule { rules, .. })
+            | CssRule::Document(DocumentAtRule { rules, .. })
+            | CssRule::Supports(SupportsAtRule { rules, .. })
+            | CssRule::Container(ContainerAtRule { rules, .. })
+            | CssRule::Layer(LayerAtRule {
+                rules: Some(rules), ..
+            }) => collect_animation_names(rules, names),
+            _ => {}
+        }
+    }
+}
+
+fn retain_referenced_keyframes(
+    rules: &mut CssRules,
+    animation_names: &HashSet<String>,
+) {
+    rules.0.retain_mut(|r| match r {
+        CssRule::Keyframes(KeyframesAtRule { name, .. }) => {
+            animation_names.contains(&name.as_atom().0)
+        }
+        CssRule::Media(MediaAtRule { rules, .. })
+        | CssRule::Document(DocumentAtRule { rules, .. })
+        | CssRule::Supports(SupportsAtRule { rules, .. })
+        | CssRule::Container(ContainerAtRule { rules, .. }) => {
+            retain_referenced_keyframes(rules, animation_names);
+            !rules.is_empty()
+        }
+        CssRule::Layer(layer) => {
+            if let Some(rules) = &mut layer.rules {
+                retain_referenced_keyframes(rules, animation_names);
+                if rules.is_empty() {
+                    layer.rules = None;
+                }
+            }
+            !layer.is_statement() || !layer.names.is_empty()
+        }
+        _ => true,
+    });
+}
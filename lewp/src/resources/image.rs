@@ -2,13 +2,85 @@ use {
     crate::{
         archive::{Archive, ArchiveComponent},
         component::{ComponentDetails, ComponentId},
-        resources::{ResourceLevel, ResourceType},
+        html::{
+            api::responsive_image,
+            ImageCandidate,
+            ImageSource,
+            Node,
+            ResponsiveImage,
+        },
+        resources::{Resource, ResourceId, ResourceLevel, ResourceType},
+    },
+    image::{
+        codecs::{jpeg::JpegEncoder, png::PngEncoder, webp::WebPEncoder},
+        imageops::FilterType,
+        DynamicImage,
     },
     mime::Mime,
     rust_embed::RustEmbed,
-    std::path::PathBuf,
+    std::path::{Path, PathBuf},
 };
 
+/// The quality of re-encoded `JPEG` variants.
+const JPEG_QUALITY: u8 = 80;
+
+/// The formats that resized variants of an [Image] can be encoded to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ImageFormat {
+    /// Portable Network Graphics.
+    Png,
+    /// `JPEG`, encoded with a quality of 80. Transparency is removed.
+    Jpeg,
+    /// Lossless `WebP`.
+    WebP,
+}
+
+impl ImageFormat {
+    fn from_image_format(format: image::ImageFormat) -> Option<Self> {
+        match format {
+            image::ImageFormat::Png => Some(Self::Png),
+            image::ImageFormat::Jpeg => Some(Self::Jpeg),
+            image::ImageFormat::WebP => Some(Self::WebP),
+            _ => None,
+        }
+    }
+
+    /// The [Mime] type of the format.
+    pub fn mime_type(&self) -> Mime {
+        match self {
+            Self::Png => mime::IMAGE_PNG,
+            Self::Jpeg => mime::IMAGE_JPEG,
+            Self::WebP => "image/webp".parse().unwrap_or(mime::IMAGE_STAR),
+        }
+    }
+
+    /// The file extension of the format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Png => "png",
+            Self::Jpeg => "jpg",
+            Self::WebP => "webp",
+        }
+    }
+
+    fn encode(&self, image: &DynamicImage) -> anyhow::Result<Vec<u8>> {
+        let mut content = vec![];
+        match self {
+            Self::Png => {
+                image.write_with_encoder(PngEncoder::new(&mut content))?
+            }
+            Self::Jpeg => {
+                DynamicImage::from(image.to_rgb8()).write_with_encoder(
+                    JpegEncoder::new_with_quality(&mut content, JPEG_QUALITY),
+                )?
+            }
+            Self::WebP => DynamicImage::from(image.to_rgba8())
+                .write_with_encoder(WebPEncoder::new_lossless(&mut content))?,
+        }
+        Ok(content)
+    }
+}
+
 /// The options to be passed when loading an image.
 #[derive(Debug)]
 pub struct ImageOptions {
@@ -17,14 +89,80 @@ pub struct ImageOptions {
     /// The resource level.
     pub level: ResourceLevel,
     /// File name to load including the extension.
-    filename: PathBuf,
+    pub filename: PathBuf,
+    /// The widths in pixels of the resized variants that are created. Widths
+    /// that are not smaller than the original image are skipped.
+    pub widths: Vec<u32>,
+    /// Additional formats that the image and its variants are encoded to.
+    /// Variants in the format of the original image are always created.
+    pub formats: Vec<ImageFormat>,
+}
+
+impl ImageOptions {
+    /// Creates options that load the given file without creating variants.
+    pub fn new(
+        id: ComponentId,
+        level: ResourceLevel,
+        filename: PathBuf,
+    ) -> Self {
+        Self {
+            id,
+            level,
+            filename,
+            widths: vec![],
+            formats: vec![],
+        }
+    }
+
+    /// Sets the widths of the resized variants.
+    pub fn with_widths(mut self, widths: Vec<u32>) -> Self {
+        self.widths = widths;
+        self
+    }
+
+    /// Sets the additional formats of the variants.
+    pub fn with_formats(mut self, formats: Vec<ImageFormat>) -> Self {
+        self.formats = formats;
+        self
+    }
+}
+
+/// A resized or re-encoded version of an [Image].
+#[derive(Debug)]
+pub struct ImageVariant {
+    /// The id of the variant, for example `logo.320w.webp`.
+    pub resource_id: ResourceId,
+    /// The [Mime] type of the variant.
+    pub mime_type: Mime,
+    /// The width in pixels.
+    pub width: u32,
+    /// The height in pixels.
+    pub height: u32,
+    /// The encoded content.
+    pub content: Vec<u8>,
 }
 
 /// Enables interactions with image files in an [Archive](crate::archive::Archive).
+///
+/// The [Mime] type of the image is detected from its content. Raster images
+/// in `PNG`, `JPEG`, `GIF` or `WebP` format are decoded on load to get their
+/// dimensions and to create the [variants](ImageVariant) that are requested by
+/// the [ImageOptions]. Other images, for example `SVG`, are kept as they are.
+#[doc = include_str!(concat!("../../docs/resources/image.md"))]
 pub struct Image {
     details: ComponentDetails,
     /// The image content.
     pub content: Vec<u8>,
+    /// The [Mime] type that has been detected from the content.
+    pub mime: Mime,
+    /// The width and height in pixels, if the image could be decoded.
+    pub dimensions: Option<(u32, u32)>,
+    /// The resized and re-encoded variants, ordered by format and width.
+    pub variants: Vec<ImageVariant>,
+    /// The SHA-256 hash of the content in the archive. The
+    /// [ResourceServer](crate::archive::ResourceServer) creates the entity
+    /// tags of the image and its variants from it.
+    pub(crate) sha256: [u8; 32],
 }
 
 impl ArchiveComponent for Image {
@@ -35,11 +173,11 @@ impl ArchiveComponent for Image {
             options.id.clone(),
             ResourceType::Image,
             options.level,
-        );
+        )
+        .with_resource_id(options.filename.clone());
         log::debug!("Created ComponentDetails for {options:?}:\n{details:#?}");
 
-        let mut filename = A::path(&details);
-        filename.push(options.filename);
+        let filename = A::path(&details);
         log::debug!("Image filename to load: {:#?}", filename);
         let filename = match filename.to_str() {
             Some(s) => s,
@@ -50,7 +188,7 @@ impl ArchiveComponent for Image {
                 ))
             }
         };
-        let image = match <A as RustEmbed>::get(&filename) {
+        let image = match <A as RustEmbed>::get(filename) {
             Some(r) => r,
             None => {
                 return Err(anyhow::anyhow!(
@@ -58,9 +196,35 @@ impl ArchiveComponent for Image {
                 ));
             }
         };
+        let content = image.data.to_vec();
+        let sha256 = image.metadata.sha256_hash();
+        let mime = Self::detect_mime_type(&content);
+
+        let decoded = image::guess_format(&content).ok().and_then(|f| {
+            image::load_from_memory_with_format(&content, f).ok()
+        });
+        let decoded = match decoded {
+            Some(d) => d,
+            None => {
+                log::debug!("{filename} is not a supported raster image, no variants are created");
+                return Ok(Self {
+                    details,
+                    content,
+                    mime,
+                    dimensions: None,
+                    variants: vec![],
+                    sha256,
+                });
+            }
+        };
+        let variants = Self::create_variants(&decoded, &content, &options)?;
         Ok(Self {
             details,
-            content: image.data.to_vec(),
+            content,
+            mime,
+            dimensions: Some((decoded.width(), decoded.height())),
+            variants,
+            sha256,
         })
     }
 
@@ -73,31 +237,207 @@ impl ArchiveComponent for Image {
     }
 }
 
-#[test]
-fn read_rust_logo() {
-    use crate::{
-        lewp_archive,
-        resources::{Image, WebInterface},
+impl Image {
+    /// Detects the [Mime] type of the given image content.
+    fn detect_mime_type(content: &[u8]) -> Mime {
+        if let Ok(f) = image::guess_format(content) {
+            return f.to_mime_type().parse().unwrap_or(mime::IMAGE_STAR);
+        }
+        let start = String::from_utf8_lossy(&content[..content.len().min(512)]);
+        match start.contains("<svg") {
+            true => mime::IMAGE_SVG,
+            false => mime::IMAGE_STAR,
+        }
+    }
+
+    fn create_variants(
+        image: &DynamicImage,
+        content: &[u8],
+        options: &ImageOptions,
+    ) -> anyhow::Result<Vec<ImageVariant>> {
+        let original_format = image::guess_format(content)
+            .ok()
+            .and_then(ImageFormat::from_image_format);
+        let mut formats = vec![];
+        for format in original_format.iter().chain(options.formats.iter()) {
+            if !formats.contains(format) {
+                formats.push(*format);
+            }
+        }
+
+        let mut widths = options
+            .widths
+            .iter()
+            .copied()
+            .filter(|w| *w > 0 && *w < image.width())
+            .collect::<Vec<_>>();
+        widths.sort_unstable();
+        widths.dedup();
+
+        let stem = options
+            .filename
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let mut variants = vec![];
+        for format in formats {
+            let mut format_widths = widths.clone();
+            // the original size in the original format is the image itself
+            if Some(format) != original_format {
+                format_widths.push(image.width());
+            }
+            for width in format_widths {
+                let resized = match width == image.width() {
+                    true => image.clone(),
+                    false => {
+                        image.resize(width, u32::MAX, FilterType::Lanczos3)
+                    }
+                };
+                variants.push(ImageVariant {
+                    resource_id: ResourceId::from(format!(
+                        "{stem}.{width}w.{}",
+                        format.extension()
+                    )),
+                    mime_type: format.mime_type(),
+                    width: resized.width(),
+                    height: resized.height(),
+                    content: format.encode(&resized)?,
+                });
+            }
+        }
+        Ok(variants)
+    }
+
+    /// Returns the variant with the given [ResourceId].
+    pub fn variant(&self, resource_id: &Path) -> Option<&ImageVariant> {
+        self.variants.iter().find(|v| v.resource_id == resource_id)
+    }
+}
+
+impl Resource<Image> {
+    /// Creates a `<picture>` element that lets the browser choose the best
+    /// fitting [variant](ImageVariant) of the image. `sizes` is the value of
+    /// the `sizes` attribute, for example `(max-width: 600px) 100vw, 50vw`.
+    pub fn picture(&self, sizes: impl ToString, alt: impl ToString) -> Node {
+        let src = self.url();
+        let variant_url = |v: &ImageVariant| ImageCandidate {
+            url: src.with_file_name(&v.resource_id),
+            width: v.width,
+        };
+        let mut sources: Vec<ImageSource> = vec![];
+        let mut candidates = vec![];
+        for variant in &self.variants {
+            if variant.mime_type == self.mime {
+                candidates.push(variant_url(variant));
+                continue;
+            }
+            let mime_type = variant.mime_type.to_string();
+            match sources.iter_mut().find(|s| s.mime_type == mime_type) {
+                Some(s) => s.candidates.push(variant_url(variant)),
+                None => sources.push(ImageSource {
+                    mime_type,
+                    candidates: vec![variant_url(variant)],
+                }),
+            }
+        }
+        let (width, height) = self.dimensions.unwrap_or_default();
+        responsive_image(&ResponsiveImage {
+            src,
+            width,
+            height,
+            alt: alt.to_string(),
+            sizes: sizes.to_string(),
+            sources,
+            candidates,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            html::NodeExt,
+            lewp_archive,
+            resources::{Image, WebInterface},
+        },
     };
 
     lewp_archive!(TestArchive, "testfiles");
     impl WebInterface for TestArchive {}
 
-    let image_details = ImageOptions {
-        id: "hello-world".into(),
-        level: ResourceLevel::Component,
-        filename: PathBuf::from("rust-logo-512x512-blk.png"),
-    };
-    let image_resource = match Image::load::<TestArchive>(image_details) {
-        Ok(f) => f,
-        Err(e) => panic!("{e:#?}"),
-    };
+    fn rust_logo(options: ImageOptions) -> Resource<Image> {
+        match Resource::<Image>::load::<TestArchive>(options) {
+            Ok(f) => f,
+            Err(e) => panic!("{e:#?}"),
+        }
+    }
 
-    assert_eq!(
-        std::fs::read(
-            "testfiles/components/hello-world/images/rust-logo-512x512-blk.png"
+    fn options() -> ImageOptions {
+        ImageOptions::new(
+            "hello-world".into(),
+            ResourceLevel::Component,
+            PathBuf::from("rust-logo-512x512-blk.png"),
         )
-        .unwrap(),
-        image_resource.content
-    );
+    }
+
+    #[test]
+    fn read_rust_logo() {
+        let image_resource = rust_logo(options());
+
+        assert_eq!(
+            std::fs::read(
+                "testfiles/components/hello-world/images/rust-logo-512x512-blk.png"
+            )
+            .unwrap(),
+            image_resource.content
+        );
+        assert_eq!(image_resource.mime, mime::IMAGE_PNG);
+        assert_eq!(image_resource.dimensions, Some((512, 512)));
+        assert!(image_resource.variants.is_empty());
+    }
+
+    #[test]
+    fn create_variants_and_picture() {
+        let image_resource = rust_logo(
+            options()
+                .with_widths(vec![128, 1024])
+                .with_formats(vec![ImageFormat::WebP]),
+        );
+        let variants = image_resource
+            .variants
+            .iter()
+            .map(|v| (v.resource_id.display().to_string(), v.width, v.height))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            variants,
+            vec![
+                ("rust-logo-512x512-blk.128w.png".into(), 128, 128),
+                ("rust-logo-512x512-blk.128w.webp".into(), 128, 128),
+                ("rust-logo-512x512-blk.512w.webp".into(), 512, 512),
+            ]
+        );
+        let webp = image_resource
+            .variant(Path::new("rust-logo-512x512-blk.128w.webp"))
+            .unwrap();
+        assert_eq!(Image::detect_mime_type(&webp.content), webp.mime_type);
+
+        let picture = image_resource.picture("50vw", "Rust logo");
+        let source = picture.query_selector("source").unwrap().unwrap();
+        assert_eq!(
+            source.attribute_value("type").as_deref(),
+            Some("image/webp")
+        );
+        assert_eq!(
+            source.attribute_value("srcset").as_deref(),
+            Some("/resources/components/hello-world/images/rust-logo-512x512-blk.128w.webp 128w, /resources/components/hello-world/images/rust-logo-512x512-blk.512w.webp 512w")
+        );
+        let img = picture.query_selector("img").unwrap().unwrap();
+        assert_eq!(
+            img.attribute_value("src").as_deref(),
+            Some("/resources/components/hello-world/images/rust-logo-512x512-blk.png")
+        );
+        assert_eq!(img.attribute_value("height").as_deref(), Some("512"));
+    }
 }
@@ -29,6 +29,10 @@ pub struct Js {
     details: ComponentDetails,
     /// The JavaScript content.
     pub content: Arc<String>,
+    /// The [Subresource Integrity](https://developer.mozilla.org/en-US/docs/Web/Security/Subresource_Integrity)
+    /// value of the content. It is added as `integrity` attribute to the
+    /// `<script>` element of the component.
+    pub integrity: String,
 }
 
 impl ArchiveComponent for Js {
@@ -71,7 +75,12 @@ impl ArchiveComponent for Js {
                 ));
             }
         };
-        Ok(Self { details, content })
+        let integrity = super::integrity(content.as_bytes());
+        Ok(Self {
+            details,
+            content,
+            integrity,
+        })
     }
 
     fn mime_type() -> Mime {
@@ -81,6 +90,10 @@ impl ArchiveComponent for Js {
     fn details(&self) -> &ComponentDetails {
         &self.details
     }
+
+    fn content_hash(&self) -> Option<String> {
+        Some(super::content_hash(self.content.as_bytes()))
+    }
 }
 
 impl Js {
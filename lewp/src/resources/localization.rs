@@ -0,0 +1,376 @@
+//! Localised messages for pages and components.
+use {
+    crate::{
+        archive::{Archive, ArchiveComponent},
+        component::{ComponentDetails, ComponentId},
+        resources::{Resource, ResourceLevel, ResourceType},
+        LanguageTag,
+    },
+    mime::Mime,
+    rust_embed::RustEmbed,
+    std::{collections::HashMap, path::Path, sync::Arc},
+};
+
+/// The file extension of message files.
+pub(crate) const MESSAGES_EXTENSION: &str = "ftl";
+
+/// The options to be passed when loading the [Messages] of a component.
+#[derive(Debug)]
+pub struct MessagesOptions {
+    /// The component id.
+    pub id: ComponentId,
+    /// The resource level.
+    pub level: ResourceLevel,
+}
+
+/// Contains the localised messages of a page or component, one file per
+/// language:
+/// ```text
+/// components/hello-world/text/en.ftl
+/// components/hello-world/text/de.ftl
+/// components/hello-world/text/de-AT.ftl
+/// ```
+///
+/// The files use a subset of the [Fluent](https://projectfluent.org) syntax:
+/// ```text
+/// # Comments start with a hash.
+/// greeting = Hello { $name }!
+/// description =
+///     Values can span multiple lines
+///     when the lines are indented.
+/// ```
+/// Use a [Localization] to look up the messages in the languages of a page.
+#[derive(Debug)]
+pub struct Messages {
+    details: ComponentDetails,
+    /// The messages by lowercase language tag and message key.
+    messages: HashMap<String, HashMap<String, String>>,
+}
+
+impl Messages {
+    /// Returns the raw message with the given key in the given language,
+    /// without any fallback.
+    pub fn get(&self, language: &str, key: &str) -> Option<&str> {
+        self.messages
+            .get(&language.to_lowercase())?
+            .get(key)
+            .map(String::as_str)
+    }
+
+    /// Returns the lowercase tags of all languages that are available.
+    pub fn languages(&self) -> impl Iterator<Item = &str> {
+        self.messages.keys().map(String::as_str)
+    }
+
+    /// Parses the content of a message file. Lines that cannot be parsed are
+    /// logged and skipped.
+    fn parse(content: &str) -> HashMap<String, String> {
+        let mut messages: HashMap<String, String> = HashMap::new();
+        let mut current: Option<String> = None;
+        for (number, line) in content.lines().enumerate() {
+            let trimmed = line.trim();
+            if trimmed.starts_with('#') {
+                continue;
+            }
+            if trimmed.is_empty() {
+                current = None;
+                continue;
+            }
+            // indented lines continue the value of the previous message
+            if line.starts_with([' ', '\t']) {
+                match current.as_ref().and_then(|k| messages.get_mut(k)) {
+                    Some(value) => {
+                        if !value.is_empty() {
+                            value.push('\n');
+                        }
+                        value.push_str(trimmed);
+                    }
+                    None => log::warn!(
+                        "Ignoring line {} of message file: \"{line}\"",
+                        number + 1
+                    ),
+                }
+                continue;
+            }
+            match line.split_once('=') {
+                Some((key, value)) if !key.trim().is_empty() => {
+                    let key = key.trim().to_string();
+                    messages.insert(key.clone(), value.trim().to_string());
+                    current = Some(key);
+                }
+                _ => {
+                    log::warn!(
+                        "Ignoring line {} of message file: \"{line}\"",
+                        number + 1
+                    );
+                    current = None;
+                }
+            }
+        }
+        messages
+    }
+}
+
+impl ArchiveComponent for Messages {
+    type Options = MessagesOptions;
+
+    fn load<A: Archive>(options: Self::Options) -> anyhow::Result<Self> {
+        let details = ComponentDetails::new(
+            options.id.clone(),
+            ResourceType::Text,
+            options.level,
+        );
+        log::debug!("Created ComponentDetails for {options:?}:\n{details:#?}");
+
+        let mut messages = HashMap::new();
+        for file in A::get_file_list(&details) {
+            if file.extension().and_then(|e| e.to_str())
+                != Some(MESSAGES_EXTENSION)
+            {
+                continue;
+            }
+            let language = match file.file_stem().and_then(|s| s.to_str()) {
+                Some(l) => l.to_lowercase(),
+                None => continue,
+            };
+            let content = Self::read_file::<A>(&file)?;
+            messages.insert(language, Self::parse(&content));
+        }
+        Ok(Self { details, messages })
+    }
+
+    fn mime_type() -> Mime {
+        mime::TEXT_PLAIN_UTF_8
+    }
+
+    fn details(&self) -> &ComponentDetails {
+        &self.details
+    }
+}
+
+impl Messages {
+    fn read_file<A: Archive>(file: &Path) -> anyhow::Result<String> {
+        let filename = match file.to_str() {
+            Some(f) => f,
+            None => {
+                return Err(anyhow::anyhow!(
+                    "Could not convert {} to str!",
+                    file.display()
+                ))
+            }
+        };
+        match <A as RustEmbed>::get(filename) {
+            Some(f) => Ok(String::from(std::str::from_utf8(&f.data)?)),
+            None => Err(anyhow::anyhow!(
+                "Error reading message file \"{filename}\" from file hierarchy!",
+            )),
+        }
+    }
+}
+
+/// Looks up the localised [Messages] of a component in the languages of a
+/// page. Get an instance for your component using
+/// [PageView::localization](crate::view::PageView::localization).
+///
+/// Messages are searched in the order of the language chain, which is derived
+/// from the language tags by removing subtags from the end, for example
+/// `de-CH-1996` followed by `de-CH` and `de`.
+///
+#[doc = include_str!(concat!("../../docs/resources/localization.md"))]
+#[derive(Debug, Clone, Default)]
+pub struct Localization {
+    messages: Option<Arc<Resource<Messages>>>,
+    languages: Vec<String>,
+}
+
+impl Localization {
+    /// Creates a new instance for the given messages and language.
+    pub fn new(
+        messages: Option<Arc<Resource<Messages>>>,
+        language: &LanguageTag,
+    ) -> Self {
+        Self {
+            messages,
+            languages: vec![],
+        }
+        .with_fallback(language)
+    }
+
+    /// Replaces the messages that are looked up.
+    pub fn with_messages(
+        mut self,
+        messages: Option<Arc<Resource<Messages>>>,
+    ) -> Self {
+        self.messages = messages;
+        self
+    }
+
+    /// Appends the chain of the given language to the languages that are
+    /// searched for a message.
+    pub fn with_fallback(mut self, language: &LanguageTag) -> Self {
+        for l in language_chain(language.as_str()) {
+            if !self.languages.contains(&l) {
+                self.languages.push(l);
+            }
+        }
+        self
+    }
+
+    /// Returns the languages that are searched, in order.
+    pub fn languages(&self) -> &[String] {
+        &self.languages
+    }
+
+    /// Returns the message with the given key in the first language of the
+    /// chain that contains it. The placeables of the message, like
+    /// `{ $name }`, are replaced by the given arguments.
+    pub fn get(&self, key: &str, args: &[(&str, &str)]) -> Option<String> {
+        let messages = self.messages.as_ref()?;
+        self.languages
+            .iter()
+            .find_map(|l| messages.get(l, key))
+            .map(|m| format_message(m, args))
+    }
+
+    /// Like [get](Self::get), but returns the key itself if the message is
+    /// not available in any language, so a missing translation does not
+    /// break your page.
+    pub fn message(&self, key: &str, args: &[(&str, &str)]) -> String {
+        match self.get(key, args) {
+            Some(m) => m,
+            None => {
+                log::warn!(
+                    "No message \"{key}\" available for languages {:?}",
+                    self.languages
+                );
+                key.to_string()
+            }
+        }
+    }
+}
+
+/// Creates the lookup chain of the given language tag as described in
+/// [RFC 4647](https://www.rfc-editor.org/rfc/rfc4647#section-3.4). The tags
+/// are lowercase.
+pub(crate) fn language_chain(language: &str) -> Vec<String> {
+    let mut subtags = language
+        .to_lowercase()
+        .split('-')
+        .map(String::from)
+        .collect::<Vec<_>>();
+    let mut chain = vec![];
+    while !subtags.is_empty() {
+        chain.push(subtags.join("-"));
+        subtags.pop();
+        // a singleton introduces an extension and is never the last subtag
+        if subtags.last().is_some_and(|s| s.len() == 1) {
+            subtags.pop();
+        }
+    }
+    chain
+}
+
+/// Replaces the placeables of the given message by the given arguments.
+/// Placeables without a matching argument are left untouched.
+fn format_message(message: &str, args: &[(&str, &str)]) -> String {
+    let mut result = String::with_capacity(message.len());
+    let mut rest = message;
+    while let Some(start) = rest.find('{') {
+        let end = match rest[start..].find('}') {
+            Some(e) => start + e,
+            None => break,
+        };
+        result.push_str(&rest[..start]);
+        let placeable = rest[start + 1..end].trim();
+        let value = placeable.strip_prefix('$').and_then(|name| {
+            args.iter().find(|(k, _)| *k == name).map(|(_, v)| *v)
+        });
+        match value {
+            Some(v) => result.push_str(v),
+            // string literals like { "{" } are used to escape braces
+            None => match placeable
+                .strip_prefix('"')
+                .and_then(|p| p.strip_suffix('"'))
+            {
+                Some(literal) => result.push_str(literal),
+                None => result.push_str(&rest[start..=end]),
+            },
+        }
+        rest = &rest[end + 1..];
+    }
+    result.push_str(rest);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{lewp_archive, resources::WebInterface},
+    };
+
+    lewp_archive!(ResourceArchive, "testfiles");
+    impl WebInterface for ResourceArchive {}
+
+    fn hello_world() -> Option<Arc<Resource<Messages>>> {
+        let options = MessagesOptions {
+            id: "hello-world".into(),
+            level: ResourceLevel::Component,
+        };
+        Some(Arc::new(
+            Resource::<Messages>::load::<ResourceArchive>(options).unwrap(),
+        ))
+    }
+
+    #[test]
+    fn parse_messages() {
+        let messages = Messages::parse(
+            "# comment\ngreeting = Hello { $name }!\n\nlong =\n    first\n    second\ninvalid\n",
+        );
+        assert_eq!(messages["greeting"], "Hello { $name }!");
+        assert_eq!(messages["long"], "first\nsecond");
+        assert_eq!(messages.len(), 2);
+    }
+
+    #[test]
+    fn create_language_chain() {
+        assert_eq!(
+            language_chain("de-CH-1996"),
+            vec!["de-ch-1996", "de-ch", "de"]
+        );
+        assert_eq!(
+            language_chain("zh-Hant-CN-x-private1"),
+            vec!["zh-hant-cn-x-private1", "zh-hant-cn", "zh-hant", "zh"]
+        );
+    }
+
+    #[test]
+    fn format_placeables() {
+        assert_eq!(
+            format_message(
+                "Hello { $name }, { $unknown }{ \"{\" }",
+                &[("name", "Ferris")]
+            ),
+            "Hello Ferris, { $unknown }{"
+        );
+    }
+
+    #[test]
+    fn lookup_with_fallback() {
+        let l10n = Localization::new(
+            hello_world(),
+            &LanguageTag::parse("de-AT").unwrap(),
+        );
+        assert_eq!(
+            l10n.message("greeting", &[("name", "Ferris")]),
+            "Servus Ferris!"
+        );
+        assert_eq!(l10n.message("farewell", &[]), "Auf Wiedersehen!");
+        assert_eq!(l10n.get("only-english", &[]), None);
+
+        let l10n = l10n.with_fallback(&LanguageTag::parse("en").unwrap());
+        assert_eq!(l10n.languages(), ["de-at", "de", "en"]);
+        assert_eq!(l10n.message("only-english", &[]), "Only in English");
+        assert_eq!(l10n.message("missing", &[]), "missing");
+    }
+}
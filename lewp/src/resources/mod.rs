@@ -2,28 +2,125 @@
 
 use {
     crate::archive::{Archive, ArchiveComponent},
-    std::path::PathBuf,
+    sha2::{Digest, Sha256, Sha384},
+    std::{
+        fmt::Write,
+        path::{Path, PathBuf},
+    },
 };
 
 pub(crate) mod css;
 mod image;
 mod js;
+pub(crate) mod localization;
 mod resource_type;
+mod text;
 mod web_interface;
-//mod text;
 
 pub use {
-    css::{Css, CssOptions},
-    image::Image,
+    css::{
+        Browser,
+        BrowserTargets,
+        BrowserVersion,
+        Css,
+        CssOptions,
+        Entireness,
+    },
+    image::{Image, ImageFormat, ImageOptions, ImageVariant},
     js::{Js, JsOptions},
+    localization::{Localization, Messages, MessagesOptions},
     resource_type::ResourceType,
-    //text::Text,
+    text::{Text, TextOptions},
     web_interface::WebInterface,
 };
 
 /// Defines an id for a resource, for example `sample-image.jpg`.
 pub type ResourceId = PathBuf;
 
+/// The number of hexadecimal characters of a content hash.
+pub(crate) const CONTENT_HASH_LENGTH: usize = 8;
+
+/// Creates the hash of the given content that is embedded into the URL of a
+/// resource.
+pub(crate) fn content_hash(content: &[u8]) -> String {
+    let mut hash = String::new();
+    for b in Sha256::digest(content).iter().take(CONTENT_HASH_LENGTH / 2) {
+        let _ = write!(hash, "{b:02x}");
+    }
+    hash
+}
+
+/// Creates the [Subresource Integrity](https://developer.mozilla.org/en-US/docs/Web/Security/Subresource_Integrity)
+/// value of the given content, for example `sha384-oqVuAfXRKap7fdgcCY5uykM6+R9GqQ8K/uxy9rx7HNQlGYl1kPzQho1wx4JwY8wC`.
+pub(crate) fn integrity(content: &[u8]) -> String {
+    format!("sha384-{}", base64(&Sha384::digest(content)))
+}
+
+/// Encodes the given bytes using the standard base64 alphabet with padding.
+pub(crate) fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] =
+        b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        for i in 0..4 {
+            match i <= chunk.len() {
+                true => encoded.push(
+                    ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char,
+                ),
+                false => encoded.push('='),
+            }
+        }
+    }
+    encoded
+}
+
+/// Inserts the given hash into the file name of the path, so
+/// `components/nav/js` becomes `components/nav/js.3f2a1c09.js` and
+/// `css/non-render-critical.css` becomes `css/non-render-critical.3f2a1c09.css`.
+///
+/// The `extension` is appended if the file name does not have one.
+pub(crate) fn with_content_hash(
+    path: &Path,
+    hash: &str,
+    extension: Option<String>,
+) -> PathBuf {
+    let (stem, extension) = match (path.file_stem(), path.extension()) {
+        (Some(s), Some(e)) => (s, Some(e.to_string_lossy().to_string())),
+        (Some(s), None) => (s, extension),
+        _ => return path.to_path_buf(),
+    };
+    let stem = stem.to_string_lossy();
+    match extension {
+        Some(e) => path.with_file_name(format!("{stem}.{hash}.{e}")),
+        None => path.with_file_name(format!("{stem}.{hash}")),
+    }
+}
+
+/// The reverse operation of [with_content_hash]. Returns the file name without
+/// the hash and the hash, or `None` if the file name does not contain a hash.
+pub(crate) fn strip_content_hash(file_name: &str) -> Option<(String, String)> {
+    let mut parts = file_name.rsplitn(3, '.');
+    let (extension, hash, stem) = (parts.next()?, parts.next()?, parts.next()?);
+    if hash.len() != CONTENT_HASH_LENGTH
+        || !hash.chars().all(|c| c.is_ascii_hexdigit())
+        || stem.is_empty()
+    {
+        return None;
+    }
+    // component wide resources are addressed without extension
+    let file_name = match ResourceType::from(stem).extension() {
+        Some(e) if e == extension => stem.to_string(),
+        _ => format!("{stem}.{extension}"),
+    };
+    Some((file_name, hash.to_string()))
+}
+
 /// The different storage level available.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ResourceLevel {
@@ -77,6 +174,9 @@ pub struct Resource<R: crate::archive::ArchiveComponent> {
     pub web_root: PathBuf,
     /// The path to the resource.
     pub path: PathBuf,
+    /// The hash of the content, see [ArchiveComponent::content_hash]. It is
+    /// embedded into the [url](Self::url) of the resource.
+    pub content_hash: Option<String>,
 }
 
 impl<R: crate::archive::ArchiveComponent> Resource<R> {
@@ -86,12 +186,42 @@ impl<R: crate::archive::ArchiveComponent> Resource<R> {
     ) -> anyhow::Result<Self> {
         let model = R::load::<A>(options)?;
         let path = A::path(model.details());
+        let content_hash = model.content_hash();
         Ok(Self {
             model,
             web_root: A::web_root(),
             path,
+            content_hash,
         })
     }
+
+    /// Returns the URL of the resource on the webserver. If the resource has
+    /// a [content_hash](Self::content_hash), it is embedded into the URL, for
+    /// example `/resources/components/nav/js.3f2a1c09.js`. The URL changes
+    /// whenever the content changes, so it can be cached forever by the
+    /// clients.
+    pub fn url(&self) -> PathBuf {
+        self.url_of(None)
+    }
+
+    /// Returns the URL of the given part of the resource, like [url](Self::url),
+    /// for example the [non render critical](Entireness::NonRenderCritical)
+    /// part of a [Css] resource.
+    pub fn url_of(&self, resource_id: Option<ResourceId>) -> PathBuf {
+        let url = self.web_root.join(&self.path);
+        let url = match resource_id {
+            Some(r) => url.join(r),
+            None => url,
+        };
+        match &self.content_hash {
+            Some(h) => with_content_hash(
+                &url,
+                h,
+                self.details().resource_type.extension(),
+            ),
+            None => url,
+        }
+    }
 }
 
 impl<R: crate::archive::ArchiveComponent> std::ops::Deref for Resource<R> {
@@ -100,3 +230,21 @@ impl<R: crate::archive::ArchiveComponent> std::ops::Deref for Resource<R> {
         &self.model
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{base64, integrity};
+
+    #[test]
+    fn encode_base64() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+        assert_eq!(
+            integrity(b"alert('Hello, world.');"),
+            "sha384-H8BRh8j48O9oYatfu5AZzq6A9RINhZO5H16dQZngK7T62em8MUt1FLm52t+eX6xO"
+        );
+    }
+}
@@ -55,7 +55,7 @@ impl From<&str> for ResourceType {
             "css" => Self::Css,
             "js" => Self::JavaScript,
             "images" => Self::Image,
-            "txt" => Self::Text,
+            "text" | "txt" => Self::Text,
             _ => Self::Custom(value.into()),
         }
     }
@@ -1,42 +1,49 @@
 use {
     crate::{
-        component::ComponentId,
-        resources::ResourceType,
-        storage::{Level, Storage, StorageComponent},
+        archive::{Archive, ArchiveComponent},
+        component::{ComponentDetails, ComponentId},
+        resources::{ResourceLevel, ResourceType},
     },
     mime::Mime,
     rust_embed::RustEmbed,
+    std::{path::PathBuf, sync::Arc},
 };
 
-/// Enables loading text files from a [Storage].
-pub struct Text {
-    id: ComponentId,
-    level: Level,
+/// The options to be passed when loading a [Text] file.
+#[derive(Debug)]
+pub struct TextOptions {
+    /// The component id.
+    pub id: ComponentId,
+    /// The resource level.
+    pub level: ResourceLevel,
+    /// File name to load including the extension, relative to the `text`
+    /// folder of the component.
+    pub filename: PathBuf,
 }
 
-impl StorageComponent for Text {
+/// Enables loading text files from an [Archive], for example
+/// `components/imprint/text/address.txt`.
+///
+/// See [Messages](crate::resources::Messages) for localised texts.
+pub struct Text {
+    details: ComponentDetails,
     /// The actual content of the text file.
-    type Content = String;
-    /// The file name relative to the level's "text" folder without file extension.
-    type ContentParameter = String;
+    pub content: Arc<String>,
+}
 
-    fn content<T: Storage>(
-        &self,
-        params: Self::ContentParameter,
-    ) -> anyhow::Result<Self::Content> {
-        let mut filename = T::folder_path(self);
-        filename.push(params);
-        let extension = match ResourceType::Text.extension() {
-            Some(e) => e,
-            None => {
-                return Err(
-                    anyhow::anyhow!(
-                        "The extension for a text file could not be found! This error should never occur!"
-                    )
-                );
-            }
-        };
-        filename.set_extension(extension);
+impl ArchiveComponent for Text {
+    type Options = TextOptions;
+
+    fn load<A: Archive>(options: Self::Options) -> anyhow::Result<Self> {
+        let details = ComponentDetails::new(
+            options.id.clone(),
+            ResourceType::Text,
+            options.level,
+        )
+        .with_resource_id(options.filename.clone());
+        log::debug!("Created ComponentDetails for {options:?}:\n{details:#?}");
+
+        let filename = A::path(&details);
         log::trace!("filename: {:#?}", filename);
         let filename = match filename.to_str() {
             Some(s) => s,
@@ -47,37 +54,25 @@ impl StorageComponent for Text {
                 ))
             }
         };
-        let text = match <T as RustEmbed>::get(&filename) {
+        let text = match <A as RustEmbed>::get(filename) {
             Some(r) => r,
             None => {
                 return Err(anyhow::anyhow!(
-                    "Error reading text file from file hierarchy!",
+                    "Error reading text file \"{filename}\" from file hierarchy!",
                 ));
             }
         };
-        Ok(String::from(std::str::from_utf8(&text.data)?))
-    }
-
-    fn id(&self) -> ComponentId {
-        self.id.clone()
-    }
-
-    fn level(&self) -> Level {
-        self.level
-    }
-
-    fn kind(&self) -> ResourceType {
-        ResourceType::Text
+        Ok(Self {
+            details,
+            content: Arc::new(String::from(std::str::from_utf8(&text.data)?)),
+        })
     }
 
     fn mime_type() -> Mime {
-        mime::TEXT_STAR
+        mime::TEXT_PLAIN_UTF_8
     }
-}
 
-impl Text {
-    /// Creates a new Text component.
-    pub fn new(id: ComponentId, level: Level) -> Self {
-        Self { id, level }
+    fn details(&self) -> &ComponentDetails {
+        &self.details
     }
 }
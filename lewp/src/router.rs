@@ -0,0 +1,520 @@
+//! Maps the path of a request to the [PageModel] that renders it.
+//!
+//! A [Router] holds a list of path patterns together with a constructor of
+//! the page. Segments in curly braces are parameters that are passed to the
+//! constructor and can be converted to any type implementing [FromStr]:
+//! ```rust
+//! # use lewp::{
+//! #     page::{PageId, PageModel},
+//! #     router::Router,
+//! #     Error,
+//! # };
+//! struct HomePage;
+//!
+//! impl PageModel for HomePage {
+//!     fn id(&self) -> PageId {
+//!         "home".into()
+//!     }
+//! }
+//!
+//! struct ArticlePage {
+//!     id: u32,
+//! }
+//!
+//! impl PageModel for ArticlePage {
+//!     fn id(&self) -> PageId {
+//!         "article".into()
+//!     }
+//!     fn title(&self) -> String {
+//!         format!("Article {}", self.id)
+//!     }
+//! }
+//!
+//! let router = Router::default()
+//!     .with_route("/", |_| Ok(HomePage))
+//!     .with_route("/articles/{id}", |params| {
+//!         Ok(ArticlePage {
+//!             id: params.get("id")?,
+//!         })
+//!     });
+//!
+//! let html = router.render("/articles/42").unwrap();
+//! assert!(html.contains("<title>Article 42</title>"));
+//! // parameters that can not be converted do not match the route
+//! assert!(matches!(router.render("/articles/latest"), Err(Error::NotFound { .. })));
+//! assert_eq!(router.static_paths(), vec!["/"]);
+//! ```
+//! Routes are matched in the order they have been added. The
+//! [ArchiveCache] given by [with_archive_cache](Router::with_archive_cache) is
+//! shared by all pages.
+
+use {
+    crate::{
+        archive::ArchiveCache,
+        page::{state::PagePreparing, Page, PageModel},
+        seo::SitemapEntry,
+        Error,
+    },
+    futures_util::future::BoxFuture,
+    std::{fmt, str::FromStr, sync::Arc},
+};
+
+type Constructor = Box<
+    dyn Fn(
+            &Params,
+            Option<Arc<ArchiveCache>>,
+        ) -> anyhow::Result<Box<dyn RoutedPage>>
+        + Send
+        + Sync,
+>;
+
+/// A segment of a route pattern.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    /// Needs to match exactly.
+    Static(String),
+    /// Matches any non empty segment and is passed as parameter.
+    Parameter(String),
+}
+
+/// A path pattern of a [Router], for example `/articles/{id}`.
+pub struct Route {
+    pattern: String,
+    segments: Vec<Segment>,
+    constructor: Constructor,
+}
+
+impl Route {
+    /// Parses the given pattern. Returns an error if the pattern does not
+    /// start with a `/`, contains an empty or duplicate parameter name or a
+    /// parameter that does not cover a whole segment.
+    fn parse(pattern: &str, constructor: Constructor) -> anyhow::Result<Self> {
+        if !pattern.starts_with('/') {
+            return Err(anyhow::anyhow!(
+                "Route \"{pattern}\" needs to start with \"/\""
+            ));
+        }
+        let mut segments = vec![];
+        for segment in split_path(pattern) {
+            let parameter =
+                segment.strip_prefix('{').and_then(|s| s.strip_suffix('}'));
+            match parameter {
+                Some(name) if name.is_empty() || name.contains(['{', '}']) => {
+                    return Err(anyhow::anyhow!(
+                        "Route \"{pattern}\" contains an invalid parameter"
+                    ));
+                }
+                Some(name) => {
+                    if segments.contains(&Segment::Parameter(name.into())) {
+                        return Err(anyhow::anyhow!(
+                            "Route \"{pattern}\" contains the parameter \"{name}\" twice"
+                        ));
+                    }
+                    segments.push(Segment::Parameter(name.into()));
+                }
+                None if segment.contains(['{', '}']) => {
+                    return Err(anyhow::anyhow!(
+                        "Parameters of route \"{pattern}\" need to cover a whole segment"
+                    ));
+                }
+                None => segments.push(Segment::Static(segment.into())),
+            }
+        }
+        Ok(Self {
+            pattern: pattern.into(),
+            segments,
+            constructor,
+        })
+    }
+
+    /// The pattern of the route, as given to [Router::with_route].
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    /// The names of the parameters of the route, in order.
+    pub fn parameters(&self) -> Vec<&str> {
+        self.segments
+            .iter()
+            .filter_map(|s| match s {
+                Segment::Parameter(p) => Some(p.as_str()),
+                Segment::Static(_) => None,
+            })
+            .collect()
+    }
+
+    /// Creates the path of the route with the given parameters, for example
+    /// `/articles/42` for `/articles/{id}` and `[("id", "42")]`. Returns
+    /// `None` if a parameter is missing.
+    pub fn path(&self, params: &[(&str, &str)]) -> Option<String> {
+        let mut path = String::new();
+        for segment in &self.segments {
+            path.push('/');
+            match segment {
+                Segment::Static(s) => path.push_str(s),
+                Segment::Parameter(name) => {
+                    let (_, value) = params.iter().find(|(n, _)| n == name)?;
+                    path.push_str(&percent_encode(value));
+                }
+            }
+        }
+        match path.is_empty() {
+            true => Some(String::from("/")),
+            false => Some(path),
+        }
+    }
+
+    /// Returns the parameters if the given path matches the route.
+    fn matches(&self, path: &str) -> Option<Params> {
+        let segments = split_path(path).collect::<Vec<_>>();
+        if segments.len() != self.segments.len() {
+            return None;
+        }
+        let mut params = vec![];
+        for (segment, expected) in segments.iter().zip(&self.segments) {
+            match expected {
+                Segment::Static(s) if s == segment => (),
+                Segment::Static(_) => return None,
+                Segment::Parameter(name) => {
+                    params.push((name.clone(), percent_decode(segment)?))
+                }
+            }
+        }
+        Some(Params(params))
+    }
+}
+
+impl fmt::Debug for Route {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Route")
+            .field("pattern", &self.pattern)
+            .finish()
+    }
+}
+
+/// The parameters of a matched [Route].
+#[derive(Debug, Clone, Default)]
+pub struct Params(Vec<(String, String)>);
+
+impl Params {
+    /// Returns the raw, percent decoded value of the given parameter.
+    pub fn raw(&self, name: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Converts the given parameter to the requested type. If the parameter
+    /// is not available or can not be converted, a [ParamError] is returned.
+    /// Returning it from the constructor of the page results in
+    /// [Error::NotFound].
+    pub fn get<T: FromStr>(&self, name: &str) -> Result<T, ParamError> {
+        let value = self.raw(name).ok_or_else(|| ParamError {
+            name: name.into(),
+            value: None,
+        })?;
+        value.parse().map_err(|_| ParamError {
+            name: name.into(),
+            value: Some(value.into()),
+        })
+    }
+}
+
+/// A parameter of a [Route] that is missing or has an invalid value.
+#[derive(Debug)]
+pub struct ParamError {
+    /// The name of the parameter.
+    pub name: String,
+    /// The value of the parameter, if available.
+    pub value: Option<String>,
+}
+
+impl fmt::Display for ParamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.value {
+            Some(v) => write!(
+                f,
+                "Invalid value \"{v}\" of parameter \"{}\"",
+                self.name
+            ),
+            None => write!(f, "Missing parameter \"{}\"", self.name),
+        }
+    }
+}
+
+impl std::error::Error for ParamError {}
+
+/// A page created by a [Route], ready to be run.
+pub trait RoutedPage: Send {
+    /// Runs the page using [Page::main] and renders it.
+    fn render(self: Box<Self>) -> Result<String, Error>;
+    /// Runs the page using [Page::main_async] and renders it.
+    fn render_async(
+        self: Box<Self>,
+    ) -> BoxFuture<'static, Result<String, Error>>;
+    /// Creates the [SitemapEntry] of the page, see [SitemapEntry::from_page].
+    fn sitemap_entry(&self) -> Option<SitemapEntry>;
+}
+
+impl<P: PageModel + Send + Sync + 'static> RoutedPage
+    for Page<P, PagePreparing>
+{
+    fn render(self: Box<Self>) -> Result<String, Error> {
+        self.main()?.render()
+    }
+
+    fn render_async(
+        self: Box<Self>,
+    ) -> BoxFuture<'static, Result<String, Error>> {
+        Box::pin(async move { self.main_async().await?.render() })
+    }
+
+    fn sitemap_entry(&self) -> Option<SitemapEntry> {
+        SitemapEntry::from_page(self.model())
+    }
+}
+
+/// Maps request paths to pages. See the [module documentation](self) for an
+/// example.
+#[derive(Default)]
+pub struct Router {
+    archive_cache: Option<Arc<ArchiveCache>>,
+    routes: Vec<Route>,
+}
+
+impl Router {
+    /// Attaches the given [ArchiveCache] to every page that is created.
+    pub fn with_archive_cache(
+        mut self,
+        archive_cache: Arc<ArchiveCache>,
+    ) -> Self {
+        self.archive_cache = Some(archive_cache);
+        self
+    }
+
+    /// Adds a route with the given pattern, for example `/articles/{id}`. The
+    /// constructor creates the model of the page from the parameters of the
+    /// matched path.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pattern is invalid, for example if it does not start with
+    /// a `/` or contains the same parameter twice.
+    pub fn with_route<P, F>(mut self, pattern: &str, constructor: F) -> Self
+    where
+        P: PageModel + Send + Sync + 'static,
+        F: Fn(&Params) -> anyhow::Result<P> + Send + Sync + 'static,
+    {
+        let constructor: Constructor =
+            Box::new(move |params, archive_cache| {
+                let page = Page::from(constructor(params)?);
+                Ok(match archive_cache {
+                    Some(c) => Box::new(page.with_archive_cache(c)),
+                    None => Box::new(page),
+                })
+            });
+        match Route::parse(pattern, constructor) {
+            Ok(r) => self.routes.push(r),
+            Err(e) => panic!("Invalid route: {e}"),
+        }
+        self
+    }
+
+    /// Returns all routes in the order they are matched.
+    pub fn routes(&self) -> &[Route] {
+        &self.routes
+    }
+
+    /// Returns the paths of all routes without parameters, for example to
+    /// add them to a sitemap or a [StaticSite](crate::export::StaticSite).
+    pub fn static_paths(&self) -> Vec<String> {
+        self.routes
+            .iter()
+            .filter(|r| r.parameters().is_empty())
+            .filter_map(|r| r.path(&[]))
+            .collect()
+    }
+
+    /// Creates the page for the given path, without running it. The query
+    /// string of the path is ignored.
+    ///
+    /// Returns [Error::NotFound] if no route matches, or if the constructor of
+    /// the matched route fails with a [ParamError]. In the latter case, the
+    /// following routes are tried. Other errors of the constructor are
+    /// returned as [Error::Route].
+    pub fn page(&self, path: &str) -> Result<Box<dyn RoutedPage>, Error> {
+        let path = path.split(['?', '#']).next().unwrap_or_default();
+        for route in &self.routes {
+            let params = match route.matches(path) {
+                Some(p) => p,
+                None => continue,
+            };
+            log::debug!("Path \"{path}\" matches route \"{}\"", route.pattern);
+            match (route.constructor)(&params, self.archive_cache.clone()) {
+                Ok(p) => return Ok(p),
+                Err(e) if e.is::<ParamError>() => {
+                    log::debug!(
+                        "Route \"{}\" does not match: {e}",
+                        route.pattern
+                    );
+                    continue;
+                }
+                Err(source) => {
+                    return Err(Error::Route {
+                        pattern: route.pattern.clone(),
+                        source,
+                    })
+                }
+            }
+        }
+        Err(Error::NotFound { path: path.into() })
+    }
+
+    /// Creates, runs and renders the page for the given path using
+    /// [Page::main].
+    pub fn render(&self, path: &str) -> Result<String, Error> {
+        self.page(path)?.render()
+    }
+
+    /// The asynchronous variant of [render](Self::render) that uses
+    /// [Page::main_async].
+    pub async fn render_async(&self, path: &str) -> Result<String, Error> {
+        self.page(path)?.render_async().await
+    }
+}
+
+/// Splits the given path into its non empty segments.
+fn split_path(path: &str) -> impl Iterator<Item = &str> {
+    path.split('/').filter(|s| !s.is_empty())
+}
+
+/// Decodes `%XX` sequences of the given segment. Returns `None` if the result
+/// is not valid UTF-8.
+fn percent_decode(segment: &str) -> Option<String> {
+    let bytes = segment.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|h| std::str::from_utf8(h).ok())
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(b)) => {
+                decoded.push(b);
+                i += 3;
+            }
+            (b, _) => {
+                decoded.push(b);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
+/// Encodes all characters of the given value that are not allowed in a path
+/// segment.
+fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for b in value.bytes() {
+        match b {
+            b'A'..=b'Z'
+            | b'a'..=b'z'
+            | b'0'..=b'9'
+            | b'-'
+            | b'.'
+            | b'_'
+            | b'~' => encoded.push(b as char),
+            _ => encoded.push_str(&format!("%{b:02X}")),
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::page::PageId};
+
+    struct TestPage(String);
+
+    impl PageModel for TestPage {
+        fn id(&self) -> PageId {
+            "test".into()
+        }
+        fn title(&self) -> String {
+            self.0.clone()
+        }
+    }
+
+    fn router() -> Router {
+        Router::default()
+            .with_route("/", |_| Ok(TestPage("home".into())))
+            .with_route("/users/{id}", |p| {
+                Ok(TestPage(format!("user {}", p.get::<u32>("id")?)))
+            })
+            .with_route("/users/{name}", |p| {
+                Ok(TestPage(format!("name {}", p.get::<String>("name")?)))
+            })
+            .with_route("/fail", |_| -> anyhow::Result<TestPage> {
+                Err(anyhow::anyhow!("database unavailable"))
+            })
+    }
+
+    #[test]
+    fn match_routes() {
+        let router = router();
+        assert_eq!(router.routes().len(), 4);
+        assert!(router.render("/").unwrap().contains("<title>home</title>"));
+        assert!(router
+            .render("/users/7/?tab=posts")
+            .unwrap()
+            .contains("<title>user 7</title>"));
+        assert!(router
+            .render("/users/J%C3%BCrgen")
+            .unwrap()
+            .contains("<title>name Jürgen</title>"));
+        assert!(matches!(
+            router.render("/users/7/posts"),
+            Err(Error::NotFound { .. })
+        ));
+        assert!(matches!(
+            router.render("/fail"),
+            Err(Error::Route { pattern, .. }) if pattern == "/fail"
+        ));
+    }
+
+    #[test]
+    fn list_routes() {
+        let router = router();
+        assert_eq!(router.static_paths(), vec!["/", "/fail"]);
+        let users = &router.routes()[1];
+        assert_eq!(users.pattern(), "/users/{id}");
+        assert_eq!(users.parameters(), vec!["id"]);
+        assert_eq!(
+            users.path(&[("id", "a b")]).as_deref(),
+            Some("/users/a%20b")
+        );
+        assert_eq!(users.path(&[]), None);
+    }
+
+    #[test]
+    fn render_async() {
+        let html =
+            futures_executor::block_on(router().render_async("/users/3"))
+                .unwrap();
+        assert!(html.contains("<title>user 3</title>"));
+    }
+
+    #[test]
+    fn reject_invalid_patterns() {
+        for pattern in ["invalid", "/a/{x}/{x}", "/a/b{x}", "/a/{}"] {
+            let result = std::panic::catch_unwind(|| {
+                Router::default()
+                    .with_route(pattern, |_| Ok(TestPage("invalid".into())))
+            });
+            assert!(result.is_err(), "{pattern} has been accepted");
+        }
+    }
+}
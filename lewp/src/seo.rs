@@ -0,0 +1,38 @@
+//! Search engine optimization of your site, like the structured
+//! [Metadata] of your pages and the `sitemap.xml` and `robots.txt` that are
+//! generated from them.
+
+mod metadata;
+mod robots;
+mod sitemap;
+
+pub(crate) use metadata::remove_duplicates;
+pub use {
+    metadata::{
+        ImagePreview,
+        Metadata,
+        OpenGraph,
+        OpenGraphImage,
+        RobotsDirective,
+        TwitterCard,
+        TwitterCardType,
+    },
+    robots::RobotsTxt,
+    sitemap::{Sitemap, SitemapEntry},
+};
+
+/// Escapes the given text to be used as content or attribute value in `XML`.
+pub(crate) fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,660 @@
+use {
+    crate::{
+        html::{
+            api::{custom, meta, text},
+            Node,
+            NodeExt,
+            NodeList,
+        },
+        page::PageModel,
+    },
+    std::fmt,
+};
+
+/// Structured metadata of a page that is rendered into its `<head>`, see
+/// [PageModel::metadata].
+///
+/// Open Graph values that are not set are taken from the page, for example
+/// `og:title` from [PageModel::title] and `og:url` from
+/// [PageModel::canonical_url].
+/// ```rust
+/// # use lewp::{
+/// #     page::{Page, PageId, PageModel},
+/// #     seo::{Metadata, OpenGraph, OpenGraphImage, RobotsDirective, TwitterCard, TwitterCardType},
+/// #     serde_json::json,
+/// # };
+/// struct ArticlePage;
+///
+/// impl PageModel for ArticlePage {
+///     fn id(&self) -> PageId {
+///         "article".into()
+///     }
+///     fn title(&self) -> String {
+///         "Rust & the web".into()
+///     }
+///     fn canonical_url(&self) -> Option<String> {
+///         Some("https://example.com/articles/rust".into())
+///     }
+///     fn metadata(&self) -> Metadata {
+///         Metadata::default()
+///             .with_robots(RobotsDirective::NoArchive)
+///             .with_open_graph(
+///                 OpenGraph::default()
+///                     .with_kind("article")
+///                     .with_image(
+///                         OpenGraphImage::new("https://example.com/rust.png")
+///                             .with_size(1200, 630)
+///                             .with_alt("The Rust logo"),
+///                     ),
+///             )
+///             .with_twitter_card(
+///                 TwitterCard::new(TwitterCardType::SummaryLargeImage)
+///                     .with_site("@rustlang"),
+///             )
+///             .with_structured_data(json!({
+///                 "@context": "https://schema.org",
+///                 "@type": "Article",
+///                 "headline": "Rust & the web </script>",
+///             }))
+///     }
+/// }
+///
+/// let html = Page::from(ArticlePage).main().unwrap().render().unwrap();
+/// assert!(html.contains(r#"<link rel="canonical" href="https://example.com/articles/rust">"#));
+/// assert!(html.contains(r#"<meta name="robots" content="noarchive">"#));
+/// assert!(html.contains(r#"<meta property="og:title" content="Rust &amp; the web">"#));
+/// assert!(html.contains(r#"<meta property="og:image:width" content="1200">"#));
+/// assert!(html.contains(r#"<meta name="twitter:card" content="summary_large_image">"#));
+/// assert!(html.contains(r#""headline":"Rust \u0026 the web \u003c/script\u003e""#));
+/// ```
+///
+/// Tags that are also returned by [PageModel::head] are not generated, the
+/// nodes of [head](PageModel::head) take precedence and a warning is logged.
+#[derive(Debug, Clone, Default)]
+pub struct Metadata {
+    /// The directives of the `<meta name="robots">` tag.
+    pub robots: Vec<RobotsDirective>,
+    /// The [Open Graph](https://ogp.me) metadata.
+    pub open_graph: Option<OpenGraph>,
+    /// The [Twitter card](https://developer.twitter.com/en/docs/twitter-for-websites/cards/overview/markup)
+    /// metadata.
+    pub twitter_card: Option<TwitterCard>,
+    /// [JSON-LD](https://json-ld.org) structured data objects, each rendered
+    /// into its own `<script type="application/ld+json">`.
+    pub structured_data: Vec<serde_json::Value>,
+}
+
+impl Metadata {
+    /// Adds the given robots directive.
+    pub fn with_robots(mut self, directive: RobotsDirective) -> Self {
+        self.robots.push(directive);
+        self
+    }
+
+    /// Sets the Open Graph metadata.
+    pub fn with_open_graph(mut self, open_graph: OpenGraph) -> Self {
+        self.open_graph = Some(open_graph);
+        self
+    }
+
+    /// Sets the Twitter card metadata.
+    pub fn with_twitter_card(mut self, twitter_card: TwitterCard) -> Self {
+        self.twitter_card = Some(twitter_card);
+        self
+    }
+
+    /// Adds the given JSON-LD structured data object.
+    pub fn with_structured_data(mut self, data: serde_json::Value) -> Self {
+        self.structured_data.push(data);
+        self
+    }
+
+    /// True if search engines are not allowed to index the page.
+    pub fn is_noindex(&self) -> bool {
+        self.robots.iter().any(|r| {
+            matches!(r, RobotsDirective::NoIndex | RobotsDirective::None)
+        })
+    }
+
+    /// Creates the nodes of the metadata of the given page.
+    pub(crate) fn to_nodes<P: PageModel>(&self, page: &P) -> NodeList {
+        let mut nodes = NodeList::new();
+        let canonical_url = page.canonical_url();
+        if let Some(url) = &canonical_url {
+            nodes.push(
+                custom("link", vec![])
+                    .attrs(vec![("rel", "canonical"), ("href", url)]),
+            );
+        }
+        if !self.robots.is_empty() {
+            let robots = self
+                .robots
+                .iter()
+                .map(|r| r.to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            nodes.push(named_meta("robots", &robots));
+        }
+        if let Some(og) = &self.open_graph {
+            nodes.append(&mut og.to_nodes(page, canonical_url));
+        }
+        if let Some(twitter) = &self.twitter_card {
+            nodes.append(&mut twitter.to_nodes());
+        }
+        for data in &self.structured_data {
+            nodes.push(json_ld(data));
+        }
+        nodes
+    }
+}
+
+/// A directive of the `<meta name="robots">` tag, see the
+/// [Google documentation](https://developers.google.com/search/docs/crawling-indexing/robots-meta-tag#directives).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RobotsDirective {
+    /// `noindex`, the page is not shown in search results. Pages with this
+    /// directive are not added to the [Sitemap](super::Sitemap).
+    NoIndex,
+    /// `nofollow`, the links on the page are not followed.
+    NoFollow,
+    /// `none`, equivalent to [NoIndex](Self::NoIndex) and
+    /// [NoFollow](Self::NoFollow).
+    None,
+    /// `noarchive`, no cached copy of the page is shown.
+    NoArchive,
+    /// `nosnippet`, no text snippet or video preview is shown.
+    NoSnippet,
+    /// `noimageindex`, the images of the page are not indexed.
+    NoImageIndex,
+    /// `notranslate`, no translation of the page is offered.
+    NoTranslate,
+    /// `max-snippet`, the maximum number of characters of a text snippet.
+    MaxSnippet(i32),
+    /// `max-image-preview`, the maximum size of an image preview.
+    MaxImagePreview(ImagePreview),
+    /// `max-video-preview`, the maximum number of seconds of a video preview.
+    MaxVideoPreview(i32),
+    /// `unavailable_after`, the date after which the page is not shown in
+    /// search results anymore, for example `2025-12-31`.
+    UnavailableAfter(String),
+}
+
+impl fmt::Display for RobotsDirective {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoIndex => write!(f, "noindex"),
+            Self::NoFollow => write!(f, "nofollow"),
+            Self::None => write!(f, "none"),
+            Self::NoArchive => write!(f, "noarchive"),
+            Self::NoSnippet => write!(f, "nosnippet"),
+            Self::NoImageIndex => write!(f, "noimageindex"),
+            Self::NoTranslate => write!(f, "notranslate"),
+            Self::MaxSnippet(n) => write!(f, "max-snippet:{n}"),
+            Self::MaxImagePreview(p) => write!(f, "max-image-preview:{p}"),
+            Self::MaxVideoPreview(n) => write!(f, "max-video-preview:{n}"),
+            Self::UnavailableAfter(d) => write!(f, "unavailable_after:{d}"),
+        }
+    }
+}
+
+/// The values of [RobotsDirective::MaxImagePreview].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImagePreview {
+    /// No image preview.
+    None,
+    /// A default image preview.
+    Standard,
+    /// A larger image preview.
+    Large,
+}
+
+impl fmt::Display for ImagePreview {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::None => write!(f, "none"),
+            Self::Standard => write!(f, "standard"),
+            Self::Large => write!(f, "large"),
+        }
+    }
+}
+
+/// The [Open Graph](https://ogp.me) metadata of a page.
+#[derive(Debug, Clone, Default)]
+pub struct OpenGraph {
+    /// `og:title`, defaults to [PageModel::title].
+    pub title: Option<String>,
+    /// `og:description`, defaults to [PageModel::description].
+    pub description: Option<String>,
+    /// `og:type`, defaults to `website`.
+    pub kind: Option<String>,
+    /// `og:url`, defaults to [PageModel::canonical_url].
+    pub url: Option<String>,
+    /// `og:site_name`.
+    pub site_name: Option<String>,
+    /// `og:image` and its properties.
+    pub image: Option<OpenGraphImage>,
+}
+
+impl OpenGraph {
+    /// Sets the title.
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Sets the description.
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Sets the type, for example `article`.
+    pub fn with_kind(mut self, kind: impl Into<String>) -> Self {
+        self.kind = Some(kind.into());
+        self
+    }
+
+    /// Sets the absolute URL.
+    pub fn with_url(mut self, url: impl Into<String>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+
+    /// Sets the name of the site.
+    pub fn with_site_name(mut self, site_name: impl Into<String>) -> Self {
+        self.site_name = Some(site_name.into());
+        self
+    }
+
+    /// Sets the image.
+    pub fn with_image(mut self, image: OpenGraphImage) -> Self {
+        self.image = Some(image);
+        self
+    }
+
+    /// Creates the nodes, `og:locale` and `og:locale:alternate` are taken from
+    /// the [languages](PageModel::languages) of the page.
+    fn to_nodes<P: PageModel>(
+        &self,
+        page: &P,
+        canonical_url: Option<String>,
+    ) -> NodeList {
+        let mut nodes = vec![
+            property_meta(
+                "og:title",
+                &self.title.clone().unwrap_or_else(|| page.title()),
+            ),
+            property_meta(
+                "og:description",
+                &self
+                    .description
+                    .clone()
+                    .unwrap_or_else(|| page.description()),
+            ),
+            property_meta("og:type", self.kind.as_deref().unwrap_or("website")),
+        ];
+        if let Some(url) = self.url.clone().or(canonical_url) {
+            nodes.push(property_meta("og:url", &url));
+        }
+        if let Some(site_name) = &self.site_name {
+            nodes.push(property_meta("og:site_name", site_name));
+        }
+        let language = page.language().to_string();
+        nodes.push(property_meta("og:locale", &open_graph_locale(&language)));
+        for l in page.languages() {
+            let l = l.to_string();
+            if l != language {
+                nodes.push(property_meta(
+                    "og:locale:alternate",
+                    &open_graph_locale(&l),
+                ));
+            }
+        }
+        if let Some(image) = &self.image {
+            nodes.push(property_meta("og:image", &image.url));
+            if let Some((width, height)) = image.size {
+                nodes.push(property_meta("og:image:width", &width.to_string()));
+                nodes.push(property_meta(
+                    "og:image:height",
+                    &height.to_string(),
+                ));
+            }
+            if let Some(alt) = &image.alt {
+                nodes.push(property_meta("og:image:alt", alt));
+            }
+        }
+        nodes
+    }
+}
+
+/// The image of the [OpenGraph] metadata.
+#[derive(Debug, Clone)]
+pub struct OpenGraphImage {
+    /// The absolute URL of the image.
+    pub url: String,
+    /// The width and height in pixels.
+    pub size: Option<(u32, u32)>,
+    /// The description of the image.
+    pub alt: Option<String>,
+}
+
+impl OpenGraphImage {
+    /// Creates a new instance with the given absolute URL.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            size: None,
+            alt: None,
+        }
+    }
+
+    /// Sets the width and height in pixels.
+    pub fn with_size(mut self, width: u32, height: u32) -> Self {
+        self.size = Some((width, height));
+        self
+    }
+
+    /// Sets the description of the image.
+    pub fn with_alt(mut self, alt: impl Into<String>) -> Self {
+        self.alt = Some(alt.into());
+        self
+    }
+}
+
+/// The types of a [TwitterCard].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TwitterCardType {
+    /// `summary`
+    Summary,
+    /// `summary_large_image`
+    SummaryLargeImage,
+    /// `app`
+    App,
+    /// `player`
+    Player,
+}
+
+impl fmt::Display for TwitterCardType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Summary => write!(f, "summary"),
+            Self::SummaryLargeImage => write!(f, "summary_large_image"),
+            Self::App => write!(f, "app"),
+            Self::Player => write!(f, "player"),
+        }
+    }
+}
+
+/// The Twitter card metadata of a page. Values that are not set are taken
+/// from the [OpenGraph] metadata by Twitter.
+#[derive(Debug, Clone)]
+pub struct TwitterCard {
+    /// `twitter:card`
+    pub card: TwitterCardType,
+    /// `twitter:site`, the account of the website, for example `@rustlang`.
+    pub site: Option<String>,
+    /// `twitter:creator`, the account of the author.
+    pub creator: Option<String>,
+    /// `twitter:title`
+    pub title: Option<String>,
+    /// `twitter:description`
+    pub description: Option<String>,
+    /// `twitter:image`, the absolute URL of the image.
+    pub image: Option<String>,
+    /// `twitter:image:alt`
+    pub image_alt: Option<String>,
+}
+
+impl TwitterCard {
+    /// Creates a new card of the given type.
+    pub fn new(card: TwitterCardType) -> Self {
+        Self {
+            card,
+            site: None,
+            creator: None,
+            title: None,
+            description: None,
+            image: None,
+            image_alt: None,
+        }
+    }
+
+    /// Sets the account of the website.
+    pub fn with_site(mut self, site: impl Into<String>) -> Self {
+        self.site = Some(site.into());
+        self
+    }
+
+    /// Sets the account of the author.
+    pub fn with_creator(mut self, creator: impl Into<String>) -> Self {
+        self.creator = Some(creator.into());
+        self
+    }
+
+    /// Sets the title.
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Sets the description.
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Sets the absolute URL of the image and its description.
+    pub fn with_image(
+        mut self,
+        url: impl Into<String>,
+        alt: Option<String>,
+    ) -> Self {
+        self.image = Some(url.into());
+        self.image_alt = alt;
+        self
+    }
+
+    fn to_nodes(&self) -> NodeList {
+        let mut nodes =
+            vec![named_meta("twitter:card", &self.card.to_string())];
+        let values = [
+            ("twitter:site", &self.site),
+            ("twitter:creator", &self.creator),
+            ("twitter:title", &self.title),
+            ("twitter:description", &self.description),
+            ("twitter:image", &self.image),
+            ("twitter:image:alt", &self.image_alt),
+        ];
+        for (name, value) in values {
+            if let Some(v) = value {
+                nodes.push(named_meta(name, v));
+            }
+        }
+        nodes
+    }
+}
+
+/// Returns the key that identifies the metadata the given node provides, for
+/// example `meta:og:title` or `link:canonical`. Nodes with the same key are
+/// duplicates.
+pub(crate) fn metadata_key(node: &Node) -> Option<String> {
+    match node.tag_name()?.as_str() {
+        "title" => Some(String::from("title")),
+        "meta" if node.attribute_value("charset").is_some() => {
+            Some(String::from("meta:charset"))
+        }
+        "meta" => {
+            let name = node
+                .attribute_value("name")
+                .or_else(|| node.attribute_value("property"))?
+                .to_lowercase();
+            match name.as_str() {
+                // multiple values are allowed
+                "og:locale:alternate" => None,
+                // the image properties belong to the image
+                n if n.starts_with("og:image:") => {
+                    Some(String::from("meta:og:image"))
+                }
+                _ => Some(format!("meta:{name}")),
+            }
+        }
+        "link" => match node.attribute_value("rel")?.to_lowercase().as_str() {
+            "canonical" => Some(String::from("link:canonical")),
+            "alternate" => Some(format!(
+                "link:alternate:{}",
+                node.attribute_value("hreflang")?.to_lowercase()
+            )),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Removes all nodes from `generated` that provide metadata that is already
+/// available in `existing`.
+pub(crate) fn remove_duplicates(
+    existing: &[Node],
+    generated: NodeList,
+) -> NodeList {
+    let existing = existing.iter().filter_map(metadata_key).collect::<Vec<_>>();
+    generated
+        .into_iter()
+        .filter(|node| match metadata_key(node) {
+            Some(key) if existing.contains(&key) => {
+                log::warn!(
+                    "Skipping generated metadata \"{key}\", it is already provided by PageModel::head"
+                );
+                false
+            }
+            _ => true,
+        })
+        .collect()
+}
+
+fn named_meta(name: &str, content: &str) -> Node {
+    meta().attrs(vec![("name", name), ("content", content)])
+}
+
+fn property_meta(property: &str, content: &str) -> Node {
+    meta().attrs(vec![("property", property), ("content", content)])
+}
+
+/// Creates the `<script>` of the given JSON-LD object. The characters that
+/// could end the script are escaped.
+fn json_ld(data: &serde_json::Value) -> Node {
+    let json = data
+        .to_string()
+        .replace('<', "\\u003c")
+        .replace('>', "\\u003e")
+        .replace('&', "\\u0026");
+    custom("script", vec![text(json)]).attr("type", "application/ld+json")
+}
+
+/// Converts the given language tag into the format of `og:locale`, for example
+/// `de-AT` becomes `de_AT`.
+fn open_graph_locale(language: &str) -> String {
+    language.replace('-', "_")
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            html::api::{charset, description, link, title},
+            page::PageId,
+            Charset,
+            LanguageTag,
+        },
+    };
+
+    struct TestPage;
+
+    impl PageModel for TestPage {
+        fn id(&self) -> PageId {
+            "test".into()
+        }
+        fn language(&self) -> LanguageTag<'_> {
+            LanguageTag::parse("de-AT").unwrap()
+        }
+        fn languages(&self) -> Vec<LanguageTag<'_>> {
+            vec![
+                LanguageTag::parse("en").unwrap(),
+                LanguageTag::parse("de-AT").unwrap(),
+            ]
+        }
+    }
+
+    #[test]
+    fn detect_duplicates() {
+        let existing = vec![
+            charset(&Charset::Utf8),
+            title("title"),
+            description("description"),
+            meta().attrs(vec![("property", "OG:IMAGE"), ("content", "a.png")]),
+            link("", "https://example.com").attr("rel", "canonical"),
+        ];
+        let generated = Metadata::default()
+            .with_open_graph(
+                OpenGraph::default()
+                    .with_image(OpenGraphImage::new("b.png").with_size(1, 1)),
+            )
+            .to_nodes(&TestPage);
+        let keys = |nodes: &NodeList| {
+            nodes.iter().filter_map(metadata_key).collect::<Vec<_>>()
+        };
+        assert_eq!(
+            keys(&generated),
+            vec![
+                "meta:og:title",
+                "meta:og:description",
+                "meta:og:type",
+                "meta:og:locale",
+                "meta:og:image",
+                "meta:og:image",
+                "meta:og:image",
+            ]
+        );
+        // og:locale:alternate is allowed more than once
+        assert_eq!(generated.len(), 8);
+        let generated = remove_duplicates(&existing, generated);
+        assert_eq!(
+            keys(&generated),
+            vec![
+                "meta:og:title",
+                "meta:og:description",
+                "meta:og:type",
+                "meta:og:locale"
+            ]
+        );
+        assert_eq!(
+            remove_duplicates(
+                &existing,
+                vec![
+                    charset(&Charset::Utf8),
+                    title("other"),
+                    description("other")
+                ]
+            )
+            .len(),
+            0
+        );
+    }
+
+    #[test]
+    fn render_robots_directives() {
+        let metadata = Metadata::default()
+            .with_robots(RobotsDirective::NoFollow)
+            .with_robots(RobotsDirective::MaxImagePreview(ImagePreview::Large))
+            .with_robots(RobotsDirective::MaxSnippet(-1));
+        assert!(!metadata.is_noindex());
+        let nodes = metadata.to_nodes(&TestPage);
+        assert_eq!(
+            nodes[0].attribute_value("content").as_deref(),
+            Some("nofollow, max-image-preview:large, max-snippet:-1")
+        );
+        assert!(Metadata::default()
+            .with_robots(RobotsDirective::None)
+            .is_noindex());
+    }
+}
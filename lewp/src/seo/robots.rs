@@ -0,0 +1,101 @@
+/// A rule of a [RobotsTxt] group.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Rule {
+    Allow(String),
+    Disallow(String),
+}
+
+/// Generates the [robots.txt](https://www.rfc-editor.org/rfc/rfc9309) of your
+/// site.
+///
+/// Rules are grouped by user agent, in the order the user agents have been
+/// added first.
+/// ```rust
+/// # use lewp::seo::RobotsTxt;
+/// let robots = RobotsTxt::default()
+///     .with_disallow("*", "/admin/")
+///     .with_allow("*", "/admin/public/")
+///     .with_disallow("ExampleBot", "/")
+///     .with_sitemap("https://example.com/sitemap.xml")
+///     .to_string();
+/// assert_eq!(
+///     robots,
+///     "User-agent: *
+/// Disallow: /admin/
+/// Allow: /admin/public/
+///
+/// User-agent: ExampleBot
+/// Disallow: /
+///
+/// Sitemap: https://example.com/sitemap.xml
+/// "
+/// );
+/// ```
+/// If no rule has been added, all user agents are allowed to crawl the site.
+#[derive(Debug, Clone, Default)]
+pub struct RobotsTxt {
+    groups: Vec<(String, Vec<Rule>)>,
+    sitemaps: Vec<String>,
+}
+
+impl RobotsTxt {
+    /// Allows the given user agent to crawl the given path.
+    pub fn with_allow(
+        self,
+        user_agent: impl Into<String>,
+        path: impl Into<String>,
+    ) -> Self {
+        self.with_rule(user_agent.into(), Rule::Allow(path.into()))
+    }
+
+    /// Disallows the given user agent to crawl the given path.
+    pub fn with_disallow(
+        self,
+        user_agent: impl Into<String>,
+        path: impl Into<String>,
+    ) -> Self {
+        self.with_rule(user_agent.into(), Rule::Disallow(path.into()))
+    }
+
+    /// Adds the absolute URL of a sitemap, see
+    /// [Sitemap](super::Sitemap).
+    pub fn with_sitemap(mut self, url: impl Into<String>) -> Self {
+        self.sitemaps.push(url.into());
+        self
+    }
+
+    fn with_rule(mut self, user_agent: String, rule: Rule) -> Self {
+        match self.groups.iter_mut().find(|(u, _)| *u == user_agent) {
+            Some((_, rules)) => rules.push(rule),
+            None => self.groups.push((user_agent, vec![rule])),
+        }
+        self
+    }
+}
+
+impl std::fmt::Display for RobotsTxt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.groups.is_empty() {
+            writeln!(f, "User-agent: *\nAllow: /")?;
+        }
+        for (i, (user_agent, rules)) in self.groups.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            writeln!(f, "User-agent: {user_agent}")?;
+            for rule in rules {
+                match rule {
+                    Rule::Allow(p) => writeln!(f, "Allow: {p}")?,
+                    Rule::Disallow(p) => writeln!(f, "Disallow: {p}")?,
+                }
+            }
+        }
+        if !self.sitemaps.is_empty() {
+            writeln!(f)?;
+        }
+        for sitemap in &self.sitemaps {
+            writeln!(f, "Sitemap: {sitemap}")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,285 @@
+use {
+    super::escape_xml,
+    crate::{
+        page::{alternate_urls, LocalizedPageModel, PageModel},
+        router::Router,
+    },
+    std::fmt::Write,
+};
+
+/// A page in a [Sitemap].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SitemapEntry {
+    /// The absolute URL of the page.
+    pub location: String,
+    /// The date of the last modification in
+    /// [W3C Datetime](https://www.w3.org/TR/NOTE-datetime) format.
+    pub last_modified: Option<String>,
+    /// The `hreflang` and absolute URL of every language version of the page,
+    /// including the page itself.
+    pub alternates: Vec<(String, String)>,
+}
+
+impl SitemapEntry {
+    /// Creates a new entry with the given absolute URL.
+    pub fn new(location: impl Into<String>) -> Self {
+        Self {
+            location: location.into(),
+            last_modified: None,
+            alternates: vec![],
+        }
+    }
+
+    /// Creates the entry of the given page from its
+    /// [canonical_url](PageModel::canonical_url),
+    /// [last_modified](PageModel::last_modified) and
+    /// [languages](PageModel::languages). Returns `None` if the page does not
+    /// have a canonical URL or must not be indexed, see
+    /// [RobotsDirective::NoIndex](super::RobotsDirective::NoIndex).
+    pub fn from_page<P: PageModel>(page: &P) -> Option<Self> {
+        if page.metadata().is_noindex() {
+            return None;
+        }
+        let location = page.canonical_url()?;
+        Some(Self {
+            location,
+            last_modified: page.last_modified(),
+            alternates: alternate_urls(page),
+        })
+    }
+
+    /// Sets the date of the last modification.
+    pub fn with_last_modified(
+        mut self,
+        last_modified: impl Into<String>,
+    ) -> Self {
+        self.last_modified = Some(last_modified.into());
+        self
+    }
+
+    /// Adds a language version of the page.
+    pub fn with_alternate(
+        mut self,
+        hreflang: impl Into<String>,
+        url: impl Into<String>,
+    ) -> Self {
+        self.alternates.push((hreflang.into(), url.into()));
+        self
+    }
+}
+
+/// Generates the [sitemap.xml](https://www.sitemaps.org/protocol.html) of
+/// your site.
+///
+/// The language versions of a page are added as
+/// `<xhtml:link rel="alternate" hreflang>`, as
+/// [recommended by Google](https://developers.google.com/search/docs/specialty/international/localized-versions#sitemap).
+/// ```rust
+/// # use lewp::{
+/// #     page::{PageId, PageModel},
+/// #     seo::{Sitemap, SitemapEntry},
+/// # };
+/// struct AboutPage;
+///
+/// impl PageModel for AboutPage {
+///     fn id(&self) -> PageId {
+///         "about".into()
+///     }
+///     fn canonical_url(&self) -> Option<String> {
+///         Some("https://example.com/about".into())
+///     }
+///     fn last_modified(&self) -> Option<String> {
+///         Some("2023-04-01".into())
+///     }
+/// }
+///
+/// let sitemap = Sitemap::default()
+///     .with_page(&AboutPage)
+///     .with_entry(SitemapEntry::new("https://example.com/imprint"))
+///     .to_xml();
+/// assert!(sitemap.contains(
+///     "<url><loc>https://example.com/about</loc><lastmod>2023-04-01</lastmod></url>"
+/// ));
+/// assert!(sitemap.contains("<url><loc>https://example.com/imprint</loc></url>"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Sitemap {
+    entries: Vec<SitemapEntry>,
+}
+
+impl Sitemap {
+    /// Adds the given entry. An entry with the same location is replaced.
+    pub fn with_entry(mut self, entry: SitemapEntry) -> Self {
+        match self
+            .entries
+            .iter_mut()
+            .find(|e| e.location == entry.location)
+        {
+            Some(e) => *e = entry,
+            None => self.entries.push(entry),
+        }
+        self
+    }
+
+    /// Adds the given page, see [SitemapEntry::from_page]. Pages without a
+    /// canonical URL and pages that must not be indexed are skipped.
+    pub fn with_page<P: PageModel>(self, page: &P) -> Self {
+        match SitemapEntry::from_page(page) {
+            Some(e) => self.with_entry(e),
+            None => {
+                log::debug!(
+                    "Page \"{}\" has no canonical URL or is not indexed, it is not added to the sitemap",
+                    page.id()
+                );
+                self
+            }
+        }
+    }
+
+    /// Adds the given page in all of its [languages](PageModel::languages),
+    /// see [Page::localized](crate::page::Page::localized).
+    pub fn with_localized_page<P: LocalizedPageModel>(
+        mut self,
+        page: &P,
+    ) -> Self {
+        for language in page.languages() {
+            self = self.with_page(&page.localize(&language));
+        }
+        self
+    }
+
+    /// Adds the pages of the given [Router] at the given paths, see
+    /// [StaticSite::with_router](crate::export::StaticSite::with_router).
+    /// Paths that do not match a route are skipped and logged.
+    pub fn with_router(
+        mut self,
+        router: &Router,
+        paths: impl IntoIterator<Item = String>,
+    ) -> Self {
+        for path in paths {
+            match router.page(&path) {
+                Ok(p) => match p.sitemap_entry() {
+                    Some(e) => self = self.with_entry(e),
+                    None => log::debug!(
+                        "Page at \"{path}\" has no canonical URL or is not indexed, it is not added to the sitemap"
+                    ),
+                },
+                Err(e) => log::error!("Could not add \"{path}\" to the sitemap: {e}"),
+            }
+        }
+        self
+    }
+
+    /// Returns all entries.
+    pub fn entries(&self) -> &[SitemapEntry] {
+        &self.entries
+    }
+
+    /// Creates the content of the `sitemap.xml`.
+    pub fn to_xml(&self) -> String {
+        let mut xml = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\"",
+        );
+        if self.entries.iter().any(|e| !e.alternates.is_empty()) {
+            xml.push_str(" xmlns:xhtml=\"http://www.w3.org/1999/xhtml\"");
+        }
+        xml.push_str(">\n");
+        for entry in &self.entries {
+            let _ =
+                write!(xml, "<url><loc>{}</loc>", escape_xml(&entry.location));
+            if let Some(l) = &entry.last_modified {
+                let _ = write!(xml, "<lastmod>{}</lastmod>", escape_xml(l));
+            }
+            for (hreflang, url) in &entry.alternates {
+                let _ = write!(
+                    xml,
+                    "<xhtml:link rel=\"alternate\" hreflang=\"{}\" href=\"{}\"/>",
+                    escape_xml(hreflang),
+                    escape_xml(url)
+                );
+            }
+            xml.push_str("</url>\n");
+        }
+        xml.push_str("</urlset>\n");
+        xml
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{page::PageId, LanguageTag},
+    };
+
+    struct ArticlePage {
+        language: String,
+    }
+
+    impl PageModel for ArticlePage {
+        fn id(&self) -> PageId {
+            "article".into()
+        }
+        fn language(&self) -> LanguageTag<'_> {
+            LanguageTag::parse(&self.language).unwrap()
+        }
+        fn languages(&self) -> Vec<LanguageTag<'_>> {
+            vec![
+                LanguageTag::parse("en").unwrap(),
+                LanguageTag::parse("de").unwrap(),
+            ]
+        }
+        fn language_url(&self, language: &LanguageTag) -> Option<String> {
+            Some(format!("https://example.com/{language}/article?a=1&b=2"))
+        }
+        fn canonical_url(&self) -> Option<String> {
+            self.language_url(&self.language())
+        }
+    }
+
+    impl LocalizedPageModel for ArticlePage {
+        fn localize(&self, language: &LanguageTag) -> Self {
+            Self {
+                language: language.to_string(),
+            }
+        }
+    }
+
+    #[test]
+    fn localized_sitemap() {
+        let page = ArticlePage {
+            language: "en".into(),
+        };
+        let sitemap = Sitemap::default()
+            .with_localized_page(&page)
+            .with_page(&page);
+        assert_eq!(sitemap.entries().len(), 2);
+        assert_eq!(
+            sitemap.to_xml(),
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>
+<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\" xmlns:xhtml=\"http://www.w3.org/1999/xhtml\">
+<url><loc>https://example.com/en/article?a=1&amp;b=2</loc><xhtml:link rel=\"alternate\" hreflang=\"en\" href=\"https://example.com/en/article?a=1&amp;b=2\"/><xhtml:link rel=\"alternate\" hreflang=\"de\" href=\"https://example.com/de/article?a=1&amp;b=2\"/><xhtml:link rel=\"alternate\" hreflang=\"x-default\" href=\"https://example.com/en/article?a=1&amp;b=2\"/></url>
+<url><loc>https://example.com/de/article?a=1&amp;b=2</loc><xhtml:link rel=\"alternate\" hreflang=\"en\" href=\"https://example.com/en/article?a=1&amp;b=2\"/><xhtml:link rel=\"alternate\" hreflang=\"de\" href=\"https://example.com/de/article?a=1&amp;b=2\"/><xhtml:link rel=\"alternate\" hreflang=\"x-default\" href=\"https://example.com/en/article?a=1&amp;b=2\"/></url>
+</urlset>
+"
+        );
+    }
+
+    #[test]
+    fn sitemap_from_router() {
+        let router = Router::default().with_route("/{language}/article", |p| {
+            Ok(ArticlePage {
+                language: p.get("language")?,
+            })
+        });
+        let sitemap = Sitemap::default().with_router(
+            &router,
+            ["/en/article".to_string(), "/missing".to_string()],
+        );
+        assert_eq!(sitemap.entries().len(), 1);
+        assert_eq!(
+            sitemap.entries()[0].location,
+            "https://example.com/en/article?a=1&b=2"
+        );
+    }
+}
@@ -5,38 +5,94 @@
 
 use {
     crate::{
-        component::{Component, ComponentId, ComponentModel, DependencyList},
-        html::{Node, NodeExt, NodeList},
+        archive::ArchiveCache,
+        component::{
+            lock,
+            Component,
+            ComponentDetails,
+            ComponentId,
+            ComponentModel,
+            DependencyList,
+            InstanceId,
+        },
+        html::{Node, NodeExt, NodeList, OwnedNode},
+        resources::{Localization, Messages, ResourceLevel, ResourceType},
+        Error,
+    },
+    futures_util::future::{join_all, BoxFuture},
+    std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
     },
-    std::{cell::RefCell, rc::Rc},
 };
 
+/// The attribute that holds the [ComponentId] of a component view.
+pub(crate) const COMPONENT_ID_ATTRIBUTE: &str = "data-lewp-id";
+/// The attribute that holds the [InstanceId] of a component view.
+pub(crate) const INSTANCE_ID_ATTRIBUTE: &str = "data-lewp-instance";
+
+/// The attribute that holds the client state of a component view.
+pub(crate) const CLIENT_STATE_ATTRIBUTE: &str = "data-lewp-state";
+
 /// Defines required additions for a [Node] to be a view of a component.
 pub trait ComponentView {
     /// Prepares the [Node] to be considered a view of a component if required.
     fn to_component_view(&self, id: ComponentId);
+    /// Attaches the given [InstanceId] to the view. The id is unique for every
+    /// component view on a page.
+    fn attach_instance_id(&self, instance_id: &InstanceId);
+    /// Attaches the given client state to the view. When the page is rendered,
+    /// the state is moved to a `<script type="application/json">` element.
+    fn attach_client_state(&self, state: &serde_json::Value);
 }
 
 impl ComponentView for Node {
     fn to_component_view(&self, id: ComponentId) {
         self.add_class(&id);
+        self.remove_attribute("data-lewp-type");
         self.borrow_attr("data-lewp-type", "component");
+        self.remove_attribute(COMPONENT_ID_ATTRIBUTE);
+        self.borrow_attr(COMPONENT_ID_ATTRIBUTE, &id);
+    }
+
+    fn attach_instance_id(&self, instance_id: &InstanceId) {
+        self.remove_attribute(INSTANCE_ID_ATTRIBUTE);
+        self.borrow_attr(INSTANCE_ID_ATTRIBUTE, instance_id);
+    }
+
+    fn attach_client_state(&self, state: &serde_json::Value) {
+        self.remove_attribute(CLIENT_STATE_ATTRIBUTE);
+        self.borrow_attr(CLIENT_STATE_ATTRIBUTE, &state.to_string());
     }
 }
 
 /// A complete web page view. Contains all `HTML` nodes as well as parameters
 /// required to render a valid `HTML` page.
+///
+/// The nodes are stored as [OwnedNode], so the view is [Send] and can be
+/// built inside of a multi-threaded async runtime.
 #[derive(Default)]
 pub struct PageView {
     /// The `<head>` tag content.
     ///
     /// Every entry of the [Vec] corresponds to a component. Because the component
-    /// itself cannot be stored, a [Rc] is passed to the view.
-    head: Vec<Rc<RefCell<NodeList>>>,
+    /// itself cannot be stored, an [Arc] is passed to the view.
+    head: Vec<Arc<Mutex<Vec<OwnedNode>>>>,
     /// The `<body>` tag content.
-    body: Vec<Rc<RefCell<Option<Node>>>>,
+    body: Vec<Arc<Mutex<Option<OwnedNode>>>>,
     /// The component dependency list of the page.
     dependency_list: DependencyList,
+    /// The number of instances that have been pushed for every component.
+    instance_count: HashMap<ComponentId, usize>,
+    /// The errors of all components that failed.
+    errors: Vec<Arc<Error>>,
+    /// Components that have been added using [PageView::push_async] and are
+    /// not loaded yet, together with the address of their model.
+    pending: Vec<(usize, BoxFuture<'static, Option<Arc<Error>>>)>,
+    /// The archive cache of the page, used to look up localised messages.
+    archive_cache: Option<Arc<ArchiveCache>>,
+    /// The languages of the page, without any messages.
+    localization: Localization,
 }
 
 impl PageView {
@@ -46,9 +102,92 @@ impl PageView {
         component: &mut Component<C>,
     ) -> &mut Self {
         log::debug!("Added component \"{}\"", component.id());
+        self.new_instance(component);
         component.main();
         self.body.push(component.view());
+        if let Some(e) = component.error() {
+            self.errors.push(e);
+        }
+        self.register(component);
+        self
+    }
+
+    /// Appends the component to the page view without running it. The
+    /// component is loaded by awaiting its
+    /// [main_async](crate::component::ComponentModel::main_async) method when
+    /// the page is run using
+    /// [Page::main_async](crate::page::Page::main_async). All components
+    /// that have been added this way are loaded concurrently, only instances
+    /// that share the same model are loaded one after another.
+    ///
+    /// The position of the view in the page is reserved when the component is
+    /// added. The [dependency list](ComponentModel::dependency_list) is
+    /// queried immediately, so it must not depend on the loaded data. The
+    /// same applies to the [head](ComponentModel::head) if the page is
+    /// rendered using [Page::render_stream](crate::page::Page::render_stream),
+    /// which sends it before the components are loaded.
+    pub fn push_async<C: ComponentModel + Send + 'static>(
+        &mut self,
+        component: &mut Component<C>,
+    ) -> &mut Self {
+        log::debug!("Added component \"{}\" for loading", component.id());
+        self.new_instance(component);
+        // available before loading, for streamed pages
+        component.update_head();
+        self.body.push(component.view());
+        let mut instance = component.share_instance();
+        self.pending.push((
+            component.model_address(),
+            Box::pin(async move {
+                instance.main_async().await;
+                instance.error()
+            }),
+        ));
+        self.register(component);
+        self
+    }
+
+    /// Loads all components that have been added using
+    /// [push_async](Self::push_async) concurrently.
+    pub(crate) async fn load(&mut self) {
+        let mut groups: Vec<(usize, Vec<_>)> = vec![];
+        for (address, loader) in self.pending.drain(..) {
+            match groups.iter_mut().find(|(a, _)| *a == address) {
+                Some((_, loaders)) => loaders.push(loader),
+                None => groups.push((address, vec![loader])),
+            }
+        }
+        let groups = groups.into_iter().map(|(_, loaders)| async move {
+            let mut errors = vec![];
+            for loader in loaders {
+                errors.extend(loader.await);
+            }
+            errors
+        });
+        for errors in join_all(groups).await {
+            self.errors.extend(errors);
+        }
+    }
 
+    /// True if there are components that have been added using
+    /// [push_async](Self::push_async) and have not been loaded yet.
+    pub(crate) fn has_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Assigns a new [InstanceId](crate::component::InstanceId) to the given
+    /// component.
+    fn new_instance<C: ComponentModel>(
+        &mut self,
+        component: &mut Component<C>,
+    ) {
+        let count = self.instance_count.entry(component.id()).or_default();
+        *count += 1;
+        component.new_instance(format!("{}-{}", component.id(), count));
+    }
+
+    /// Registers the head nodes and the dependencies of the given component.
+    fn register<C: ComponentModel>(&mut self, component: &Component<C>) {
         if !&self.dependency_list.contains(component.id()) {
             log::debug!(
                 "Storing head tags reference for ID \"{}\"",
@@ -59,32 +198,81 @@ impl PageView {
 
         log::debug!("Processing dependencies for ID \"{}\"", component.id());
         let mut dependencies = component.dependency_list();
-        dependencies.push(component.model().id());
+        dependencies.push(component.lock_model().id());
         log::debug!(
             "Adding dependencies for component \"{}\": \"{}\"",
-            component.model().id(),
+            component.lock_model().id(),
             dependencies
         );
         self.dependency_list.append(dependencies);
-
-        self
     }
 
     /// Collects the children of the `<body>` tag of the current page view.
-    pub fn body(self) -> NodeList {
+    pub fn body(&self) -> NodeList {
         self.body
-            .into_iter()
-            .filter(|n| n.borrow().is_some())
-            .map(|n| n.borrow().as_ref().unwrap().to_owned())
+            .iter()
+            .filter_map(|n| lock(n).as_ref().map(OwnedNode::to_node))
             .collect()
     }
 
     /// Collects the children of the `<head>` tag of the current page view.
     pub fn head(&self) -> NodeList {
-        self.head.iter().fold(NodeList::new(), |mut acc, h| {
-            acc.append(&mut h.borrow_mut());
-            acc
-        })
+        self.head
+            .iter()
+            .flat_map(|h| {
+                lock(h).iter().map(OwnedNode::to_node).collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /// Returns the errors of all components that failed and have been
+    /// replaced by their
+    /// [fallback view](crate::component::ComponentModel::fallback_view).
+    pub fn errors(&self) -> impl Iterator<Item = &Error> {
+        self.errors.iter().map(|e| e.as_ref())
+    }
+
+    /// Returns the [Localization] of the given component in the languages of
+    /// the page, see [PageModel::language](crate::page::PageModel::language)
+    /// and [PageModel::fallback_languages](crate::page::PageModel::fallback_languages).
+    ///
+    /// The messages are taken from the
+    /// [ArchiveCache](crate::archive::ArchiveCache) of the page, so they need
+    /// to be loaded using
+    /// [load_text](crate::archive::ArchiveCache::load_text). If they are not
+    /// available, the returned instance falls back to the message keys.
+    pub fn localization(&self, component_id: &str) -> Localization {
+        self.localization_of(component_id, ResourceLevel::Component)
+    }
+
+    /// Like [localization](Self::localization), but returns the messages that
+    /// are stored on [page level](ResourceLevel::Page).
+    pub fn page_localization(&self, page_id: &str) -> Localization {
+        self.localization_of(page_id, ResourceLevel::Page)
+    }
+
+    fn localization_of(&self, id: &str, level: ResourceLevel) -> Localization {
+        let details =
+            ComponentDetails::new(id.into(), ResourceType::Text, level);
+        let messages = self
+            .archive_cache
+            .as_ref()
+            .and_then(|c| c.get::<Messages>(&details));
+        if messages.is_none() {
+            log::debug!("No messages available for {details:?}");
+        }
+        self.localization.clone().with_messages(messages)
+    }
+
+    /// Sets the archive cache and the languages of the page, called before
+    /// the [PageModel](crate::page::PageModel) is executed.
+    pub(crate) fn set_localization(
+        &mut self,
+        archive_cache: Option<Arc<ArchiveCache>>,
+        localization: Localization,
+    ) {
+        self.archive_cache = archive_cache;
+        self.localization = localization;
     }
 
     /// Returns a reference to the component dependency list.
@@ -92,3 +280,113 @@ impl PageView {
         &self.dependency_list
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::PageView,
+        crate::{
+            component::{Component, ComponentId, ComponentModel},
+            html::{api::p, Node, NodeExt},
+        },
+        std::{
+            future::poll_fn,
+            sync::{
+                atomic::{AtomicUsize, Ordering},
+                Arc,
+            },
+            task::Poll,
+        },
+    };
+
+    /// Records how many components have been started when it continues
+    /// after yielding once.
+    struct Loading {
+        id: &'static str,
+        started: Arc<AtomicUsize>,
+        seen: usize,
+    }
+
+    impl ComponentModel for Loading {
+        type Message = ();
+        fn id(&self) -> ComponentId {
+            self.id.into()
+        }
+        async fn main_async(&mut self) -> anyhow::Result<()> {
+            self.started.fetch_add(1, Ordering::SeqCst);
+            let mut yielded = false;
+            poll_fn(|cx| match yielded {
+                true => Poll::Ready(()),
+                false => {
+                    yielded = true;
+                    cx.waker().wake_by_ref();
+                    Poll::Pending
+                }
+            })
+            .await;
+            self.seen = self.started.load(Ordering::SeqCst);
+            Ok(())
+        }
+        fn view(&self) -> anyhow::Result<Option<Node>> {
+            Ok(Some(p(vec![]).attr("data-seen", &self.seen.to_string())))
+        }
+    }
+
+    #[test]
+    fn load_components_concurrently() {
+        let started = Arc::new(AtomicUsize::new(0));
+        let mut view = PageView::default();
+        for id in ["first", "second"] {
+            view.push_async(&mut Component::from(Loading {
+                id,
+                started: Arc::clone(&started),
+                seen: 0,
+            }));
+        }
+        assert!(view.body().is_empty());
+        futures_executor::block_on(view.load());
+        let seen = view
+            .body()
+            .iter()
+            .filter_map(|n| n.attribute_value("data-seen"))
+            .collect::<Vec<_>>();
+        assert_eq!(seen, vec!["2", "2"]);
+    }
+
+    struct Failing;
+
+    impl ComponentModel for Failing {
+        type Message = ();
+        fn id(&self) -> ComponentId {
+            "failing".into()
+        }
+        async fn main_async(&mut self) -> anyhow::Result<()> {
+            Err(anyhow::anyhow!("database unavailable"))
+        }
+        fn view(&self) -> anyhow::Result<Option<Node>> {
+            Ok(Some(p(vec![])))
+        }
+    }
+
+    #[test]
+    fn propagate_errors_of_loaded_components() {
+        let mut view = PageView::default();
+        let mut component = Component::from(Failing);
+        view.push_async(&mut component);
+        assert!(component.error().is_none());
+        futures_executor::block_on(view.load());
+        assert_eq!(view.errors().count(), 1);
+        assert!(component
+            .error()
+            .is_some_and(|e| e.to_string().contains("database unavailable")));
+        assert!(component.model().is_some());
+    }
+
+    fn assert_send<T: Send>() {}
+
+    #[test]
+    fn page_view_and_components_are_send() {
+        assert_send::<PageView>();
+        assert_send::<Component<Loading>>();
+    }
+}
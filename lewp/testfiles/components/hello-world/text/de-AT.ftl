@@ -0,0 +1 @@
+greeting = Servus { $name }!
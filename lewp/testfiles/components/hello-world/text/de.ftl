@@ -0,0 +1,2 @@
+greeting = Hallo { $name }!
+farewell = Auf Wiedersehen!
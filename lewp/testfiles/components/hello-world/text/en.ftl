@@ -0,0 +1,4 @@
+# Messages of the hello-world component.
+greeting = Hello { $name }!
+farewell = Goodbye!
+only-english = Only in English